@@ -0,0 +1,60 @@
+// Signed score updates relayed from the Cosmos Hub.
+//
+// A bridge message is the Solidity-packed encoding
+// `abi.encodePacked("CERTID_BRIDGE", uint256 chain_id, address verifier,
+// bytes32 device_id, uint256 score, uint64 seq, uint64 expiry)`,
+// i.e. exactly 145 bytes, all integers big-endian:
+//
+//   offset  size  field
+//   0       13    domain tag, the ASCII bytes "CERTID_BRIDGE"
+//   13      32    chain_id (the Arbitrum chain the verifier is deployed on)
+//   45      20    verifier (address of the target CertIDVerifier)
+//   65      32    device_id
+//   97      32    score (0–100)
+//   129     8     seq    (strictly increasing per device)
+//   137     8     expiry (unix seconds; rejected once block time passes it)
+//
+// The tag, chain id and verifier address bind a message to one deployment,
+// so a signature cannot be replayed on another chain or another verifier
+// sharing the same bridge key. `CertBridge.releaseTokens` binds `chainId`
+// into its signed hash for the same reason.
+//
+// Signing follows `BridgeValidator.handleTokenLock` in
+// `services/bridge-validator/src/validator.ts`: the key signs
+// `keccak256(solidityPacked(...))` with ethers' `signMessage`, i.e. an
+// EIP-191 `personal_sign` yielding a 65-byte `r || s || v` signature.
+
+use stylus_sdk::alloy_primitives::{Address, FixedBytes, U256};
+
+/// Domain tag every bridge message starts with.
+pub(crate) const BRIDGE_MESSAGE_TAG: &[u8] = b"CERTID_BRIDGE";
+
+/// Encoded length of a bridge message.
+pub(crate) const BRIDGE_MESSAGE_LEN: usize = 145;
+
+/// A decoded bridge message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BridgeMessage {
+    pub chain_id: U256,
+    pub verifier: Address,
+    pub device_id: FixedBytes<32>,
+    pub score: U256,
+    pub seq: u64,
+    pub expiry: u64,
+}
+
+/// Decode a bridge message, or `None` if it is not exactly 145 bytes or
+/// lacks the domain tag.
+pub(crate) fn decode_bridge_message(message: &[u8]) -> Option<BridgeMessage> {
+    if message.len() != BRIDGE_MESSAGE_LEN || !message.starts_with(BRIDGE_MESSAGE_TAG) {
+        return None;
+    }
+    Some(BridgeMessage {
+        chain_id: U256::from_be_slice(&message[13..45]),
+        verifier: Address::from_slice(&message[45..65]),
+        device_id: FixedBytes::from_slice(&message[65..97]),
+        score: U256::from_be_slice(&message[97..129]),
+        seq: u64::from_be_bytes(message[129..137].try_into().ok()?),
+        expiry: u64::from_be_bytes(message[137..145].try_into().ok()?),
+    })
+}
@@ -0,0 +1,507 @@
+// Minimal CBOR (RFC 8949) decoder for TEE attestation payloads.
+//
+// Only the subset needed to extract the fields the verifier checks is
+// supported, keeping the WASM small and the gas cost predictable:
+//
+//   attestation = {            ; definite-length map
+//     1: bstr .size 32,        ; challenge
+//     2: uint,                 ; timestamp (unix seconds)
+//     3: uint / bstr .le 32,   ; claims bitmask (bstr = big-endian)
+//     ? 4: uint,               ; Cosmos epoch produced in (absent = 0)
+//     ? 5: uint,               ; attestation type/version (absent = 0)
+//     ? 6: uint,               ; per-device attestation nonce
+//     ? 7: uint,               ; TEE monotonic hardware counter
+//     ? 8: bstr .size 32,       ; hardware model hash
+//     ? 10: uint,              ; L2 block number of the freshness beacon
+//     ? 11: bstr .size 32,      ; that block's hash, as the device saw it
+//     ? 12: bstr .size 32,      ; seed of the epoch the device attested in
+//     ? 13: uint,              ; manufacture date (unix seconds)
+//     ? 14: bstr,              ; certificate chain, 65-byte signatures leaf first
+//     * uint => simple value   ; other keys are skipped
+//   }
+//
+// "Simple value" means an unsigned/negative integer, byte string, or text
+// string. Indefinite lengths, tags, floats, and nested arrays or maps are
+// rejected, as are duplicate keys, missing fields, and trailing bytes.
+
+use stylus_sdk::alloy_primitives::{FixedBytes, U256};
+
+const MAJOR_UINT: u8 = 0;
+const MAJOR_NINT: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_MAP: u8 = 5;
+
+const KEY_CHALLENGE: u64 = 1;
+const KEY_TIMESTAMP: u64 = 2;
+const KEY_CLAIMS: u64 = 3;
+const KEY_EPOCH: u64 = 4;
+const KEY_TYPE: u64 = 5;
+const KEY_NONCE: u64 = 6;
+const KEY_HW_COUNTER: u64 = 7;
+const KEY_MODEL: u64 = 8;
+const KEY_BEACON_BLOCK: u64 = 10;
+const KEY_BEACON_HASH: u64 = 11;
+const KEY_SEED: u64 = 12;
+const KEY_MANUFACTURED_AT: u64 = 13;
+const KEY_CERT_CHAIN: u64 = 14;
+
+/// Fields decoded from an attestation payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Attestation<'a> {
+    pub challenge: FixedBytes<32>,
+    pub timestamp: u64,
+    pub claims: U256,
+    pub epoch: u64,
+    pub attestation_type: u64,
+    pub nonce: Option<u64>,
+    pub hw_counter: Option<u64>,
+    pub model: Option<FixedBytes<32>>,
+    /// `(block number, block hash)`; present only when both keys are
+    pub beacon: Option<(u64, FixedBytes<32>)>,
+    pub seed: Option<FixedBytes<32>>,
+    pub manufactured_at: Option<u64>,
+    /// Undecoded; the verifier checks the links itself
+    pub cert_chain: Option<&'a [u8]>,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(len)?;
+        let bytes = self.data.get(self.pos..end)?;
+        self.pos = end;
+        Some(bytes)
+    }
+
+    /// Read an item header, returning `(major type, argument)`.
+    fn header(&mut self) -> Option<(u8, u64)> {
+        let initial = *self.take(1)?.first()?;
+        let major = initial >> 5;
+        let argument = match initial & 0x1f {
+            info @ 0..=23 => u64::from(info),
+            24 => u64::from(self.take(1)?[0]),
+            25 => u64::from(u16::from_be_bytes(self.take(2)?.try_into().ok()?)),
+            26 => u64::from(u32::from_be_bytes(self.take(4)?.try_into().ok()?)),
+            27 => u64::from_be_bytes(self.take(8)?.try_into().ok()?),
+            // Reserved values and indefinite lengths
+            _ => return None,
+        };
+        Some((major, argument))
+    }
+
+    fn uint(&mut self) -> Option<u64> {
+        match self.header()? {
+            (MAJOR_UINT, value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        match self.header()? {
+            (MAJOR_BYTES, len) => self.take(usize::try_from(len).ok()?),
+            _ => None,
+        }
+    }
+
+    fn skip_simple(&mut self) -> Option<()> {
+        match self.header()? {
+            (MAJOR_UINT | MAJOR_NINT, _) => Some(()),
+            (MAJOR_BYTES | MAJOR_TEXT, len) => self.take(usize::try_from(len).ok()?).map(|_| ()),
+            _ => None,
+        }
+    }
+}
+
+/// Decode an attestation payload, or `None` if it is malformed.
+pub(crate) fn decode_attestation(data: &[u8]) -> Option<Attestation<'_>> {
+    let mut reader = Reader { data, pos: 0 };
+    let entries = match reader.header()? {
+        (MAJOR_MAP, entries) => entries,
+        _ => return None,
+    };
+
+    let mut challenge = None;
+    let mut timestamp = None;
+    let mut claims = None;
+    let mut epoch = None;
+    let mut attestation_type = None;
+    let mut nonce = None;
+    let mut hw_counter = None;
+    let mut model = None;
+    let mut beacon_block = None;
+    let mut beacon_hash = None;
+    let mut seed = None;
+    let mut manufactured_at = None;
+    let mut cert_chain = None;
+    for _ in 0..entries {
+        match reader.uint()? {
+            KEY_CHALLENGE if challenge.is_none() => {
+                let bytes = reader.bytes()?;
+                challenge = Some(FixedBytes::<32>::try_from(bytes).ok()?);
+            }
+            KEY_TIMESTAMP if timestamp.is_none() => timestamp = Some(reader.uint()?),
+            KEY_CLAIMS if claims.is_none() => {
+                claims = Some(match reader.header()? {
+                    (MAJOR_UINT, value) => U256::from(value),
+                    (MAJOR_BYTES, len) if len <= 32 => {
+                        U256::try_from_be_slice(reader.take(len as usize)?)?
+                    }
+                    _ => return None,
+                });
+            }
+            KEY_EPOCH if epoch.is_none() => epoch = Some(reader.uint()?),
+            KEY_TYPE if attestation_type.is_none() => attestation_type = Some(reader.uint()?),
+            KEY_NONCE if nonce.is_none() => nonce = Some(reader.uint()?),
+            KEY_HW_COUNTER if hw_counter.is_none() => hw_counter = Some(reader.uint()?),
+            KEY_MODEL if model.is_none() => {
+                model = Some(FixedBytes::<32>::try_from(reader.bytes()?).ok()?);
+            }
+            KEY_BEACON_BLOCK if beacon_block.is_none() => beacon_block = Some(reader.uint()?),
+            KEY_BEACON_HASH if beacon_hash.is_none() => {
+                beacon_hash = Some(FixedBytes::<32>::try_from(reader.bytes()?).ok()?);
+            }
+            KEY_SEED if seed.is_none() => {
+                seed = Some(FixedBytes::<32>::try_from(reader.bytes()?).ok()?);
+            }
+            KEY_MANUFACTURED_AT if manufactured_at.is_none() => {
+                manufactured_at = Some(reader.uint()?)
+            }
+            KEY_CERT_CHAIN if cert_chain.is_none() => cert_chain = Some(reader.bytes()?),
+            KEY_CHALLENGE | KEY_TIMESTAMP | KEY_CLAIMS | KEY_EPOCH | KEY_TYPE | KEY_NONCE
+            | KEY_HW_COUNTER | KEY_MODEL | KEY_BEACON_BLOCK | KEY_BEACON_HASH | KEY_SEED
+            | KEY_MANUFACTURED_AT | KEY_CERT_CHAIN => return None,
+            _ => reader.skip_simple()?,
+        }
+    }
+
+    if reader.pos != data.len() {
+        return None;
+    }
+    Some(Attestation {
+        challenge: challenge?,
+        timestamp: timestamp?,
+        claims: claims?,
+        epoch: epoch.unwrap_or(0),
+        attestation_type: attestation_type.unwrap_or(0),
+        nonce,
+        hw_counter,
+        model,
+        beacon: beacon_block.zip(beacon_hash),
+        seed,
+        manufactured_at,
+        cert_chain,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn header(out: &mut Vec<u8>, major: u8, argument: u64) {
+        let major = major << 5;
+        if argument < 24 {
+            out.push(major | argument as u8);
+        } else if argument <= u64::from(u8::MAX) {
+            out.extend_from_slice(&[major | 24, argument as u8]);
+        } else if argument <= u64::from(u16::MAX) {
+            out.push(major | 25);
+            out.extend_from_slice(&(argument as u16).to_be_bytes());
+        } else if argument <= u64::from(u32::MAX) {
+            out.push(major | 26);
+            out.extend_from_slice(&(argument as u32).to_be_bytes());
+        } else {
+            out.push(major | 27);
+            out.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+
+    /// Encode an attestation in the supported subset.
+    pub(crate) fn encode_attestation(challenge: [u8; 32], timestamp: u64, claims: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 3);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        out
+    }
+
+    /// Encode an attestation that also carries its epoch.
+    pub(crate) fn encode_attestation_at_epoch(
+        challenge: [u8; 32],
+        timestamp: u64,
+        claims: u64,
+        epoch: u64,
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 4);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        header(&mut out, MAJOR_UINT, KEY_EPOCH);
+        header(&mut out, MAJOR_UINT, epoch);
+        out
+    }
+
+    /// Encode an attestation that also carries its type.
+    pub(crate) fn encode_attestation_of_type(
+        challenge: [u8; 32],
+        timestamp: u64,
+        claims: u64,
+        attestation_type: u64,
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 4);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        header(&mut out, MAJOR_UINT, KEY_TYPE);
+        header(&mut out, MAJOR_UINT, attestation_type);
+        out
+    }
+
+    /// Encode an attestation that also carries a nonce.
+    pub(crate) fn encode_attestation_with_nonce(
+        challenge: [u8; 32],
+        timestamp: u64,
+        claims: u64,
+        nonce: u64,
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 4);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        header(&mut out, MAJOR_UINT, KEY_NONCE);
+        header(&mut out, MAJOR_UINT, nonce);
+        out
+    }
+
+    /// Encode an attestation that also carries a hardware counter.
+    pub(crate) fn encode_attestation_with_counter(
+        challenge: [u8; 32],
+        timestamp: u64,
+        claims: u64,
+        hw_counter: u64,
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 4);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        header(&mut out, MAJOR_UINT, KEY_HW_COUNTER);
+        header(&mut out, MAJOR_UINT, hw_counter);
+        out
+    }
+
+    /// Encode an attestation that also carries a hardware model hash.
+    pub(crate) fn encode_attestation_of_model(
+        challenge: [u8; 32],
+        timestamp: u64,
+        claims: u64,
+        model: [u8; 32],
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 4);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        header(&mut out, MAJOR_UINT, KEY_MODEL);
+        header(&mut out, MAJOR_BYTES, 32);
+        out.extend_from_slice(&model);
+        out
+    }
+
+    /// Encode an attestation that also carries a freshness beacon.
+    pub(crate) fn encode_attestation_with_beacon(
+        challenge: [u8; 32],
+        timestamp: u64,
+        claims: u64,
+        block: u64,
+        hash: [u8; 32],
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 5);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        header(&mut out, MAJOR_UINT, KEY_BEACON_BLOCK);
+        header(&mut out, MAJOR_UINT, block);
+        header(&mut out, MAJOR_UINT, KEY_BEACON_HASH);
+        header(&mut out, MAJOR_BYTES, 32);
+        out.extend_from_slice(&hash);
+        out
+    }
+
+    /// Encode an attestation that also carries an epoch seed.
+    pub(crate) fn encode_attestation_with_seed(
+        challenge: [u8; 32],
+        timestamp: u64,
+        claims: u64,
+        seed: [u8; 32],
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 4);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        header(&mut out, MAJOR_UINT, KEY_SEED);
+        header(&mut out, MAJOR_BYTES, 32);
+        out.extend_from_slice(&seed);
+        out
+    }
+
+    /// Encode an attestation that also carries a manufacture date.
+    pub(crate) fn encode_attestation_with_manufacture_date(
+        challenge: [u8; 32],
+        timestamp: u64,
+        claims: u64,
+        manufactured_at: u64,
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 4);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        header(&mut out, MAJOR_UINT, KEY_MANUFACTURED_AT);
+        header(&mut out, MAJOR_UINT, manufactured_at);
+        out
+    }
+
+    /// Encode an attestation that also carries a certificate chain.
+    pub(crate) fn encode_attestation_with_chain(
+        challenge: [u8; 32],
+        timestamp: u64,
+        claims: u64,
+        chain: &[u8],
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        header(&mut out, MAJOR_MAP, 4);
+        encode_fields(&mut out, challenge, timestamp, claims);
+        header(&mut out, MAJOR_UINT, KEY_CERT_CHAIN);
+        header(&mut out, MAJOR_BYTES, chain.len() as u64);
+        out.extend_from_slice(chain);
+        out
+    }
+
+    fn encode_fields(out: &mut Vec<u8>, challenge: [u8; 32], timestamp: u64, claims: u64) {
+        header(out, MAJOR_UINT, KEY_CHALLENGE);
+        header(out, MAJOR_BYTES, 32);
+        out.extend_from_slice(&challenge);
+        header(out, MAJOR_UINT, KEY_TIMESTAMP);
+        header(out, MAJOR_UINT, timestamp);
+        header(out, MAJOR_UINT, KEY_CLAIMS);
+        header(out, MAJOR_UINT, claims);
+    }
+
+    #[test]
+    fn test_decodes_valid_attestation() {
+        let data = encode_attestation([0xab; 32], 1_700_000_000, 0b1011);
+        assert_eq!(
+            decode_attestation(&data),
+            Some(Attestation {
+                challenge: FixedBytes::repeat_byte(0xab),
+                timestamp: 1_700_000_000,
+                claims: U256::from(0b1011),
+                epoch: 0,
+                attestation_type: 0,
+                nonce: None,
+                hw_counter: None,
+                model: None,
+                beacon: None,
+                seed: None,
+                manufactured_at: None,
+                cert_chain: None,
+            })
+        );
+
+        let data = encode_attestation_at_epoch([0xab; 32], 1, 0, 42);
+        assert_eq!(decode_attestation(&data).unwrap().epoch, 42);
+        let data = encode_attestation_of_type([0xab; 32], 1, 0, 3);
+        assert_eq!(decode_attestation(&data).unwrap().attestation_type, 3);
+        let data = encode_attestation_with_nonce([0xab; 32], 1, 0, 7);
+        assert_eq!(decode_attestation(&data).unwrap().nonce, Some(7));
+        let data = encode_attestation_with_counter([0xab; 32], 1, 0, 9);
+        assert_eq!(decode_attestation(&data).unwrap().hw_counter, Some(9));
+        let data = encode_attestation_of_model([0xab; 32], 1, 0, [0xcd; 32]);
+        assert_eq!(
+            decode_attestation(&data).unwrap().model,
+            Some(FixedBytes::repeat_byte(0xcd))
+        );
+        let data = encode_attestation_with_beacon([0xab; 32], 1, 0, 77, [0xbe; 32]);
+        assert_eq!(
+            decode_attestation(&data).unwrap().beacon,
+            Some((77, FixedBytes::repeat_byte(0xbe)))
+        );
+        let data = encode_attestation_with_seed([0xab; 32], 1, 0, [0x5e; 32]);
+        assert_eq!(
+            decode_attestation(&data).unwrap().seed,
+            Some(FixedBytes::repeat_byte(0x5e))
+        );
+        let data = encode_attestation_with_manufacture_date([0xab; 32], 1, 0, 1_600_000_000);
+        assert_eq!(
+            decode_attestation(&data).unwrap().manufactured_at,
+            Some(1_600_000_000)
+        );
+        let data = encode_attestation_with_chain([0xab; 32], 1, 0, &[0xc4; 130]);
+        assert_eq!(
+            decode_attestation(&data).unwrap().cert_chain,
+            Some(&[0xc4; 130][..])
+        );
+    }
+
+    #[test]
+    fn test_skips_unknown_keys_and_accepts_wide_claims() {
+        let mut data = Vec::new();
+        header(&mut data, MAJOR_MAP, 4);
+        header(&mut data, MAJOR_UINT, 9);
+        header(&mut data, MAJOR_TEXT, 5);
+        data.extend_from_slice(b"pixel");
+        header(&mut data, MAJOR_UINT, KEY_CLAIMS);
+        header(&mut data, MAJOR_BYTES, 32);
+        data.extend_from_slice(&[0xff; 32]);
+        header(&mut data, MAJOR_UINT, KEY_TIMESTAMP);
+        header(&mut data, MAJOR_UINT, 7);
+        header(&mut data, MAJOR_UINT, KEY_CHALLENGE);
+        header(&mut data, MAJOR_BYTES, 32);
+        data.extend_from_slice(&[0x01; 32]);
+
+        let attestation = decode_attestation(&data).unwrap();
+        assert_eq!(attestation.claims, U256::MAX);
+        assert_eq!(attestation.timestamp, 7);
+    }
+
+    #[test]
+    fn test_rejects_truncated_input() {
+        let data = encode_attestation([0xab; 32], 1_700_000_000, 0b1011);
+        for len in 0..data.len() {
+            assert_eq!(decode_attestation(&data[..len]), None, "prefix {len}");
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_structure() {
+        // Trailing bytes
+        let mut data = encode_attestation([0xab; 32], 1, 1);
+        data.push(0x00);
+        assert_eq!(decode_attestation(&data), None);
+
+        // Challenge of the wrong length
+        let mut data = Vec::new();
+        header(&mut data, MAJOR_MAP, 3);
+        header(&mut data, MAJOR_UINT, KEY_CHALLENGE);
+        header(&mut data, MAJOR_BYTES, 31);
+        data.extend_from_slice(&[0xab; 31]);
+        header(&mut data, MAJOR_UINT, KEY_TIMESTAMP);
+        header(&mut data, MAJOR_UINT, 1);
+        header(&mut data, MAJOR_UINT, KEY_CLAIMS);
+        header(&mut data, MAJOR_UINT, 1);
+        assert_eq!(decode_attestation(&data), None);
+
+        // Duplicate key
+        let mut data = Vec::new();
+        header(&mut data, MAJOR_MAP, 4);
+        data.extend_from_slice(&encode_attestation([0xab; 32], 1, 1)[1..]);
+        header(&mut data, MAJOR_UINT, KEY_TIMESTAMP);
+        header(&mut data, MAJOR_UINT, 2);
+        assert_eq!(decode_attestation(&data), None);
+
+        // Missing field
+        let mut data = Vec::new();
+        header(&mut data, MAJOR_MAP, 1);
+        header(&mut data, MAJOR_UINT, KEY_TIMESTAMP);
+        header(&mut data, MAJOR_UINT, 1);
+        assert_eq!(decode_attestation(&data), None);
+
+        // Indefinite-length map and a top-level array
+        assert_eq!(decode_attestation(&[0xbf, 0xff]), None);
+        assert_eq!(decode_attestation(&[0x80]), None);
+    }
+}
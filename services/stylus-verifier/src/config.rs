@@ -0,0 +1,100 @@
+// Keys identifying owner-set parameters in `ConfigChanged` events.
+//
+// Each key is the parameter's camelCase name, ASCII and right-padded with
+// zeros to 32 bytes — Solidity's `bytes32("minThreshold")` — so indexers
+// can match them without this crate. Booleans are logged as 0/1 and
+// addresses as their 160-bit value. Parameters keyed by device, class or
+// attestation type are not config and do not emit `ConfigChanged`.
+
+use stylus_sdk::alloy_primitives::{Address, FixedBytes, U256};
+
+/// Right-pad `name` to 32 bytes.
+const fn key(name: &str) -> FixedBytes<32> {
+    let name = name.as_bytes();
+    assert!(name.len() <= 32);
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < name.len() {
+        out[i] = name[i];
+        i += 1;
+    }
+    FixedBytes(out)
+}
+
+pub(crate) const SUCCESSOR: FixedBytes<32> = key("successor");
+pub(crate) const SCORE_DECREASE_ALARM: FixedBytes<32> = key("scoreDecreaseAlarm");
+pub(crate) const VERIFICATION_ENABLED: FixedBytes<32> = key("verificationEnabled");
+pub(crate) const REQUIRE_ONCHAIN_CHALLENGE: FixedBytes<32> = key("requireOnchainChallenge");
+pub(crate) const ALLOW_SELF_SIGNED: FixedBytes<32> = key("allowSelfSigned");
+pub(crate) const ALLOW_EMPTY_PAYLOAD: FixedBytes<32> = key("allowEmptyPayload");
+pub(crate) const ENFORCE_MODELS: FixedBytes<32> = key("enforceModels");
+pub(crate) const BEACON_WINDOW: FixedBytes<32> = key("beaconWindow");
+pub(crate) const REQUIRE_EPOCH_SEED: FixedBytes<32> = key("requireEpochSeed");
+pub(crate) const MIN_MANUFACTURE_DATE: FixedBytes<32> = key("minManufactureDate");
+pub(crate) const POLICY_ORACLE: FixedBytes<32> = key("policyOracle");
+pub(crate) const PRESERVE_ON_REREGISTER: FixedBytes<32> = key("preserveOnReregister");
+pub(crate) const MY_SHARD: FixedBytes<32> = key("myShard");
+pub(crate) const SHARD_COUNT: FixedBytes<32> = key("shardCount");
+pub(crate) const ACCEPT_ZERO_SCORE: FixedBytes<32> = key("acceptZeroScore");
+pub(crate) const DEFAULT_TRUST_SCORE: FixedBytes<32> = key("defaultTrustScore");
+pub(crate) const AGGREGATOR: FixedBytes<32> = key("aggregator");
+pub(crate) const MIN_THRESHOLD: FixedBytes<32> = key("minThreshold");
+pub(crate) const SCORE_GRACE: FixedBytes<32> = key("scoreGrace");
+pub(crate) const MIN_INTERVAL: FixedBytes<32> = key("minInterval");
+pub(crate) const MAX_ATTESTATION_LEN: FixedBytes<32> = key("maxAttestationLen");
+pub(crate) const REQUIRED_CLAIMS: FixedBytes<32> = key("requiredClaims");
+pub(crate) const INPUT_SCALE: FixedBytes<32> = key("inputScale");
+pub(crate) const ROUNDING_MODE: FixedBytes<32> = key("roundingMode");
+pub(crate) const MAX_SCORE_DELTA: FixedBytes<32> = key("maxScoreDelta");
+pub(crate) const EWMA_ALPHA: FixedBytes<32> = key("ewmaAlpha");
+pub(crate) const SILVER_MIN: FixedBytes<32> = key("silverMin");
+pub(crate) const GOLD_MIN: FixedBytes<32> = key("goldMin");
+pub(crate) const COMPROMISE_BOUNTY: FixedBytes<32> = key("compromiseBounty");
+pub(crate) const QUOTA_ENABLED: FixedBytes<32> = key("quotaEnabled");
+pub(crate) const MAX_RELAYER_SILENCE: FixedBytes<32> = key("maxRelayerSilence");
+pub(crate) const MIN_RELAYERS: FixedBytes<32> = key("minRelayers");
+pub(crate) const EPOCH_GRACE: FixedBytes<32> = key("epochGrace");
+pub(crate) const MERKLE_HASH_ALGO: FixedBytes<32> = key("merkleHashAlgo");
+pub(crate) const VERIFICATION_LOG_CAPACITY: FixedBytes<32> = key("verificationLogCapacity");
+pub(crate) const RELAYER_TIMELOCK: FixedBytes<32> = key("relayerTimelock");
+pub(crate) const RENOUNCE_TIMELOCK: FixedBytes<32> = key("renounceTimelock");
+pub(crate) const KEY_ROTATION_GRACE: FixedBytes<32> = key("keyRotationGrace");
+pub(crate) const CONSENSUS_THRESHOLD: FixedBytes<32> = key("consensusThreshold");
+pub(crate) const DEPOSIT_TOKEN: FixedBytes<32> = key("depositToken");
+pub(crate) const REGISTRATION_DEPOSIT: FixedBytes<32> = key("registrationDeposit");
+pub(crate) const STAKE_PER_POINT: FixedBytes<32> = key("stakePerPoint");
+pub(crate) const MAX_CONTRACT_BALANCE: FixedBytes<32> = key("maxContractBalance");
+pub(crate) const REWARD_TOKEN: FixedBytes<32> = key("rewardToken");
+pub(crate) const REWARD_AMOUNT: FixedBytes<32> = key("rewardAmount");
+pub(crate) const DEVICE_KEY_GRACE: FixedBytes<32> = key("deviceKeyGrace");
+pub(crate) const MEMOIZE_VERIFICATIONS: FixedBytes<32> = key("memoizeVerifications");
+pub(crate) const MILESTONE_INTERVAL: FixedBytes<32> = key("milestoneInterval");
+pub(crate) const STRICT_LIVENESS_WINDOW: FixedBytes<32> = key("strictLivenessWindow");
+pub(crate) const NONCE_WINDOW: FixedBytes<32> = key("nonceWindow");
+
+/// An address as a `ConfigChanged` value.
+pub(crate) fn address_value(address: Address) -> U256 {
+    U256::from_be_slice(address.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_match_solidity_bytes32_literals() {
+        assert_eq!(
+            MIN_THRESHOLD,
+            FixedBytes::<32>::right_padding_from(b"minThreshold")
+        );
+        assert_eq!(
+            VERIFICATION_LOG_CAPACITY,
+            FixedBytes::<32>::right_padding_from(b"verificationLogCapacity")
+        );
+        let address = Address::repeat_byte(0x11);
+        assert_eq!(
+            address_value(address).to_be_bytes::<32>()[12..],
+            address[..]
+        );
+    }
+}
@@ -0,0 +1,433 @@
+// Revert errors for the CertID Stylus verifier.
+//
+// Each variant ABI-encodes as a Solidity custom error, so callers and
+// indexers can decode failures with the generated contract ABI. Clients
+// that cannot decode custom errors use the numeric codes from `code()` and
+// `error_code_of` instead: codes are never renumbered or reused, and a new
+// error takes the next free code.
+
+use alloy_sol_types::{sol, SolError};
+use stylus_sdk::prelude::*;
+
+sol! {
+    /// `initialize` has already been called.
+    #[derive(Debug)]
+    error AlreadyInitialized();
+
+    /// `relayer_verify_and_update` found the attestation did not verify;
+    /// `status` is its `STATUS_*` code.
+    #[derive(Debug)]
+    error AttestationNotVerified(bytes32 device_id, uint8 status);
+
+    /// `attestation_data` exceeds the policy's maximum length.
+    #[derive(Debug)]
+    error AttestationTooLarge(uint256 length);
+
+    /// The signature is malformed or not from an authorized signer.
+    #[derive(Debug)]
+    error BadSignature();
+
+    /// The ETH sent would take the contract's balance above `max_contract_balance`.
+    #[derive(Debug)]
+    error BalanceCapExceeded(uint256 cap);
+
+    /// The batch has more entries than the per-call cap.
+    #[derive(Debug)]
+    error BatchTooLarge(uint256 length);
+
+    /// Score updates need a validator consensus proof; use
+    /// `update_trust_score_with_consensus`.
+    #[derive(Debug)]
+    error ConsensusRequired();
+
+    /// The contract is globally paused.
+    #[derive(Debug)]
+    error ContractPaused();
+
+    /// The attestation's hardware counter is missing or not above the `stored`
+    /// counter, so it may be replayed or from rolled-back hardware.
+    #[derive(Debug)]
+    error CounterRollback(uint256 stored);
+
+    /// The contract is decommissioned; use the deployment at `successor`.
+    #[derive(Debug)]
+    error Decommissioned(address successor);
+
+    /// The deposit or refund transfer failed (zero `token` = ETH).
+    #[derive(Debug)]
+    error DepositTransferFailed(address token);
+
+    /// The device id is already registered on L2.
+    #[derive(Debug)]
+    error DeviceAlreadyRegistered(bytes32 device_id);
+
+    /// The device id is not registered on L2.
+    #[derive(Debug)]
+    error DeviceNotRegistered(bytes32 device_id);
+
+    /// The device id is permanently retired and cannot be registered.
+    #[derive(Debug)]
+    error DeviceRetired(bytes32 device_id);
+
+    /// The payload's manufacture date (0 if absent) is before `min_manufacture_date`.
+    #[derive(Debug)]
+    error DeviceTooOld(uint64 manufactured_at);
+
+    /// The supplied digest is not the one the contract expects the device to sign next.
+    #[derive(Debug)]
+    error DigestMismatch(bytes32 expected);
+
+    /// Forwarding or returning a device's verification fee to `to` failed.
+    #[derive(Debug)]
+    error FeeTransferFailed(address to);
+
+    /// `msg_value` does not match the ETH registration deposit.
+    #[derive(Debug)]
+    error IncorrectDeposit(uint256 value);
+
+    /// The index is past the end of the enumeration.
+    #[derive(Debug)]
+    error IndexOutOfBounds(uint256 index);
+
+    /// `msg_value` is below the fee for the attestation's type.
+    #[derive(Debug)]
+    error InsufficientFee(uint256 required);
+
+    /// `withdraw_fees` asked for more than the fee `pool` holds.
+    #[derive(Debug)]
+    error InsufficientFeePool(uint256 pool);
+
+    /// Fewer than `min_relayers` relayers are authorized; verification is
+    /// frozen until more are added.
+    #[derive(Debug)]
+    error InsufficientRelayers(uint256 count);
+
+    /// `unstake` asked for more than the device's `staked` amount.
+    #[derive(Debug)]
+    error InsufficientStake(uint256 staked);
+
+    /// The payload's certificate chain is malformed, longer than
+    /// `MAX_CERT_CHAIN_LEN` links, or does not lead to a trusted root.
+    #[derive(Debug)]
+    error InvalidCertChain();
+
+    /// The attestation nonce was already used or falls outside the device's
+    /// `nonce_window`.
+    #[derive(Debug)]
+    error InvalidNonce(uint64 nonce);
+
+    /// The zero address cannot own a device.
+    #[derive(Debug)]
+    error InvalidOwner();
+
+    /// A `set_policy` field is out of range; nothing was written.
+    #[derive(Debug)]
+    error InvalidPolicy();
+
+    /// Trust scores must be in 0–100.
+    #[derive(Debug)]
+    error InvalidScore(uint256 score);
+
+    /// A signer set entry is the zero address or a duplicate.
+    #[derive(Debug)]
+    error InvalidSigner(address signer);
+
+    /// The proof is malformed or does not link to the committed state root.
+    #[derive(Debug)]
+    error InvalidStateProof();
+
+    /// The quorum threshold is zero or exceeds the number of signers.
+    #[derive(Debug)]
+    error InvalidThreshold(uint256 threshold);
+
+    /// The submitted secret is not a valid key, or its `derived` address (zero if
+    /// invalid) is not the device's attestation key.
+    #[derive(Debug)]
+    error KeyProofMismatch(address derived);
+
+    /// `verify_all` was given a different number of attestations than devices.
+    #[derive(Debug)]
+    error LengthMismatch(uint256 device_ids, uint256 attestations);
+
+    /// `attestation_data` is not a valid CBOR attestation payload.
+    #[derive(Debug)]
+    error MalformedAttestation();
+
+    /// The bridge message is not the layout from `bridge.rs`, or is
+    /// addressed to another chain or verifier.
+    #[derive(Debug)]
+    error MalformedBridgeMessage();
+
+    /// The signed message's expiry has passed.
+    #[derive(Debug)]
+    error MessageExpired(uint64 expiry);
+
+    /// Device metadata exceeds `MAX_METADATA_LEN` bytes.
+    #[derive(Debug)]
+    error MetadataTooLarge(uint256 length);
+
+    /// Model enforcement is on and the attestation's hardware `model` is not
+    /// allowlisted (zero = the payload names no model).
+    #[derive(Debug)]
+    error ModelNotAllowed(bytes32 model);
+
+    /// The policy oracle's `check()` returned false or reverted.
+    #[derive(Debug)]
+    error OracleRejected(address oracle);
+
+    /// The device still has unclaimed rewards; claim them or deregister with
+    /// `force` to forfeit them.
+    #[derive(Debug)]
+    error PendingRewardsExist(uint256 amount);
+
+    /// Fewer than `consensus_threshold` distinct validators signed the
+    /// score update.
+    #[derive(Debug)]
+    error QuorumNotMet(uint256 signers);
+
+    /// Quotas are on and the device has no verification quota left.
+    #[derive(Debug)]
+    error QuotaExhausted(bytes32 device_id);
+
+    /// No relayer change is proposed (`eta` = 0), or its timelock has not
+    /// elapsed.
+    #[derive(Debug)]
+    error RelayerChangeNotReady(uint256 eta);
+
+    /// No relayer activity within `max_relayer_silence`; verification is frozen.
+    #[derive(Debug)]
+    error RelayerSilent(uint256 last_heartbeat);
+
+    /// No renouncement is pending (`eta` = 0), or its timelock has not elapsed.
+    #[derive(Debug)]
+    error RenounceNotReady(uint256 eta);
+
+    /// The reward token rejected the mint; pending rewards are unchanged.
+    #[derive(Debug)]
+    error RewardMintFailed(uint256 amount);
+
+    /// No score is staged for the device (`effective_at` = 0), or it is not yet
+    /// effective.
+    #[derive(Debug)]
+    error StagedScoreNotReady(uint256 effective_at);
+
+    /// Beacon checks are on and the attestation's beacon `block` is missing (0),
+    /// outside `beacon_window`, or does not match its recorded block hash.
+    #[derive(Debug)]
+    error StaleBeacon(uint256 block);
+
+    /// The attestation's epoch is older than the current epoch allows, or an epoch update moved backwards.
+    #[derive(Debug)]
+    error StaleEpoch(uint64 epoch);
+
+    /// Epoch seeds are required and the payload does not carry `epoch`'s seed.
+    #[derive(Debug)]
+    error StaleSeed(uint256 epoch);
+
+    /// The bridge message sequence number is not above the last applied one.
+    #[derive(Debug)]
+    error StaleSequence(uint256 seq);
+
+    /// A signer set would exceed its cap (`MAX_SIGNERS_PER_DEVICE` per
+    /// device, `MAX_CONSENSUS_VALIDATORS` for the validator set).
+    #[derive(Debug)]
+    error TooManySigners(uint256 max);
+
+    /// The caller is not allowed to perform this operation.
+    #[derive(Debug)]
+    error Unauthorized(address caller);
+
+    /// The key scheme is not one of the `KEY_SCHEME_*` values.
+    #[derive(Debug)]
+    error UnknownKeyScheme(uint8 scheme);
+
+    /// Attestation verification is switched off by the owner.
+    #[derive(Debug)]
+    error VerificationDisabled();
+
+    /// The device was verified less than `min_interval` seconds ago.
+    #[derive(Debug)]
+    error VerificationTooFrequent(bytes32 device_id);
+
+    /// A device passed `verify_all`'s dry run but failed the real verification,
+    /// e.g. because it was listed twice.
+    #[derive(Debug)]
+    error VerifyAllFailed(bytes32 device_id);
+
+    /// The device id maps to `shard`, which is not this deployment's shard.
+    #[derive(Debug)]
+    error WrongShard(uint256 shard);
+}
+
+/// Errors returned by the verifier contract.
+#[derive(SolidityError, Debug)]
+pub enum VerifierError {
+    AlreadyInitialized(AlreadyInitialized),
+    AttestationNotVerified(AttestationNotVerified),
+    AttestationTooLarge(AttestationTooLarge),
+    BadSignature(BadSignature),
+    BalanceCapExceeded(BalanceCapExceeded),
+    BatchTooLarge(BatchTooLarge),
+    ConsensusRequired(ConsensusRequired),
+    ContractPaused(ContractPaused),
+    CounterRollback(CounterRollback),
+    Decommissioned(Decommissioned),
+    DepositTransferFailed(DepositTransferFailed),
+    DeviceAlreadyRegistered(DeviceAlreadyRegistered),
+    DeviceNotRegistered(DeviceNotRegistered),
+    DeviceRetired(DeviceRetired),
+    DeviceTooOld(DeviceTooOld),
+    DigestMismatch(DigestMismatch),
+    FeeTransferFailed(FeeTransferFailed),
+    IncorrectDeposit(IncorrectDeposit),
+    IndexOutOfBounds(IndexOutOfBounds),
+    InsufficientFee(InsufficientFee),
+    InsufficientFeePool(InsufficientFeePool),
+    InsufficientRelayers(InsufficientRelayers),
+    InsufficientStake(InsufficientStake),
+    InvalidCertChain(InvalidCertChain),
+    InvalidNonce(InvalidNonce),
+    InvalidOwner(InvalidOwner),
+    InvalidPolicy(InvalidPolicy),
+    InvalidScore(InvalidScore),
+    InvalidSigner(InvalidSigner),
+    InvalidStateProof(InvalidStateProof),
+    InvalidThreshold(InvalidThreshold),
+    KeyProofMismatch(KeyProofMismatch),
+    LengthMismatch(LengthMismatch),
+    MalformedAttestation(MalformedAttestation),
+    MalformedBridgeMessage(MalformedBridgeMessage),
+    MessageExpired(MessageExpired),
+    MetadataTooLarge(MetadataTooLarge),
+    ModelNotAllowed(ModelNotAllowed),
+    OracleRejected(OracleRejected),
+    PendingRewardsExist(PendingRewardsExist),
+    QuorumNotMet(QuorumNotMet),
+    QuotaExhausted(QuotaExhausted),
+    RelayerChangeNotReady(RelayerChangeNotReady),
+    RelayerSilent(RelayerSilent),
+    RenounceNotReady(RenounceNotReady),
+    RewardMintFailed(RewardMintFailed),
+    StagedScoreNotReady(StagedScoreNotReady),
+    StaleBeacon(StaleBeacon),
+    StaleEpoch(StaleEpoch),
+    StaleSeed(StaleSeed),
+    StaleSequence(StaleSequence),
+    TooManySigners(TooManySigners),
+    Unauthorized(Unauthorized),
+    UnknownKeyScheme(UnknownKeyScheme),
+    VerificationDisabled(VerificationDisabled),
+    VerificationTooFrequent(VerificationTooFrequent),
+    VerifyAllFailed(VerifyAllFailed),
+    WrongShard(WrongShard),
+}
+
+/// Assigns each variant its stable code, and builds the selector → code
+/// table behind `error_code_of`.
+macro_rules! error_codes {
+    ($($name:ident = $code:literal,)*) => {
+        impl VerifierError {
+            /// Stable numeric code of this error (never 0).
+            pub fn code(&self) -> u32 {
+                match self {
+                    $(Self::$name(_) => $code,)*
+                }
+            }
+        }
+
+        /// `(selector, code)` for every error.
+        pub(crate) const ERROR_CODES: &[([u8; 4], u32)] = &[$(($name::SELECTOR, $code),)*];
+    };
+}
+
+error_codes! {
+    AlreadyInitialized = 1,
+    AttestationTooLarge = 2,
+    BadSignature = 3,
+    BatchTooLarge = 4,
+    ConsensusRequired = 5,
+    ContractPaused = 6,
+    Decommissioned = 7,
+    DepositTransferFailed = 8,
+    DeviceAlreadyRegistered = 9,
+    DeviceNotRegistered = 10,
+    DeviceRetired = 11,
+    IncorrectDeposit = 12,
+    IndexOutOfBounds = 13,
+    InsufficientFee = 14,
+    InsufficientRelayers = 15,
+    InvalidNonce = 16,
+    InvalidOwner = 17,
+    InvalidPolicy = 18,
+    InvalidScore = 19,
+    InvalidSigner = 20,
+    InvalidStateProof = 21,
+    InvalidThreshold = 22,
+    MalformedAttestation = 23,
+    MalformedBridgeMessage = 24,
+    MessageExpired = 25,
+    MetadataTooLarge = 26,
+    OracleRejected = 27,
+    PendingRewardsExist = 28,
+    QuorumNotMet = 29,
+    RelayerSilent = 30,
+    RewardMintFailed = 31,
+    StaleEpoch = 32,
+    StaleSequence = 33,
+    TooManySigners = 34,
+    Unauthorized = 35,
+    VerificationDisabled = 36,
+    VerificationTooFrequent = 37,
+    CounterRollback = 38,
+    FeeTransferFailed = 39,
+    StagedScoreNotReady = 40,
+    RelayerChangeNotReady = 41,
+    ModelNotAllowed = 42,
+    StaleBeacon = 43,
+    QuotaExhausted = 44,
+    KeyProofMismatch = 45,
+    DigestMismatch = 46,
+    BalanceCapExceeded = 47,
+    LengthMismatch = 48,
+    VerifyAllFailed = 49,
+    UnknownKeyScheme = 50,
+    WrongShard = 51,
+    InsufficientStake = 52,
+    StaleSeed = 53,
+    AttestationNotVerified = 54,
+    DeviceTooOld = 55,
+    InvalidCertChain = 56,
+    RenounceNotReady = 57,
+    InsufficientFeePool = 58,
+}
+
+/// Code of the error with 4-byte `selector`, or 0 if it is not one of ours.
+pub(crate) fn error_code_of(selector: [u8; 4]) -> u32 {
+    ERROR_CODES
+        .iter()
+        .find(|(known, _)| *known == selector)
+        .map_or(0, |(_, code)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_are_stable_and_unique() {
+        for (i, (selector, code)) in ERROR_CODES.iter().enumerate() {
+            assert_ne!(*code, 0);
+            assert!(ERROR_CODES[i + 1..]
+                .iter()
+                .all(|(other, other_code)| other != selector && other_code != code));
+        }
+
+        // Published codes must never change
+        assert_eq!(error_code_of(AlreadyInitialized::SELECTOR), 1);
+        assert_eq!(error_code_of(Unauthorized::SELECTOR), 35);
+        assert_eq!(error_code_of(VerificationTooFrequent::SELECTOR), 37);
+        assert_eq!(error_code_of([0xff; 4]), 0);
+        let error = VerifierError::BadSignature(BadSignature {});
+        assert_eq!(error.code(), error_code_of(BadSignature::SELECTOR));
+    }
+}
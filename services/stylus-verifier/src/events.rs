@@ -0,0 +1,58 @@
+// Events emitted by the CertID Stylus verifier.
+
+use alloy_sol_types::sol;
+
+sol! {
+    /// The aggregator's `report` call reverted; the verification stands.
+    event AggregatorNotifyFailed(bytes32 indexed device_id, bool verdict);
+
+    /// More than `score_decrease_alarm` scores dropped in `epoch`; the
+    /// contract paused itself.
+    event AnomalyDetected(uint256 epoch, uint256 decreases);
+
+    /// The owner changed the parameter identified by `key` (see
+    /// `config.rs`) from `old_value` to `new_value`.
+    event ConfigChanged(bytes32 indexed key, uint256 old_value, uint256 new_value);
+
+    /// The device was revoked by the owner and can no longer verify.
+    event DeviceRevoked(bytes32 indexed device_id);
+
+    /// The owner withdrew `amount` of collected fees to `to`.
+    event FeesWithdrawn(address indexed to, uint256 amount);
+
+    /// The device's verification count reached `count`, a multiple of
+    /// `milestone_interval`.
+    event MilestoneReached(bytes32 indexed device_id, uint256 count);
+
+    /// The device moved from `previous_owner` to `new_owner` in a
+    /// `reassign_owner` page.
+    event OwnershipTransferred(
+        bytes32 indexed device_id,
+        address indexed previous_owner,
+        address indexed new_owner
+    );
+
+    /// An owner override decided this verification instead of the normal
+    /// checks (`mode` is `OVERRIDE_FORCE_PASS` or `OVERRIDE_FORCE_FAIL`).
+    event OverrideApplied(bytes32 indexed device_id, uint8 mode);
+
+    /// The owner proposed `relayer` as the relayer signing key; it can be
+    /// installed from `eta`.
+    event RelayerChangeProposed(address indexed relayer, uint256 eta);
+
+    /// The owner started renouncing ownership; `finalize_renounce` can
+    /// complete it from `eta`.
+    event RenounceInitiated(uint256 eta);
+
+    /// The reward mint for a verification reverted; `amount` was added to
+    /// the device's pending rewards instead.
+    event RewardFailed(bytes32 indexed device_id, uint256 amount);
+
+    /// The device was force-deregistered with `amount` of unclaimed
+    /// rewards, which are gone.
+    event RewardsForfeited(bytes32 indexed device_id, uint256 amount);
+
+    /// The owner reset the contract statistics for a new pilot phase;
+    /// `total_verifications` is the total before the reset.
+    event StatisticsReset(uint256 total_verifications);
+}
@@ -0,0 +1,43 @@
+// ABI definitions for the external contracts the verifier calls.
+//
+// Calls are encoded with `SolCall::abi_encode` and dispatched through
+// `self.vm().call`, so they work under the Stylus test VM as well.
+
+use alloy_sol_types::sol;
+use stylus_sdk::alloy_primitives::{address, Address};
+
+/// Address of the Arbitrum `ArbSys` precompile.
+pub(crate) const ARB_SYS: Address = address!("0000000000000000000000000000000000000064");
+
+/// How far back `ArbSys.arbBlockHash` serves block hashes.
+pub(crate) const ARB_BLOCK_HASH_DEPTH: u64 = 256;
+
+sol! {
+    /// ERC-20 used for registration deposits when `deposit_token` is set.
+    interface IERC20 {
+        function transfer(address to, uint256 amount) external returns (bool);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool);
+    }
+
+    /// L2 aggregator told the outcome of every verification.
+    interface IVerificationAggregator {
+        function report(bytes32 device_id, bool verdict) external;
+    }
+
+    /// External health/price gate consulted before accepting attestations.
+    interface IPolicyOracle {
+        function check() external view returns (bool);
+    }
+
+    /// Arbitrum system precompile; block hashes come from here because
+    /// Stylus has no `blockhash` host call.
+    interface IArbSys {
+        function arbBlockNumber() external view returns (uint256);
+        function arbBlockHash(uint256 arbBlockNum) external view returns (bytes32);
+    }
+
+    /// Reward token minted to device owners on successful verification.
+    interface IRewardToken {
+        function mint(address to, uint256 amount) external;
+    }
+}
@@ -10,16 +10,147 @@
 //   TEE signature verification in Solidity costs 5M+ gas.
 //   In Stylus (Rust → WASM), the same operation is ~10x cheaper.
 
-#![cfg_attr(not(feature = "export-abi"), no_main)]
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+#![recursion_limit = "4096"]
 extern crate alloc;
 
+mod bridge;
+mod cbor;
+mod config;
+mod errors;
+mod events;
+mod interfaces;
+mod merkle;
+mod page;
+mod selectors;
+mod signature;
+
 use alloc::vec::Vec;
+use alloy_sol_types::SolCall;
 use stylus_sdk::{
-    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_primitives::{Address, FixedBytes, B256, U256},
     prelude::*,
-    storage::{StorageAddress, StorageMap, StorageU256},
+    storage::{
+        StorageAddress, StorageBool, StorageBytes, StorageFixedBytes, StorageMap, StorageU256,
+        StorageVec,
+    },
+    stylus_core::calls::context::Call,
 };
 
+pub use errors::*;
+pub use events::*;
+
+/// Highest trust score the Cosmos L1 scoring engine can produce.
+pub const MAX_TRUST_SCORE: u64 = 100;
+
+/// Hard upper bound on `attestation_data` length, in bytes.
+pub const MAX_ATTESTATION_LEN: u64 = 4096;
+
+/// Maximum per-device metadata blob length, in bytes.
+pub const MAX_METADATA_LEN: usize = 256;
+
+/// `rounding_mode` values for rescaling relayed scores.
+const ROUND_FLOOR: u64 = 0;
+const ROUND_NEAREST: u64 = 1;
+
+/// Fixed-point denominator of `ewma_alpha`: 10 000 is an alpha of 1.
+pub const EWMA_SCALE: u64 = 10_000;
+
+/// Largest per-device signer set, bounding the storage and gas of
+/// `set_device_signers`, `add_delegate_signer` and `get_device_signers`.
+pub const MAX_SIGNERS_PER_DEVICE: usize = 16;
+
+/// Signature schemes a device's attestation key can use. Only secp256k1
+/// can be checked on-chain (through `ecrecover`); ed25519 is reserved and
+/// rejected with `UnknownKeyScheme` until a verifier for it exists.
+pub const KEY_SCHEME_SECP256K1: u8 = 0;
+pub const KEY_SCHEME_ED25519: u8 = 1;
+
+/// Largest validator set `update_trust_score_with_consensus` checks against.
+pub const MAX_CONSENSUS_VALIDATORS: usize = 64;
+
+/// `verify_tee_attestation_strict` statuses, in the order checks run.
+pub const STATUS_VERIFIED: u8 = 0;
+pub const STATUS_BAD_SIGNATURE: u8 = 1;
+pub const STATUS_LOW_SCORE: u8 = 2;
+pub const STATUS_STALE: u8 = 3;
+pub const STATUS_REJECTED: u8 = 4;
+
+/// `get_trust_tier` values.
+pub const TIER_NONE: u8 = 0;
+pub const TIER_BRONZE: u8 = 1;
+pub const TIER_SILVER: u8 = 2;
+pub const TIER_GOLD: u8 = 3;
+
+/// Lowest silver and gold scores until the owner sets tier boundaries.
+const DEFAULT_SILVER_MIN: u64 = 50;
+const DEFAULT_GOLD_MIN: u64 = 80;
+
+/// Width and number of the hourly buckets behind `verifications_in_window`;
+/// windows longer than the ring are clamped to it.
+const THROUGHPUT_BUCKET_SECONDS: u64 = 3600;
+const THROUGHPUT_BUCKETS: u64 = 24;
+
+/// Widest `nonce_window`, so a window's consumed nonces fit one bitmap word.
+pub const MAX_NONCE_WINDOW: u64 = 255;
+
+/// Buckets in `get_score_histogram`: scores 0–9, 10–19, …, 90–99, and 100.
+pub const SCORE_HISTOGRAM_BUCKETS: usize = 11;
+
+/// `set_verification_override` modes.
+pub const OVERRIDE_NORMAL: u8 = 0;
+pub const OVERRIDE_FORCE_PASS: u8 = 1;
+pub const OVERRIDE_FORCE_FAIL: u8 = 2;
+
+/// Bit indices into a device's packed `device_flags` word. Revoked,
+/// retired and hardware-counter tracking survive deregistration; the rest
+/// are cleared with the device. `FLAG_REGISTERED` answers registration
+/// checks, so a verification gets it from the word it already reads.
+const FLAG_REVOKED: usize = 0;
+const FLAG_RETIRED: usize = 1;
+const FLAG_HAS_SCORE: usize = 2;
+const FLAG_FORCE_PASS: usize = 3;
+const FLAG_FORCE_FAIL: usize = 4;
+const FLAG_HAS_HW_COUNTER: usize = 5;
+const FLAG_REGISTERED: usize = 6;
+
+/// EIP-712 domain `name` and `version` of this contract.
+pub const EIP712_NAME: &str = "CertIDVerifier";
+pub const EIP712_VERSION: &str = "1";
+
+/// Ring capacity of the on-chain verification log until the owner sets one.
+const DEFAULT_VERIFICATION_LOG_CAPACITY: u64 = 1024;
+
+/// Delay between `initiate_renounce` and `finalize_renounce` until the
+/// owner sets one: 7 days.
+const DEFAULT_RENOUNCE_TIMELOCK: u64 = 7 * 24 * 3600;
+
+/// Maximum number of devices per `revoke_devices_batch` call, keeping a
+/// batch well inside the block gas limit.
+const MAX_REVOKE_BATCH: usize = 256;
+
+/// Maximum number of attestations per `verify_all` call.
+pub const MAX_VERIFY_ALL: usize = 32;
+
+/// Most links a payload's certificate chain may have, bounding the
+/// `ecrecover` calls one verification makes.
+pub const MAX_CERT_CHAIN_LEN: usize = 4;
+
+/// Parallel `(owners, scores, revoked)` vectors from `get_devices_batch`.
+pub type DeviceRows = (Vec<Address>, Vec<U256>, Vec<bool>);
+
+/// `(owner, score, attestation_nonce, last_verified_at,
+/// verification_count, AUDIT_* flags, last_attestation_hash)` from
+/// `get_device_audit`.
+pub type DeviceAudit = (Address, U256, U256, U256, U256, u8, FixedBytes<32>);
+
+/// Bits of the flags byte in `get_device_audit`. No device can be frozen
+/// on its own, only the whole contract, so `AUDIT_FROZEN` is never set
+/// yet; it is reserved so the layout stays stable if one is added.
+pub const AUDIT_REVOKED: u8 = 1 << 0;
+pub const AUDIT_FROZEN: u8 = 1 << 1;
+pub const AUDIT_RETIRED: u8 = 1 << 2;
+
 /// CertID Hardware Verifier Contract
 ///
 /// Stores device registrations, trust scores, and attestation verification
@@ -36,127 +167,11109 @@ pub struct CertIDVerifier {
 
     /// Total number of successful TEE verifications
     total_verifications: StorageU256,
+
+    /// Contract administrator, set once by `initialize`
+    owner: StorageAddress,
+    /// Delay before a started renouncement can complete (0 = the
+    /// default), when it can (0 = none pending), and whether the owner
+    /// has renounced, which also keeps `initialize` from running again
+    renounce_timelock: StorageU256,
+    renounce_eta: StorageU256,
+    renounced: StorageBool,
+
+    /// Maps DeviceID → timestamp of the last trust score update
+    score_updated_at: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Maps DeviceID → timestamp of the last successful verification
+    last_verified_at: StorageMap<FixedBytes<32>, StorageU256>,
+    /// Maps DeviceID → keccak256 of the attestation data (or the digest)
+    /// of its last successful verification
+    last_attestation_hashes: StorageMap<FixedBytes<32>, StorageFixedBytes<32>>,
+
+    // Verification policy, written only through `set_policy`.
+    min_threshold: StorageU256,
+    score_grace: StorageU256,
+    min_interval: StorageU256,
+    max_attestation_len: StorageU256,
+    required_claims: StorageU256,
+
+    /// Number of registrations ever performed; source of registration indices
+    registration_count: StorageU256,
+
+    /// Maps DeviceID → 1-based registration index (0 = unregistered)
+    registration_index: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Shard this deployment registers devices for, out of `shard_count`
+    /// (0 or 1 = unsharded)
+    my_shard: StorageU256,
+    shard_count: StorageU256,
+
+    /// Authorized bridge relayers (transaction senders and signing keys)
+    relayers: StorageMap<Address, StorageBool>,
+    relayer_count: StorageU256,
+
+    /// Maps DeviceID → consecutive failed verifications
+    failure_count: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Maps DeviceID → nonce consumed by `restore_score` signatures.
+    /// Deliberately kept on deregistration so old signatures stay spent.
+    restore_nonces: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Root-of-trust allowlist (hashes of manufacturer root keys)
+    trusted_roots: StorageMap<FixedBytes<32>, StorageBool>,
+
+    /// Hardware model allowlist (hashes of model identifiers), enforced
+    /// only while `enforce_models` is set
+    allowed_models: StorageMap<FixedBytes<32>, StorageBool>,
+    enforce_models: StorageBool,
+    /// How many L2 blocks old an attestation's freshness beacon may be
+    /// (0 = beacons not required)
+    beacon_window: StorageU256,
+    /// Maps epoch → seed published by the relayer, which payloads must
+    /// carry for the current epoch while `require_epoch_seed` is set
+    epoch_seeds: StorageMap<U256, StorageFixedBytes<32>>,
+    require_epoch_seed: StorageBool,
+    /// Earliest manufacture date (unix seconds) a payload may carry
+    /// (0 = any, including none)
+    min_manufacture_date: StorageU256,
+
+    /// Enumerable copy of `trusted_roots`, kept in sync on add/remove
+    trusted_root_list: StorageVec<StorageFixedBytes<32>>,
+
+    /// Maps root → 1-based position in `trusted_root_list` (0 = absent)
+    trusted_root_positions: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Global pause: halts registration, score updates, and verification
+    paused: StorageBool,
+
+    /// Verification-only kill switch, stored inverted so the zero value
+    /// means "enabled"
+    verification_disabled: StorageBool,
+
+    /// Maps DeviceID → integrator metadata (firmware version, region, ...)
+    device_metadata: StorageMap<FixedBytes<32>, StorageBytes>,
+
+    /// Cosmos-side keys whose signed bridge messages anyone may submit
+    bridge_keys: StorageMap<Address, StorageBool>,
+
+    /// Maps DeviceID → last applied bridge message sequence number.
+    /// Kept on deregistration so old messages cannot be replayed.
+    bridge_seqs: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Token minted to the device owner per successful verification
+    /// (zero address = rewards off)
+    reward_token: StorageAddress,
+    reward_amount: StorageU256,
+
+    /// Maps DeviceID → rewards whose mint failed, claimable later
+    pending_rewards: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Block timestamp of `initialize` (zero until initialized)
+    deployed_at: StorageU256,
+
+    /// Maps DeviceID → device class (0 = global policy)
+    device_class: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Per-class overrides of `min_threshold` and `min_interval`, used only
+    /// once `class_policy_set` is true for the class
+    class_policy_set: StorageMap<U256, StorageBool>,
+    class_threshold: StorageMap<U256, StorageU256>,
+    class_min_interval: StorageMap<U256, StorageU256>,
+
+    /// Maps DeviceID → `FLAG_*` bitfield, so the gating checks read one
+    /// slot
+    device_flags: StorageMap<FixedBytes<32>, StorageU256>,
+    /// Number of device ids with `FLAG_REVOKED` set
+    revoked_count: StorageU256,
+
+    /// Maps DeviceID → address of the key that signs its attestations
+    device_attestation_keys: StorageMap<FixedBytes<32>, StorageAddress>,
+
+    /// Maps DeviceID → keccak256 of the attestation key address, for
+    /// integrations that keep the key itself off-chain until first use
+    device_key_commitment: StorageMap<FixedBytes<32>, StorageFixedBytes<32>>,
+
+    /// Maps DeviceID → `KEY_SCHEME_*` of its attestation key, its ed25519
+    /// public key once migrated to one, and the next
+    /// `rotate_key_and_scheme` nonce (survives deregistration)
+    device_key_schemes: StorageMap<FixedBytes<32>, StorageU256>,
+    device_ed25519_keys: StorageMap<FixedBytes<32>, StorageFixedBytes<32>>,
+    key_rotation_nonces: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Full-scale value of scores passed to `update_trust_score`, rescaled
+    /// to 0–100 (0 = scores are already 0–100)
+    input_scale: StorageU256,
+    /// How rescaling rounds: `ROUND_FLOOR` or `ROUND_NEAREST`
+    rounding_mode: StorageU256,
+
+    /// Dead man's switch: verification freezes once no relayer has been
+    /// active for `max_relayer_silence` seconds (0 = disabled)
+    last_relayer_heartbeat: StorageU256,
+    max_relayer_silence: StorageU256,
+
+    /// Maps DeviceID → authorized signer set and its quorum threshold, for
+    /// multi-key devices
+    device_signers: StorageMap<FixedBytes<32>, StorageVec<StorageAddress>>,
+    device_signer_threshold: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Latest Cosmos Hub state root, and the hash its Merkle tree uses
+    /// (see `merkle.rs`)
+    state_root: StorageFixedBytes<32>,
+    merkle_hash_algo: StorageU256,
+
+    /// Ring buffer of successful verifications: slot → (device, timestamp).
+    /// `verification_log_total` counts every append since the last reset;
+    /// only the newest `capacity` entries are retained.
+    verification_log_devices: StorageMap<U256, StorageFixedBytes<32>>,
+    verification_log_times: StorageMap<U256, StorageU256>,
+    verification_log_total: StorageU256,
+    verification_log_capacity: StorageU256,
+
+    /// When set, every attestation must echo the device's outstanding
+    /// challenge from `issue_challenge`, which is consumed on success
+    require_onchain_challenge: StorageBool,
+    outstanding_challenges: StorageMap<FixedBytes<32>, StorageFixedBytes<32>>,
+    challenge_nonces: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Enumeration of registered devices (swap-remove on deregistration)
+    device_list: StorageVec<StorageFixedBytes<32>>,
+    /// Maps DeviceID → 1-based position in `device_list` (0 = absent)
+    device_list_positions: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Deposit taken at registration, in `deposit_token` (zero = ETH)
+    registration_deposit: StorageU256,
+    deposit_token: StorageAddress,
+    /// Maps DeviceID → deposit held and its token, refunded on deregistration
+    device_deposits: StorageMap<FixedBytes<32>, StorageU256>,
+    device_deposit_tokens: StorageMap<FixedBytes<32>, StorageAddress>,
+
+    /// Maps DeviceID → ETH staked by its owner, and the stake worth one
+    /// point of `get_effective_trust` boost (0 = stake gives no boost)
+    device_stake: StorageMap<FixedBytes<32>, StorageU256>,
+    stake_per_point: StorageU256,
+
+    /// Replacement deployment once this contract is decommissioned
+    /// (zero = live)
+    successor: StorageAddress,
+
+    /// Optional `IPolicyOracle` that must approve each verification
+    /// (zero = none)
+    policy_oracle: StorageAddress,
+
+    /// Largest change one `update_trust_score` call may make (0 = unlimited)
+    max_score_delta: StorageU256,
+
+    /// Weight of each verification outcome in the device's trust score, in
+    /// `EWMA_SCALE` units (0 = verifications leave scores alone)
+    ewma_alpha: StorageU256,
+
+    /// Maps owner → their registered devices (swap-remove), and DeviceID →
+    /// 1-based position in its owner's list (0 = absent)
+    owner_devices: StorageMap<Address, StorageVec<StorageFixedBytes<32>>>,
+    owner_device_positions: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Maps DeviceID → next `claim_reward_with_sig` nonce; survives
+    /// deregistration so old authorizations cannot be replayed
+    claim_nonces: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Cosmos epoch mirrored by the relayer, and how many epochs back an
+    /// attestation may have been produced
+    current_epoch: StorageU256,
+    epoch_grace: StorageU256,
+    /// Maps epoch → `total_verifications` when the epoch's first
+    /// verification was counted, and when a later epoch's first one was;
+    /// `verification_epoch` is the epoch verifications last counted into
+    epoch_verification_snapshots: StorageMap<U256, StorageU256>,
+    epoch_verification_ends: StorageMap<U256, StorageU256>,
+    verification_epoch: StorageU256,
+
+    /// Maps DeviceID → next `register_device_authorized` nonce
+    registration_nonces: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Maps DeviceID → successful verifications since registration
+    device_verification_counts: StorageMap<FixedBytes<32>, StorageU256>,
+    /// Verification counts parked at deregistration, restored on
+    /// re-registration while `preserve_on_reregister` is set
+    shadow_verification_counts: StorageMap<FixedBytes<32>, StorageU256>,
+    preserve_on_reregister: StorageBool,
+    /// Per-device verification count step at which `MilestoneReached` is
+    /// emitted (0 = never)
+    milestone_interval: StorageU256,
+
+    /// `IVerificationAggregator` notified after each verification
+    /// (zero = none)
+    aggregator: StorageAddress,
+
+    /// Running sum of the trust scores of registered devices
+    trust_score_sum: StorageU256,
+    /// Maps bucket (score / 10) → number of registered devices scored in it
+    score_histogram: StorageMap<U256, StorageU256>,
+    /// Maps DeviceID → manufacturer id (zero = unassigned), manufacturer →
+    /// its `get_fleet_trust` weight, and manufacturer → sum and count of
+    /// its registered devices' scores, so a weight change is O(1)
+    device_manufacturers: StorageMap<FixedBytes<32>, StorageFixedBytes<32>>,
+    manufacturer_weights: StorageMap<FixedBytes<32>, StorageU256>,
+    manufacturer_score_sums: StorageMap<FixedBytes<32>, StorageU256>,
+    manufacturer_device_counts: StorageMap<FixedBytes<32>, StorageU256>,
+    /// Σ weight × score and Σ weight over assigned registered devices
+    weighted_score_sum: StorageU256,
+    total_trust_weight: StorageU256,
+    /// Every manufacturer ever assigned a device, so `rebuild_aggregates`
+    /// can reach each one's sum and count
+    manufacturer_list: StorageVec<StorageFixedBytes<32>>,
+    manufacturer_listed: StorageMap<FixedBytes<32>, StorageBool>,
+    /// Maps exact score → number of registered devices holding it, and
+    /// the lowest and highest such score (both 0 for an empty fleet)
+    score_counts: StorageMap<U256, StorageU256>,
+    min_score: StorageU256,
+    max_score: StorageU256,
+    /// Partial `trust_score_sum`, `score_histogram`, `score_counts` and
+    /// fleet-trust totals of an unfinished `rebuild_aggregates`, and the
+    /// index its next page must start at
+    rebuild_score_sum: StorageU256,
+    rebuild_histogram: StorageMap<U256, StorageU256>,
+    rebuild_score_counts: StorageMap<U256, StorageU256>,
+    rebuild_weighted_sum: StorageU256,
+    rebuild_total_weight: StorageU256,
+    rebuild_next: StorageU256,
+    /// Bumped by each new rebuild; a manufacturer's partial sum and count
+    /// belong to the current rebuild only if its round matches
+    rebuild_round: StorageU256,
+    rebuild_manufacturer_rounds: StorageMap<FixedBytes<32>, StorageU256>,
+    rebuild_manufacturer_sums: StorageMap<FixedBytes<32>, StorageU256>,
+    rebuild_manufacturer_counts: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Fewest authorized relayers verification runs with (0 = no minimum)
+    min_relayers: StorageU256,
+
+    /// Maps DeviceID → lowest attestation nonce not yet consumed, and a
+    /// bitmap of consumed nonces above it (bit i = `next + i`)
+    attestation_nonces: StorageMap<FixedBytes<32>, StorageU256>,
+    attestation_nonce_bitmaps: StorageMap<FixedBytes<32>, StorageU256>,
+    /// How far past the next nonce an attestation may run ahead (0 = strict)
+    nonce_window: StorageU256,
+    /// Maps DeviceID → nonce bound into its next `attestation_digest_for`
+    digest_nonces: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Score decreases of registered devices counted in
+    /// `score_decrease_epoch`; past `score_decrease_alarm` (0 = off) the
+    /// contract pauses itself
+    score_decrease_epoch: StorageU256,
+    score_decrease_count: StorageU256,
+    score_decrease_alarm: StorageU256,
+
+    /// Maps DeviceID → highest TEE hardware counter accepted, once
+    /// `FLAG_HAS_HW_COUNTER` is set; survives deregistration
+    device_hw_counters: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Maps DeviceID → ETH fee a verifier pays the device owner per
+    /// successful verification
+    verification_fee: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// Maps DeviceID → score scheduled by `stage_trust_score` and when it
+    /// takes effect (0 = nothing staged)
+    staged_scores: StorageMap<FixedBytes<32>, StorageU256>,
+    staged_score_effective_at: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// When set, a repeat of a device's last payload in the same block
+    /// reuses its signature verdict instead of recovering it again
+    memoize_verifications: StorageBool,
+    /// Maps DeviceID → block, `memo_key` and `memo_word` of its last
+    /// verification
+    memo_blocks: StorageMap<FixedBytes<32>, StorageU256>,
+    memo_keys: StorageMap<FixedBytes<32>, StorageFixedBytes<32>>,
+    memo_signatures: StorageMap<FixedBytes<32>, StorageU256>,
+    /// Bumped when a contract-wide signature setting changes, which
+    /// invalidates every memo
+    memo_generation: StorageU256,
+
+    /// When set, every verification spends one unit of the device's
+    /// `device_quota`, which relayers top up with `grant_quota`
+    quota_enabled: StorageBool,
+    device_quota: StorageMap<FixedBytes<32>, StorageU256>,
+
+    /// ETH paid from `fee_pool` to whoever proves a device key leaked
+    /// (0 = no bounty)
+    compromise_bounty: StorageU256,
+    /// keccak256 of the aggregates at the last `snapshot_stats` (zero = none)
+    stats_snapshot: StorageFixedBytes<32>,
+
+    /// Inverse of `allow_self_signed`: when set, a device key must also
+    /// carry an endorsement from a trusted root. Clear by default.
+    self_signed_disallowed: StorageBool,
+    /// Inverse of `allow_empty_payload`: when set, devices without a key
+    /// can no longer take the empty-payload pilot path. Clear by default.
+    empty_payload_disallowed: StorageBool,
+
+    /// Relayer signing key accepted by the signature-based relayer methods
+    /// alongside the `relayers` set
+    current_relayer_key: StorageAddress,
+    /// Key replaced by the last `rotate_relayer_key`, still accepted until
+    /// `relayer_key_rotated_at + key_rotation_grace`
+    previous_relayer_key: StorageAddress,
+    relayer_key_rotated_at: StorageU256,
+    key_rotation_grace: StorageU256,
+    /// Delay before a proposed relayer key can be installed; while non-zero
+    /// the key only changes through `propose_relayer` (0 = no timelock)
+    relayer_timelock: StorageU256,
+    /// Key awaiting `execute_relayer_change` and when it becomes executable
+    /// (0 = nothing proposed)
+    pending_relayer: StorageAddress,
+    pending_relayer_eta: StorageU256,
+
+    /// Maps DeviceID → tag → whether the device carries that label
+    device_tags: StorageMap<FixedBytes<32>, StorageMap<FixedBytes<32>, StorageBool>>,
+    /// Enumerable copy of each device's tags, so deregistration can clear them
+    device_tag_list: StorageMap<FixedBytes<32>, StorageVec<StorageFixedBytes<32>>>,
+    /// Maps DeviceID → tag → 1-based position in `device_tag_list` (0 = absent)
+    device_tag_positions: StorageMap<FixedBytes<32>, StorageMap<FixedBytes<32>, StorageU256>>,
+
+    /// How recently a device must have verified for the strict path to
+    /// accept it (0 = no liveness requirement)
+    strict_liveness_window: StorageU256,
+
+    /// Lowest silver and gold scores for `get_trust_tier` (0 = default)
+    silver_min: StorageU256,
+    gold_min: StorageU256,
+
+    /// Maps DeviceID → attestation key replaced by the last key change,
+    /// accepted until `device_key_rotated_at + device_key_grace`
+    device_previous_key: StorageMap<FixedBytes<32>, StorageAddress>,
+    device_key_rotated_at: StorageMap<FixedBytes<32>, StorageU256>,
+    device_key_grace: StorageU256,
+
+    /// Ring of hourly verification counters: slot → (hour, count), where
+    /// hour is `timestamp / THROUGHPUT_BUCKET_SECONDS` and a slot whose
+    /// hour is stale counts as empty
+    throughput_hours: StorageMap<U256, StorageU256>,
+    throughput_counts: StorageMap<U256, StorageU256>,
+
+    /// When set, a scored device at 0 passes a 0 threshold; otherwise
+    /// verification still needs a score of at least 1
+    accept_zero_score: StorageBool,
+    /// Baseline score given to devices registering without one (0 = none)
+    default_trust_score: StorageU256,
+
+    /// Cosmos validator set whose quorum must sign score updates while
+    /// `consensus_threshold` is non-zero
+    consensus_validators: StorageVec<StorageAddress>,
+    consensus_threshold: StorageU256,
+
+    /// Maps attestation type → ETH fee charged per verification
+    fee_by_type: StorageMap<U256, StorageU256>,
+    /// ETH collected from verification fees
+    fee_pool: StorageU256,
+    /// Most ETH the contract may hold after a payable call (0 = unlimited)
+    max_contract_balance: StorageU256,
 }
 
 #[public]
 impl CertIDVerifier {
-    /// Register a new device on Arbitrum L2.
+    /// One-time setup: the caller becomes the contract owner and the current
+    /// block time is recorded as the contract's genesis.
     ///
-    /// This mirrors device state from the Cosmos Hub via the bridge relayer.
-    /// In production, this would be permissioned to the relayer address only.
-    /// For the Grant Pilot, open registration demonstrates the flow.
-    pub fn register_device(&mut self, device_id: FixedBytes<32>, owner: Address) {
-        self.device_owners.setter(device_id).set(owner);
+    /// Stylus contracts have no constructor, so this must be called right
+    /// after deployment.
+    pub fn initialize(&mut self) -> Result<(), VerifierError> {
+        if !self.owner.get().is_zero() || self.renounced.get() {
+            return Err(VerifierError::AlreadyInitialized(AlreadyInitialized {}));
+        }
+        self.owner.set(self.vm().msg_sender());
+        self.deployed_at
+            .set(U256::from(self.vm().block_timestamp()));
+        Ok(())
     }
 
-    /// Update the Trust Score of a registered device.
+    /// Decommission the contract in favor of `successor` (owner-only), or
+    /// bring it back into service with the zero address.
     ///
-    /// Called by the CertID Bridge Relayer after the Cosmos L1 recalculates
-    /// the deterministic trust score (see x/hardware/keeper/scoring.go).
-    pub fn update_trust_score(&mut self, device_id: FixedBytes<32>, new_score: U256) {
-        self.device_trust_scores.setter(device_id).set(new_score);
+    /// While decommissioned, registration, score updates and verification
+    /// revert with `Decommissioned(successor)` so clients can discover the
+    /// new deployment. Deregistration and reward claims stay open so
+    /// deposits and rewards can still be withdrawn.
+    pub fn set_successor(&mut self, successor: Address) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::SUCCESSOR,
+            config::address_value(self.successor.get()),
+            config::address_value(successor),
+        );
+        self.successor.set(successor);
+        Ok(())
     }
 
-    /// Verify a TEE Attestation — the "Stylus Magic".
-    ///
-    /// This is the high-value operation that justifies using Stylus over
-    /// Solidity. Cryptographic signature verification in WASM is ~10x
-    /// cheaper than the equivalent EVM opcodes.
-    ///
-    /// Grant Pilot behavior:
-    ///   - Checks if the device is registered and has a valid trust score
-    ///   - Increments the global verification counter
-    ///
-    /// Production behavior (Phase 3):
-    ///   - Verifies the `attestation_data` signature against the manufacturer's
-    ///     public key (ARM TrustZone / Apple Secure Enclave)
-    pub fn verify_tee_attestation(
-        &mut self,
-        device_id: FixedBytes<32>,
-        _attestation_data: Vec<u8>,
-    ) -> bool {
-        // 1. Fetch current trust score
-        let score = self.device_trust_scores.get(device_id);
+    /// View: Get the successor deployment (zero while the contract is live)
+    pub fn get_successor(&self) -> Result<Address, VerifierError> {
+        Ok(self.successor.get())
+    }
 
-        // 2. Grant Pilot: Check if device is registered with valid score
-        //    Production: verify attestation_data signature here
-        if score > U256::ZERO {
-            // Increment global verification counter
-            let current_count = self.total_verifications.get();
-            self.total_verifications.set(current_count + U256::from(1));
-            return true;
+    /// View: Get the contract owner (zero until initialized, and once
+    /// renounced)
+    pub fn owner(&self) -> Result<Address, VerifierError> {
+        Ok(self.owner.get())
+    }
+
+    /// Set the delay between `initiate_renounce` and `finalize_renounce`
+    /// in seconds (owner-only; 0 restores the 7-day default). A pending
+    /// renouncement keeps the time it was started with.
+    pub fn set_renounce_timelock(&mut self, seconds: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::RENOUNCE_TIMELOCK,
+            self.renounce_timelock.get(),
+            seconds,
+        );
+        self.renounce_timelock.set(seconds);
+        Ok(())
+    }
+
+    /// Start renouncing ownership (owner-only), e.g. at the end of the
+    /// pilot. `finalize_renounce` completes it once the renounce timelock
+    /// has elapsed; until then `cancel_renounce` aborts it, and calling
+    /// this again restarts the clock. Emits `RenounceInitiated`.
+    pub fn initiate_renounce(&mut self) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        let eta = U256::from(self.vm().block_timestamp()).saturating_add(self.renounce_delay());
+        self.renounce_eta.set(eta);
+        log(self.vm(), RenounceInitiated { eta });
+        Ok(())
+    }
+
+    /// Abort a pending renouncement (owner-only).
+    pub fn cancel_renounce(&mut self) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.renounce_eta.set(U256::ZERO);
+        Ok(())
+    }
+
+    /// Give up ownership for good once the pending renouncement's
+    /// timelock has elapsed (owner-only). Every owner-only method reverts
+    /// with `Unauthorized` from then on, and `initialize` cannot be used
+    /// to claim the contract again.
+    pub fn finalize_renounce(&mut self) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        let eta = self.renounce_eta.get();
+        if eta.is_zero() || U256::from(self.vm().block_timestamp()) < eta {
+            return Err(VerifierError::RenounceNotReady(RenounceNotReady { eta }));
         }
+        self.renounce_eta.set(U256::ZERO);
+        self.owner.set(Address::ZERO);
+        self.renounced.set(true);
+        Ok(())
+    }
 
-        false
+    /// View: Get `(eta, renounce_timelock)` of the pending renouncement
+    /// (`eta` = 0 when none is pending; the timelock in effect, default
+    /// included)
+    pub fn get_pending_renounce(&self) -> Result<(U256, U256), VerifierError> {
+        Ok((self.renounce_eta.get(), self.renounce_delay()))
     }
 
-    /// View: Get the trust score for a device
-    pub fn get_device_trust(&self, device_id: FixedBytes<32>) -> U256 {
-        self.device_trust_scores.get(device_id)
+    /// Pause all device mutations and verification (owner-only).
+    pub fn pause(&mut self) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.paused.set(true);
+        Ok(())
     }
 
-    /// View: Get the owner address of a device
-    pub fn get_device_owner(&self, device_id: FixedBytes<32>) -> Address {
-        self.device_owners.get(device_id)
+    /// Lift the global pause (owner-only).
+    pub fn unpause(&mut self) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.paused.set(false);
+        Ok(())
     }
 
-    /// View: Get total successful attestation verifications
-    pub fn get_total_verifications(&self) -> U256 {
-        self.total_verifications.get()
+    /// View: Whether the contract is globally paused
+    pub fn paused(&self) -> Result<bool, VerifierError> {
+        Ok(self.paused.get())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use stylus_sdk::testing::*;
+    /// Pause automatically, emitting `AnomalyDetected`, once more than
+    /// `threshold` registered devices' scores drop within one Cosmos epoch
+    /// (owner-only, 0 = off). Only score updates count, not the moving
+    /// average's response to verifications. Only the owner can lift the
+    /// pause.
+    pub fn set_score_decrease_alarm(&mut self, threshold: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::SCORE_DECREASE_ALARM,
+            self.score_decrease_alarm.get(),
+            threshold,
+        );
+        self.score_decrease_alarm.set(threshold);
+        Ok(())
+    }
 
-    #[test]
-    fn test_register_and_verify() {
-        let vm = TestVM::default();
-        let mut contract = CertIDVerifier::from(&vm);
+    /// View: Get `score_decrease_alarm`
+    pub fn get_score_decrease_alarm(&self) -> Result<U256, VerifierError> {
+        Ok(self.score_decrease_alarm.get())
+    }
 
-        let device_id = FixedBytes::<32>::ZERO;
-        let owner = Address::ZERO;
+    /// View: Score decreases of registered devices in the current epoch
+    pub fn score_decreases_this_epoch(&self) -> Result<U256, VerifierError> {
+        if self.score_decrease_epoch.get() != self.current_epoch.get() {
+            return Ok(U256::ZERO);
+        }
+        Ok(self.score_decrease_count.get())
+    }
 
-        // Register device
-        contract.register_device(device_id, owner).unwrap();
+    /// Turn attestation verification on or off (owner-only).
+    ///
+    /// Independent of the global pause: while disabled, verification reverts
+    /// with `VerificationDisabled` but registration and score updates keep
+    /// working, e.g. during a signature-scheme migration. Enabled by default.
+    pub fn set_verification_enabled(&mut self, enabled: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::VERIFICATION_ENABLED,
+            U256::from(!self.verification_disabled.get()),
+            U256::from(enabled),
+        );
+        self.verification_disabled.set(!enabled);
+        Ok(())
+    }
 
-        // Set trust score to 92
-        contract
-            .update_trust_score(device_id, U256::from(92))
-            .unwrap();
+    /// View: Whether attestation verification is enabled
+    pub fn is_verification_enabled(&self) -> Result<bool, VerifierError> {
+        Ok(!self.verification_disabled.get())
+    }
 
-        // Verify attestation should succeed
-        let result = contract
-            .verify_tee_attestation(device_id, Vec::new())
-            .unwrap();
-        assert!(result);
+    /// Require attestations to answer an on-chain challenge (owner-only).
+    /// Off by default, leaving the stateless path.
+    pub fn set_require_onchain_challenge(&mut self, required: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::REQUIRE_ONCHAIN_CHALLENGE,
+            U256::from(self.require_onchain_challenge.get()),
+            U256::from(required),
+        );
+        self.require_onchain_challenge.set(required);
+        Ok(())
+    }
 
-        // Check counter incremented
-        assert_eq!(
-            contract.get_total_verifications().unwrap(),
-            U256::from(1)
+    /// View: Whether attestations must answer an on-chain challenge
+    pub fn is_onchain_challenge_required(&self) -> Result<bool, VerifierError> {
+        Ok(self.require_onchain_challenge.get())
+    }
+
+    /// Accept self-signed attestations (owner-only). On by default for
+    /// development fleets; production turns it off so every device key
+    /// must be endorsed by a trusted root, directly or through a
+    /// certificate chain in the payload.
+    pub fn set_allow_self_signed(&mut self, allowed: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::ALLOW_SELF_SIGNED,
+            U256::from(!self.self_signed_disallowed.get()),
+            U256::from(allowed),
         );
+        self.self_signed_disallowed.set(!allowed);
+        self.memo_generation
+            .set(self.memo_generation.get() + U256::from(1));
+        Ok(())
     }
 
-    #[test]
-    fn test_unregistered_device_fails() {
-        let vm = TestVM::default();
-        let mut contract = CertIDVerifier::from(&vm);
+    /// View: Whether self-signed attestations are accepted
+    pub fn is_self_signed_allowed(&self) -> Result<bool, VerifierError> {
+        Ok(!self.self_signed_disallowed.get())
+    }
 
-        let device_id = FixedBytes::<32>::ZERO;
+    /// Keep accepting empty `attestation_data` from devices without an
+    /// attestation key (owner-only). On by default for the pilot; turning
+    /// it off makes those reject with `MalformedAttestation`, as devices
+    /// with a key always do.
+    pub fn set_allow_empty_payload(&mut self, allowed: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::ALLOW_EMPTY_PAYLOAD,
+            U256::from(!self.empty_payload_disallowed.get()),
+            U256::from(allowed),
+        );
+        self.empty_payload_disallowed.set(!allowed);
+        Ok(())
+    }
 
-        // Verify without registration/score should fail
-        let result = contract
-            .verify_tee_attestation(device_id, Vec::new())
-            .unwrap();
-        assert!(!result);
+    /// View: Whether keyless devices may verify with an empty payload
+    pub fn is_empty_payload_allowed(&self) -> Result<bool, VerifierError> {
+        Ok(!self.empty_payload_disallowed.get())
+    }
 
-        // Counter should not increment
-        assert_eq!(
-            contract.get_total_verifications().unwrap(),
-            U256::ZERO
+    /// Gate verification on an external `IPolicyOracle` (owner-only); the
+    /// zero address removes the gate.
+    pub fn set_policy_oracle(&mut self, oracle: Address) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::POLICY_ORACLE,
+            config::address_value(self.policy_oracle.get()),
+            config::address_value(oracle),
+        );
+        self.policy_oracle.set(oracle);
+        Ok(())
+    }
+
+    /// View: Get the policy oracle (zero if none)
+    pub fn get_policy_oracle(&self) -> Result<Address, VerifierError> {
+        Ok(self.policy_oracle.get())
+    }
+
+    /// Choose whether re-registering a deregistered device restores its
+    /// verification count (owner-only). Off by default: re-registration
+    /// starts the count from zero.
+    ///
+    /// Signature nonces (restore, claim, registration, challenge,
+    /// attestation), TEE hardware counters and
+    /// bridge sequence numbers are never reset either way, since reusing
+    /// them would let old signatures replay.
+    pub fn set_preserve_on_reregister(&mut self, preserve: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::PRESERVE_ON_REREGISTER,
+            U256::from(self.preserve_on_reregister.get()),
+            U256::from(preserve),
+        );
+        self.preserve_on_reregister.set(preserve);
+        Ok(())
+    }
+
+    /// View: Whether re-registration restores prior verification history
+    pub fn is_preserve_on_reregister(&self) -> Result<bool, VerifierError> {
+        Ok(self.preserve_on_reregister.get())
+    }
+
+    /// Let an explicitly scored 0 satisfy a 0 threshold (owner-only). Off
+    /// by default, keeping 1 as the lowest passing score. Never-scored
+    /// devices fail either way.
+    pub fn set_accept_zero_score(&mut self, accepted: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::ACCEPT_ZERO_SCORE,
+            U256::from(self.accept_zero_score.get()),
+            U256::from(accepted),
+        );
+        self.accept_zero_score.set(accepted);
+        Ok(())
+    }
+
+    /// View: Whether an explicit zero score can pass verification
+    pub fn is_zero_score_accepted(&self) -> Result<bool, VerifierError> {
+        Ok(self.accept_zero_score.get())
+    }
+
+    /// Set the baseline score a device gets when it registers unscored
+    /// (owner-only), clamped to `MAX_TRUST_SCORE`; 0 leaves new devices
+    /// unscored. The relayer's first update replaces it as usual.
+    pub fn set_default_trust_score(&mut self, score: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        let score = score.min(U256::from(MAX_TRUST_SCORE));
+        self.config_changed(
+            config::DEFAULT_TRUST_SCORE,
+            self.default_trust_score.get(),
+            score,
+        );
+        self.default_trust_score.set(score);
+        Ok(())
+    }
+
+    /// View: Get `default_trust_score`
+    pub fn get_default_trust_score(&self) -> Result<U256, VerifierError> {
+        Ok(self.default_trust_score.get())
+    }
+
+    /// Set the aggregator told about every verification verdict
+    /// (owner-only); the zero address stops notifications.
+    pub fn set_aggregator(&mut self, aggregator: Address) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::AGGREGATOR,
+            config::address_value(self.aggregator.get()),
+            config::address_value(aggregator),
+        );
+        self.aggregator.set(aggregator);
+        Ok(())
+    }
+
+    /// View: Get the verification aggregator (zero if none)
+    pub fn get_aggregator(&self) -> Result<Address, VerifierError> {
+        Ok(self.aggregator.get())
+    }
+
+    /// Replace the whole verification policy in one call (owner-only).
+    ///
+    /// - `min_threshold`: minimum trust score to pass verification (0–100;
+    ///   0 keeps the pilot rule of "any non-zero score")
+    /// - `grace`: seconds a trust score stays valid after its last update
+    ///   (0 = never expires)
+    /// - `min_interval`: minimum seconds between verifications of the same
+    ///   device (0 = no limit)
+    /// - `max_attestation_len`: maximum `attestation_data` length in bytes,
+    ///   at most `MAX_ATTESTATION_LEN` (0 = `MAX_ATTESTATION_LEN`)
+    /// - `required_claims`: bitmask of claims every attestation must assert
+    ///
+    /// All fields are validated before any is written, so an invalid value
+    /// reverts without a partial update.
+    pub fn set_policy(
+        &mut self,
+        min_threshold: U256,
+        grace: U256,
+        min_interval: U256,
+        max_attestation_len: U256,
+        required_claims: U256,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if min_threshold > U256::from(MAX_TRUST_SCORE)
+            || max_attestation_len > U256::from(MAX_ATTESTATION_LEN)
+        {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+
+        self.config_changed(
+            config::MIN_THRESHOLD,
+            self.min_threshold.get(),
+            min_threshold,
         );
+        self.min_threshold.set(min_threshold);
+        self.config_changed(config::SCORE_GRACE, self.score_grace.get(), grace);
+        self.score_grace.set(grace);
+        self.config_changed(config::MIN_INTERVAL, self.min_interval.get(), min_interval);
+        self.min_interval.set(min_interval);
+        self.config_changed(
+            config::MAX_ATTESTATION_LEN,
+            self.max_attestation_len.get(),
+            max_attestation_len,
+        );
+        self.max_attestation_len.set(max_attestation_len);
+        self.config_changed(
+            config::REQUIRED_CLAIMS,
+            self.required_claims.get(),
+            required_claims,
+        );
+        self.required_claims.set(required_claims);
+        Ok(())
+    }
+
+    /// Set the full-scale value of scores relayed through
+    /// `update_trust_score` (owner-only); e.g. 1000 for a 0–1000 feed.
+    /// 0 disables rescaling.
+    pub fn set_input_scale(&mut self, input_scale: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(config::INPUT_SCALE, self.input_scale.get(), input_scale);
+        self.input_scale.set(input_scale);
+        Ok(())
+    }
+
+    /// Choose how rescaled scores round (owner-only): 0 = floor,
+    /// 1 = nearest (halves round up).
+    pub fn set_rounding_mode(&mut self, mode: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if mode != U256::from(ROUND_FLOOR) && mode != U256::from(ROUND_NEAREST) {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.config_changed(config::ROUNDING_MODE, self.rounding_mode.get(), mode);
+        self.rounding_mode.set(mode);
+        Ok(())
+    }
+
+    /// Limit how far a single `update_trust_score` call can move a score
+    /// (owner-only, 0 = unlimited).
+    pub fn set_max_score_delta(&mut self, delta: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(config::MAX_SCORE_DELTA, self.max_score_delta.get(), delta);
+        self.max_score_delta.set(delta);
+        Ok(())
+    }
+
+    /// View: Get the per-update score delta cap (0 = unlimited)
+    pub fn get_max_score_delta(&self) -> Result<U256, VerifierError> {
+        Ok(self.max_score_delta.get())
+    }
+
+    /// Fold each verification of a registered device into its trust score
+    /// (owner-only): `score = alpha * outcome + (1 - alpha) * score`, with
+    /// `alpha` in `EWMA_SCALE` units and an outcome of 100 for a pass and
+    /// 0 for a failure. Anyone can submit a failing attestation, so only
+    /// failures submitted by a relayer or the owner count. 0 turns the
+    /// moving average off.
+    pub fn set_ewma_alpha(&mut self, alpha: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if alpha > U256::from(EWMA_SCALE) {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.config_changed(config::EWMA_ALPHA, self.ewma_alpha.get(), alpha);
+        self.ewma_alpha.set(alpha);
+        Ok(())
+    }
+
+    /// View: Get the moving-average weight in `EWMA_SCALE` units (0 = off)
+    pub fn get_ewma_alpha(&self) -> Result<U256, VerifierError> {
+        Ok(self.ewma_alpha.get())
+    }
+
+    /// View: Get `(input_scale, rounding_mode)`
+    pub fn get_score_scaling(&self) -> Result<(U256, U256), VerifierError> {
+        Ok((self.input_scale.get(), self.rounding_mode.get()))
+    }
+
+    /// Set the lowest silver and gold scores for `get_trust_tier`
+    /// (owner-only). Requires `1 < silver_min < gold_min <= 100`, so bronze
+    /// always covers at least score 1.
+    pub fn set_trust_tiers(
+        &mut self,
+        silver_min: U256,
+        gold_min: U256,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if silver_min <= U256::from(1)
+            || gold_min <= silver_min
+            || gold_min > U256::from(MAX_TRUST_SCORE)
+        {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.config_changed(config::SILVER_MIN, self.silver_min.get(), silver_min);
+        self.silver_min.set(silver_min);
+        self.config_changed(config::GOLD_MIN, self.gold_min.get(), gold_min);
+        self.gold_min.set(gold_min);
+        Ok(())
+    }
+
+    /// View: Get `(silver_min, gold_min)`
+    pub fn get_trust_tiers(&self) -> Result<(U256, U256), VerifierError> {
+        Ok(self.tier_boundaries())
+    }
+
+    /// Authorize a bridge relayer (owner-only).
+    pub fn add_relayer(&mut self, relayer: Address) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if relayer.is_zero() {
+            return Err(VerifierError::InvalidOwner(InvalidOwner {}));
+        }
+        if !self.relayers.get(relayer) {
+            self.relayers.setter(relayer).set(true);
+            self.relayer_count
+                .set(self.relayer_count.get() + U256::from(1));
+        }
+        Ok(())
+    }
+
+    /// Revoke a bridge relayer's authorization (owner-only).
+    pub fn remove_relayer(&mut self, relayer: Address) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if self.relayers.get(relayer) {
+            self.relayers.delete(relayer);
+            self.relayer_count
+                .set(self.relayer_count.get() - U256::from(1));
+        }
+        Ok(())
+    }
+
+    /// Record relayer liveness (relayer-only). Score updates submitted by a
+    /// relayer count as a heartbeat too.
+    pub fn relayer_heartbeat(&mut self) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        let caller = self.vm().msg_sender();
+        if !self.relayers.get(caller) {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        self.note_relayer_activity();
+        Ok(())
+    }
+
+    /// Add `amount` verifications to a registered device's quota
+    /// (relayer-only). Counts as a heartbeat.
+    pub fn grant_quota(
+        &mut self,
+        device_id: FixedBytes<32>,
+        amount: U256,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        let caller = self.vm().msg_sender();
+        if !self.relayers.get(caller) {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        self.registered_owner(device_id)?;
+        let quota = self.device_quota.get(device_id).saturating_add(amount);
+        self.device_quota.setter(device_id).set(quota);
+        self.note_relayer_activity();
+        Ok(())
+    }
+
+    /// View: A device's remaining verification quota
+    pub fn get_device_quota(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.device_quota.get(device_id))
+    }
+
+    /// Make every verification spend one unit of the device's quota
+    /// (owner-only, off by default). While on, a device with no quota left
+    /// reverts with `QuotaExhausted`; owner overrides and memoized verdicts
+    /// are free.
+    pub fn set_quota_enabled(&mut self, enabled: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::QUOTA_ENABLED,
+            U256::from(self.quota_enabled.get()),
+            U256::from(enabled),
+        );
+        self.quota_enabled.set(enabled);
+        Ok(())
+    }
+
+    /// View: Whether verifications spend device quota
+    pub fn is_quota_enabled(&self) -> Result<bool, VerifierError> {
+        Ok(self.quota_enabled.get())
+    }
+
+    /// Set how long relayers may stay silent before verification freezes
+    /// (owner-only, 0 = never). The window starts now.
+    pub fn set_max_relayer_silence(&mut self, seconds: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::MAX_RELAYER_SILENCE,
+            self.max_relayer_silence.get(),
+            seconds,
+        );
+        self.max_relayer_silence.set(seconds);
+        self.last_relayer_heartbeat
+            .set(U256::from(self.vm().block_timestamp()));
+        Ok(())
+    }
+
+    /// Require at least `count` authorized relayers before verification is
+    /// allowed (owner-only, 0 = no minimum), so the fleet never depends on
+    /// a single relayer.
+    pub fn set_min_relayers(&mut self, count: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(config::MIN_RELAYERS, self.min_relayers.get(), count);
+        self.min_relayers.set(count);
+        Ok(())
+    }
+
+    /// View: Get `min_relayers`
+    pub fn get_min_relayers(&self) -> Result<U256, VerifierError> {
+        Ok(self.min_relayers.get())
+    }
+
+    /// Mirror the current Cosmos epoch (relayer-only). Epochs never move
+    /// backwards.
+    pub fn set_current_epoch(&mut self, epoch: u64) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        let caller = self.vm().msg_sender();
+        if !self.relayers.get(caller) {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        if U256::from(epoch) < self.current_epoch.get() {
+            return Err(VerifierError::StaleEpoch(StaleEpoch { epoch }));
+        }
+        self.current_epoch.set(U256::from(epoch));
+        self.note_relayer_activity();
+        Ok(())
+    }
+
+    /// Accept attestations up to `grace` epochs old (owner-only).
+    pub fn set_epoch_grace(&mut self, grace: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(config::EPOCH_GRACE, self.epoch_grace.get(), grace);
+        self.epoch_grace.set(grace);
+        Ok(())
+    }
+
+    /// View: Get `(current_epoch, epoch_grace)`
+    pub fn get_epoch(&self) -> Result<(U256, U256), VerifierError> {
+        Ok((self.current_epoch.get(), self.epoch_grace.get()))
+    }
+
+    /// View: Successful verifications counted while `epoch` was the
+    /// mirrored epoch. Epoch changes are noticed at the next counted
+    /// verification, so epochs without one, and future epochs, report 0.
+    pub fn get_epoch_verifications(&self, epoch: U256) -> Result<U256, VerifierError> {
+        let start = self.epoch_verification_snapshots.get(epoch);
+        let end = if epoch == self.verification_epoch.get() {
+            self.total_verifications.get()
+        } else {
+            self.epoch_verification_ends.get(epoch)
+        };
+        Ok(end.saturating_sub(start))
+    }
+
+    /// View: Get `(last_relayer_heartbeat, max_relayer_silence)`
+    pub fn get_relayer_liveness(&self) -> Result<(U256, U256), VerifierError> {
+        Ok((
+            self.last_relayer_heartbeat.get(),
+            self.max_relayer_silence.get(),
+        ))
+    }
+
+    /// Commit a Cosmos Hub state root (owner or relayer).
+    pub fn set_state_root(&mut self, root: FixedBytes<32>) -> Result<(), VerifierError> {
+        let caller = self.vm().msg_sender();
+        if caller != self.owner.get() && !self.relayers.get(caller) {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        self.state_root.set(root);
+        Ok(())
+    }
+
+    /// Select the hash the L1 Merkle tree uses (owner-only): 0 = keccak256,
+    /// 1 = sha256.
+    pub fn set_merkle_hash_algo(&mut self, algo: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if algo != U256::from(merkle::HASH_KECCAK256) && algo != U256::from(merkle::HASH_SHA256) {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.config_changed(config::MERKLE_HASH_ALGO, self.merkle_hash_algo.get(), algo);
+        self.merkle_hash_algo.set(algo);
+        Ok(())
+    }
+
+    /// Resize the verification log ring buffer (owner-only; 0 restores the
+    /// default). Existing entries are discarded, since their slots no
+    /// longer line up with the new capacity.
+    pub fn set_verification_log_capacity(&mut self, capacity: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::VERIFICATION_LOG_CAPACITY,
+            self.verification_log_capacity.get(),
+            capacity,
+        );
+        self.verification_log_capacity.set(capacity);
+        self.verification_log_total.set(U256::ZERO);
+        Ok(())
+    }
+
+    /// View: Get `(state_root, merkle_hash_algo)`
+    pub fn get_state_root(&self) -> Result<(FixedBytes<32>, U256), VerifierError> {
+        Ok((self.state_root.get(), self.merkle_hash_algo.get()))
+    }
+
+    /// View: Whether `proof` includes the hashed `leaf` in the committed
+    /// state root
+    pub fn verify_state_proof(
+        &self,
+        leaf: FixedBytes<32>,
+        proof: Vec<FixedBytes<32>>,
+    ) -> Result<bool, VerifierError> {
+        let algo = self.merkle_hash_algo.get().to::<u64>();
+        Ok(merkle::verify_proof(
+            self.vm(),
+            algo,
+            leaf,
+            &proof,
+            self.state_root.get(),
+        ))
+    }
+
+    /// View: Whether an address is an authorized relayer
+    pub fn is_relayer(&self, account: Address) -> Result<bool, VerifierError> {
+        Ok(self.relayers.get(account))
+    }
+
+    /// View: Number of authorized relayers
+    pub fn get_relayer_count(&self) -> Result<U256, VerifierError> {
+        Ok(self.relayer_count.get())
+    }
+
+    /// Rotate the relayer signing key to `new_key` (owner-only). The
+    /// current key becomes the previous one, which `restore_score` and
+    /// `register_device_authorized` keep accepting for `key_rotation_grace`
+    /// seconds so in-flight signatures still land.
+    ///
+    /// Reverts with `Unauthorized` while a `relayer_timelock` is set; use
+    /// `propose_relayer` then.
+    pub fn rotate_relayer_key(&mut self, new_key: Address) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if !self.relayer_timelock.get().is_zero() {
+            return Err(VerifierError::Unauthorized(Unauthorized {
+                caller: self.vm().msg_sender(),
+            }));
+        }
+        self.install_relayer_key(new_key)
+    }
+
+    /// Set the delay between `propose_relayer` and `execute_relayer_change`
+    /// in seconds (owner-only). Zero turns the timelock off and re-enables
+    /// instant `rotate_relayer_key`.
+    pub fn set_relayer_timelock(&mut self, seconds: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::RELAYER_TIMELOCK,
+            self.relayer_timelock.get(),
+            seconds,
+        );
+        self.relayer_timelock.set(seconds);
+        Ok(())
+    }
+
+    /// Propose `new_key` as the relayer signing key (owner-only). It can be
+    /// installed with `execute_relayer_change` once `relayer_timelock`
+    /// seconds have passed; a new proposal replaces a pending one and
+    /// restarts the clock. Emits `RelayerChangeProposed`.
+    pub fn propose_relayer(&mut self, new_key: Address) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if new_key.is_zero() {
+            return Err(VerifierError::InvalidSigner(InvalidSigner {
+                signer: new_key,
+            }));
+        }
+        let eta =
+            U256::from(self.vm().block_timestamp()).saturating_add(self.relayer_timelock.get());
+        self.pending_relayer.set(new_key);
+        self.pending_relayer_eta.set(eta);
+        log(
+            self.vm(),
+            RelayerChangeProposed {
+                relayer: new_key,
+                eta,
+            },
+        );
+        Ok(())
+    }
+
+    /// Install the proposed relayer key once its timelock has elapsed
+    /// (owner-only), rotating it in as `rotate_relayer_key` would.
+    pub fn execute_relayer_change(&mut self) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        let eta = self.pending_relayer_eta.get();
+        if eta.is_zero() || U256::from(self.vm().block_timestamp()) < eta {
+            return Err(VerifierError::RelayerChangeNotReady(
+                RelayerChangeNotReady { eta },
+            ));
+        }
+        let new_key = self.pending_relayer.get();
+        self.pending_relayer.set(Address::ZERO);
+        self.pending_relayer_eta.set(U256::ZERO);
+        self.install_relayer_key(new_key)
+    }
+
+    /// View: Get `(pending_relayer, eta, relayer_timelock)` (`eta` = 0 when
+    /// nothing is proposed)
+    pub fn get_pending_relayer(&self) -> Result<(Address, U256, U256), VerifierError> {
+        Ok((
+            self.pending_relayer.get(),
+            self.pending_relayer_eta.get(),
+            self.relayer_timelock.get(),
+        ))
+    }
+
+    /// Set how long the previous relayer key stays valid after a rotation
+    /// (owner-only). Zero retires it as soon as it is rotated out.
+    pub fn set_key_rotation_grace(&mut self, grace: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::KEY_ROTATION_GRACE,
+            self.key_rotation_grace.get(),
+            grace,
+        );
+        self.key_rotation_grace.set(grace);
+        Ok(())
+    }
+
+    /// View: Get `(current_key, previous_key, rotated_at, grace)`
+    pub fn get_relayer_keys(&self) -> Result<(Address, Address, U256, U256), VerifierError> {
+        Ok((
+            self.current_relayer_key.get(),
+            self.previous_relayer_key.get(),
+            self.relayer_key_rotated_at.get(),
+            self.key_rotation_grace.get(),
+        ))
+    }
+
+    /// Replace the validator set and quorum for consensus-proven score
+    /// updates (owner-only). Validators must be non-zero and distinct, at
+    /// most `MAX_CONSENSUS_VALIDATORS`, with `threshold` in
+    /// `1..=validators.len()`. An empty set with threshold 0 disables
+    /// consensus and reopens `update_trust_score`.
+    pub fn set_validator_set(
+        &mut self,
+        validators: Vec<Address>,
+        threshold: U256,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if validators.len() > MAX_CONSENSUS_VALIDATORS {
+            return Err(VerifierError::TooManySigners(TooManySigners {
+                max: U256::from(MAX_CONSENSUS_VALIDATORS),
+            }));
+        }
+        if threshold > U256::from(validators.len())
+            || (threshold.is_zero() && !validators.is_empty())
+        {
+            return Err(VerifierError::InvalidThreshold(InvalidThreshold {
+                threshold,
+            }));
+        }
+        for (i, validator) in validators.iter().enumerate() {
+            if validator.is_zero() || validators[..i].contains(validator) {
+                return Err(VerifierError::InvalidSigner(InvalidSigner {
+                    signer: *validator,
+                }));
+            }
+        }
+
+        self.consensus_validators.erase();
+        for validator in validators {
+            self.consensus_validators.push(validator);
+        }
+        self.config_changed(
+            config::CONSENSUS_THRESHOLD,
+            self.consensus_threshold.get(),
+            threshold,
+        );
+        self.consensus_threshold.set(threshold);
+        Ok(())
+    }
+
+    /// View: Get the validator set and consensus threshold
+    pub fn get_validator_set(&self) -> Result<(Vec<Address>, U256), VerifierError> {
+        let set = &self.consensus_validators;
+        let validators = (0..set.len()).filter_map(|i| set.get(i)).collect();
+        Ok((validators, self.consensus_threshold.get()))
+    }
+
+    /// Authorize a bridge signing key (owner-only).
+    pub fn add_bridge_key(&mut self, key: Address) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if key.is_zero() {
+            return Err(VerifierError::InvalidOwner(InvalidOwner {}));
+        }
+        self.bridge_keys.setter(key).set(true);
+        Ok(())
+    }
+
+    /// Revoke a bridge signing key (owner-only).
+    pub fn remove_bridge_key(&mut self, key: Address) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.bridge_keys.delete(key);
+        Ok(())
+    }
+
+    /// View: Whether an address is an authorized bridge signing key
+    pub fn is_bridge_key(&self, key: Address) -> Result<bool, VerifierError> {
+        Ok(self.bridge_keys.get(key))
+    }
+
+    /// Set the registration deposit (owner-only). With a zero `token` the
+    /// deposit is paid in ETH as `msg_value`; otherwise it is pulled with
+    /// ERC-20 `transferFrom`, which the registrant must have approved.
+    /// Devices keep the terms they registered under.
+    pub fn set_registration_deposit(
+        &mut self,
+        token: Address,
+        amount: U256,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::DEPOSIT_TOKEN,
+            config::address_value(self.deposit_token.get()),
+            config::address_value(token),
+        );
+        self.deposit_token.set(token);
+        self.config_changed(
+            config::REGISTRATION_DEPOSIT,
+            self.registration_deposit.get(),
+            amount,
+        );
+        self.registration_deposit.set(amount);
+        Ok(())
+    }
+
+    /// View: Get `(deposit_token, registration_deposit)`
+    pub fn get_registration_deposit(&self) -> Result<(Address, U256), VerifierError> {
+        Ok((self.deposit_token.get(), self.registration_deposit.get()))
+    }
+
+    /// Stake `msg_value` ETH behind a device (device owner only). Stake is
+    /// returned by `unstake` or on deregistration.
+    #[payable]
+    pub fn stake(&mut self, device_id: FixedBytes<32>) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_within_balance_cap()?;
+        self.only_device_owner(device_id)?;
+        let staked = self.device_stake.get(device_id) + self.vm().msg_value();
+        self.device_stake.setter(device_id).set(staked);
+        Ok(())
+    }
+
+    /// Withdraw `amount` of a device's stake to its owner (device owner
+    /// only). The stake is reduced before the transfer, so a re-entering
+    /// call sees the new balance. Reverts with `InsufficientStake` past
+    /// the staked amount.
+    pub fn unstake(
+        &mut self,
+        device_id: FixedBytes<32>,
+        amount: U256,
+    ) -> Result<(), VerifierError> {
+        self.only_device_owner(device_id)?;
+        let owner = self.vm().msg_sender();
+        let staked = self.device_stake.get(device_id);
+        if amount > staked {
+            return Err(VerifierError::InsufficientStake(InsufficientStake {
+                staked,
+            }));
+        }
+        self.device_stake.setter(device_id).set(staked - amount);
+        self.refund_deposit(owner, Address::ZERO, amount)
+    }
+
+    /// Set the stake worth one point of trust boost (owner-only, 0 = no
+    /// boost).
+    pub fn set_stake_per_point(&mut self, amount: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(config::STAKE_PER_POINT, self.stake_per_point.get(), amount);
+        self.stake_per_point.set(amount);
+        Ok(())
+    }
+
+    /// View: Get `(device_stake, stake_per_point)` for a device
+    pub fn get_device_stake(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<(U256, U256), VerifierError> {
+        Ok((self.device_stake.get(device_id), self.stake_per_point.get()))
+    }
+
+    /// View: A device's trust score plus one point per `stake_per_point`
+    /// of stake, capped at `MAX_TRUST_SCORE`. Verification itself uses the
+    /// unboosted score.
+    pub fn get_effective_trust(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        let boost = self
+            .device_stake
+            .get(device_id)
+            .checked_div(self.stake_per_point.get())
+            .unwrap_or_default();
+        Ok(self
+            .effective_score(device_id)
+            .saturating_add(boost)
+            .min(U256::from(MAX_TRUST_SCORE)))
+    }
+
+    /// Set the ETH fee for verifying attestations of `attestation_type`
+    /// (owner-only). Payloads without a type, and the empty pilot payload,
+    /// are type 0. Zero makes the type free.
+    pub fn set_verification_fee(
+        &mut self,
+        attestation_type: U256,
+        fee: U256,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.fee_by_type.setter(attestation_type).set(fee);
+        Ok(())
+    }
+
+    /// View: Get the verification fee for an attestation type
+    pub fn get_verification_fee(&self, attestation_type: U256) -> Result<U256, VerifierError> {
+        Ok(self.fee_by_type.get(attestation_type))
+    }
+
+    /// View: Total ETH collected from verification fees
+    pub fn get_fee_pool(&self) -> Result<U256, VerifierError> {
+        Ok(self.fee_pool.get())
+    }
+
+    /// Withdraw `amount` of collected fees to `to` (owner-only), emitting
+    /// `FeesWithdrawn`. The pool is reduced before the transfer, so a
+    /// re-entering call sees the new balance. Reverts with
+    /// `InsufficientFeePool` past the pooled amount.
+    pub fn withdraw_fees(&mut self, to: Address, amount: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        let pool = self.fee_pool.get();
+        if amount > pool {
+            return Err(VerifierError::InsufficientFeePool(InsufficientFeePool {
+                pool,
+            }));
+        }
+        self.fee_pool.set(pool - amount);
+        log(self.vm(), FeesWithdrawn { to, amount });
+        self.settle_device_fee(to, amount)
+    }
+
+    /// Cap the contract's ETH balance (owner-only, 0 = unlimited). Payable
+    /// calls sending ETH revert with `BalanceCapExceeded` once the balance,
+    /// including the ETH sent, would exceed it; calls sending none still
+    /// go through.
+    pub fn set_max_contract_balance(&mut self, cap: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::MAX_CONTRACT_BALANCE,
+            self.max_contract_balance.get(),
+            cap,
+        );
+        self.max_contract_balance.set(cap);
+        Ok(())
+    }
+
+    /// View: Get the contract balance cap (0 = unlimited)
+    pub fn get_max_contract_balance(&self) -> Result<U256, VerifierError> {
+        Ok(self.max_contract_balance.get())
+    }
+
+    /// Charge verifiers `fee` ETH per verification of this device, paid to
+    /// the device owner (device owner only, 0 = free). It is due on top of
+    /// the attestation type's fee, and returned to the caller if the
+    /// verification fails.
+    pub fn set_device_verification_fee(
+        &mut self,
+        device_id: FixedBytes<32>,
+        fee: U256,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.only_device_owner(device_id)?;
+        self.verification_fee.setter(device_id).set(fee);
+        Ok(())
+    }
+
+    /// View: Get a device's owner-set verification fee
+    pub fn get_device_verification_fee(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<U256, VerifierError> {
+        Ok(self.verification_fee.get(device_id))
+    }
+
+    /// Configure the per-verification reward (owner-only). A zero `token`
+    /// disables rewards.
+    pub fn set_reward_config(&mut self, token: Address, amount: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::REWARD_TOKEN,
+            config::address_value(self.reward_token.get()),
+            config::address_value(token),
+        );
+        self.reward_token.set(token);
+        self.config_changed(config::REWARD_AMOUNT, self.reward_amount.get(), amount);
+        self.reward_amount.set(amount);
+        Ok(())
+    }
+
+    /// View: Get `(reward_token, reward_amount)`
+    pub fn get_reward_config(&self) -> Result<(Address, U256), VerifierError> {
+        Ok((self.reward_token.get(), self.reward_amount.get()))
+    }
+
+    /// Add a root of trust to the allowlist (owner-only). No-op if present.
+    pub fn add_trusted_root(&mut self, root: FixedBytes<32>) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if self.trusted_roots.get(root) {
+            return Ok(());
+        }
+        self.trusted_roots.setter(root).set(true);
+        self.trusted_root_list.push(root);
+        let position = U256::from(self.trusted_root_list.len());
+        self.trusted_root_positions.setter(root).set(position);
+        Ok(())
+    }
+
+    /// Remove a root of trust (owner-only). No-op if absent.
+    ///
+    /// The last root in the enumeration moves into the removed slot, so
+    /// `get_trusted_root_at` ordering is not stable across removals.
+    pub fn remove_trusted_root(&mut self, root: FixedBytes<32>) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        let position = self.trusted_root_positions.get(root);
+        if position.is_zero() {
+            return Ok(());
+        }
+
+        let index = position.to::<usize>() - 1;
+        let last_index = self.trusted_root_list.len() - 1;
+        if index != last_index {
+            let last = self.trusted_root_list.get(last_index).unwrap_or_default();
+            if let Some(mut slot) = self.trusted_root_list.setter(index) {
+                slot.set(last);
+            }
+            self.trusted_root_positions.setter(last).set(position);
+        }
+        self.trusted_root_list.erase_last();
+        self.trusted_root_positions.delete(root);
+        self.trusted_roots.delete(root);
+        self.memo_generation
+            .set(self.memo_generation.get() + U256::from(1));
+        Ok(())
+    }
+
+    /// View: Whether a root is on the allowlist
+    pub fn is_trusted_root(&self, root: FixedBytes<32>) -> Result<bool, VerifierError> {
+        Ok(self.trusted_roots.get(root))
+    }
+
+    /// View: Number of trusted roots
+    pub fn get_trusted_root_count(&self) -> Result<U256, VerifierError> {
+        Ok(U256::from(self.trusted_root_list.len()))
+    }
+
+    /// View: Trusted root at `index` in `0..get_trusted_root_count()`
+    pub fn get_trusted_root_at(&self, index: U256) -> Result<FixedBytes<32>, VerifierError> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| self.trusted_root_list.get(i))
+            .ok_or(VerifierError::IndexOutOfBounds(IndexOutOfBounds { index }))
+    }
+
+    /// Add a hardware model hash to the allowlist (owner-only).
+    pub fn add_allowed_model(&mut self, model: FixedBytes<32>) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if model.is_zero() {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.allowed_models.setter(model).set(true);
+        Ok(())
+    }
+
+    /// Remove a hardware model hash from the allowlist (owner-only).
+    pub fn remove_allowed_model(&mut self, model: FixedBytes<32>) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.allowed_models.delete(model);
+        Ok(())
+    }
+
+    /// View: Whether a hardware model hash is on the allowlist
+    pub fn is_model_allowed(&self, model: FixedBytes<32>) -> Result<bool, VerifierError> {
+        Ok(self.allowed_models.get(model))
+    }
+
+    /// Require attestations to name an allowlisted hardware model
+    /// (owner-only). Off by default; while on, payloads without a model,
+    /// including the empty pilot payload, revert with `ModelNotAllowed`.
+    pub fn set_model_enforcement(&mut self, enforced: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::ENFORCE_MODELS,
+            U256::from(self.enforce_models.get()),
+            U256::from(enforced),
+        );
+        self.enforce_models.set(enforced);
+        Ok(())
+    }
+
+    /// View: Whether attestations must name an allowlisted model
+    pub fn is_model_enforced(&self) -> Result<bool, VerifierError> {
+        Ok(self.enforce_models.get())
+    }
+
+    /// Require attestations to embed a recent L2 block hash (owner-only):
+    /// the beacon block must be at most `window` blocks before the current
+    /// one and its hash must match, else verification reverts with
+    /// `StaleBeacon`. 0 turns the check off; at most 256, the depth
+    /// `ArbSys` keeps hashes for.
+    pub fn set_beacon_window(&mut self, window: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if window > U256::from(interfaces::ARB_BLOCK_HASH_DEPTH) {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.config_changed(config::BEACON_WINDOW, self.beacon_window.get(), window);
+        self.beacon_window.set(window);
+        Ok(())
+    }
+
+    /// View: Get `beacon_window`
+    pub fn get_beacon_window(&self) -> Result<U256, VerifierError> {
+        Ok(self.beacon_window.get())
+    }
+
+    /// Publish the seed for `epoch` (relayer-only). Seeds may be published
+    /// ahead of their epoch and replaced until it is over.
+    pub fn set_epoch_seed(
+        &mut self,
+        epoch: u64,
+        seed: FixedBytes<32>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        let caller = self.vm().msg_sender();
+        if !self.relayers.get(caller) {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        if U256::from(epoch) < self.current_epoch.get() {
+            return Err(VerifierError::StaleEpoch(StaleEpoch { epoch }));
+        }
+        self.epoch_seeds.setter(U256::from(epoch)).set(seed);
+        self.note_relayer_activity();
+        Ok(())
+    }
+
+    /// View: Get the seed published for `epoch` (zero = none)
+    pub fn get_epoch_seed(&self, epoch: U256) -> Result<FixedBytes<32>, VerifierError> {
+        Ok(self.epoch_seeds.get(epoch))
+    }
+
+    /// Require attestation payloads to carry the current epoch's seed
+    /// (owner-only), so a signature made before the seed was published
+    /// cannot be replayed into a later epoch. While on, verification
+    /// reverts with `StaleSeed` on a missing or other seed, including
+    /// while the current epoch has none.
+    pub fn set_require_epoch_seed(&mut self, required: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::REQUIRE_EPOCH_SEED,
+            U256::from(self.require_epoch_seed.get()),
+            U256::from(required),
+        );
+        self.require_epoch_seed.set(required);
+        Ok(())
+    }
+
+    /// View: Whether payloads must carry the current epoch's seed
+    pub fn is_epoch_seed_required(&self) -> Result<bool, VerifierError> {
+        Ok(self.require_epoch_seed.get())
+    }
+
+    /// Refuse devices manufactured before `date`, e.g. a known-vulnerable
+    /// batch (owner-only; 0 disables). While set, payloads without a
+    /// manufacture date revert with `DeviceTooOld` as well.
+    pub fn set_min_manufacture_date(&mut self, date: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::MIN_MANUFACTURE_DATE,
+            self.min_manufacture_date.get(),
+            date,
+        );
+        self.min_manufacture_date.set(date);
+        Ok(())
+    }
+
+    /// View: Get `min_manufacture_date`
+    pub fn get_min_manufacture_date(&self) -> Result<U256, VerifierError> {
+        Ok(self.min_manufacture_date.get())
+    }
+
+    /// View: Get the verification policy in `set_policy` argument order
+    pub fn get_policy(&self) -> Result<(U256, U256, U256, U256, U256), VerifierError> {
+        Ok((
+            self.min_threshold.get(),
+            self.score_grace.get(),
+            self.min_interval.get(),
+            self.max_attestation_len.get(),
+            self.required_claims.get(),
+        ))
+    }
+
+    /// Set the threshold and minimum interval for a device class
+    /// (owner-only). Same ranges as `set_policy`; class 0 always uses the
+    /// global policy and cannot be overridden.
+    pub fn set_class_policy(
+        &mut self,
+        class: U256,
+        threshold: U256,
+        interval: U256,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if class.is_zero() || threshold > U256::from(MAX_TRUST_SCORE) {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.class_policy_set.setter(class).set(true);
+        self.class_threshold.setter(class).set(threshold);
+        self.class_min_interval.setter(class).set(interval);
+        Ok(())
+    }
+
+    /// View: Get `(threshold, interval)` in effect for a device class; the
+    /// global values if the class has no policy of its own
+    pub fn get_class_policy(&self, class: U256) -> Result<(U256, U256), VerifierError> {
+        if self.class_policy_set.get(class) {
+            Ok((
+                self.class_threshold.get(class),
+                self.class_min_interval.get(class),
+            ))
+        } else {
+            Ok((self.min_threshold.get(), self.min_interval.get()))
+        }
+    }
+
+    /// View: Logical shard of a device id, `uint(device_id) % shard_count`
+    /// (0 when `shard_count` is 0)
+    pub fn shard_of(
+        &self,
+        device_id: FixedBytes<32>,
+        shard_count: U256,
+    ) -> Result<U256, VerifierError> {
+        Ok(U256::from_be_bytes(device_id.0)
+            .checked_rem(shard_count)
+            .unwrap_or_default())
+    }
+
+    /// Only register devices whose `shard_of(device_id, shard_count)` is
+    /// `shard` (owner-only), so several deployments can split a fleet;
+    /// other ids revert with `WrongShard`. A `shard_count` of 0 or 1
+    /// accepts every device. Devices already registered are kept.
+    pub fn set_shard(&mut self, shard: U256, shard_count: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if shard_count > U256::from(1) && shard >= shard_count {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.config_changed(config::MY_SHARD, self.my_shard.get(), shard);
+        self.my_shard.set(shard);
+        self.config_changed(config::SHARD_COUNT, self.shard_count.get(), shard_count);
+        self.shard_count.set(shard_count);
+        Ok(())
+    }
+
+    /// View: Get `(my_shard, shard_count)`
+    pub fn get_shard(&self) -> Result<(U256, U256), VerifierError> {
+        Ok((self.my_shard.get(), self.shard_count.get()))
+    }
+
+    /// Register a new device on Arbitrum L2.
+    ///
+    /// This mirrors device state from the Cosmos Hub via the bridge relayer.
+    /// In production, this would be permissioned to the relayer address only.
+    /// For the Grant Pilot, open registration demonstrates the flow.
+    ///
+    /// Takes the registration deposit, if one is configured.
+    #[payable]
+    pub fn register_device(
+        &mut self,
+        device_id: FixedBytes<32>,
+        owner: Address,
+    ) -> Result<(), VerifierError> {
+        self.register_device_with_class(device_id, owner, U256::ZERO)
+    }
+
+    /// Register a device with a relayer's signature over
+    /// `registration_digest(device_id, owner, nonce)`, so devices can
+    /// onboard themselves without the relayer submitting the transaction.
+    /// Each authorization is single-use.
+    #[payable]
+    pub fn register_device_authorized(
+        &mut self,
+        device_id: FixedBytes<32>,
+        owner: Address,
+        signature: Vec<u8>,
+    ) -> Result<(), VerifierError> {
+        let nonce = self.registration_nonces.get(device_id);
+        let digest = self.registration_digest(device_id, owner, nonce);
+        match signature::recover_signer(self.vm(), digest, &signature) {
+            Some(signer) if self.is_relayer_signer(signer) => {}
+            _ => return Err(VerifierError::BadSignature(BadSignature {})),
+        }
+
+        self.registration_nonces
+            .setter(device_id)
+            .set(nonce + U256::from(1));
+        self.register_device_with_class(device_id, owner, U256::ZERO)
+    }
+
+    /// Register a device whose attestation key is known only by its
+    /// commitment `keccak256(key)`. Attestations must then be signed by a
+    /// key hashing to `commitment`.
+    #[payable]
+    pub fn register_device_with_commitment(
+        &mut self,
+        device_id: FixedBytes<32>,
+        owner: Address,
+        commitment: FixedBytes<32>,
+    ) -> Result<(), VerifierError> {
+        self.register_device_with_class(device_id, owner, U256::ZERO)?;
+        self.device_key_commitment.setter(device_id).set(commitment);
+        Ok(())
+    }
+
+    /// Register a device under a device class (sensor, gateway, phone, ...)
+    /// whose `set_class_policy` applies at verification. Class 0 is the
+    /// same as `register_device`.
+    #[payable]
+    pub fn register_device_with_class(
+        &mut self,
+        device_id: FixedBytes<32>,
+        owner: Address,
+        class: U256,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        self.when_within_balance_cap()?;
+        let shard_count = self.shard_count.get();
+        if shard_count > U256::from(1) {
+            let shard = self.shard_of(device_id, shard_count)?;
+            if shard != self.my_shard.get() {
+                return Err(VerifierError::WrongShard(WrongShard { shard }));
+            }
+        }
+        if self.get_flag(device_id, FLAG_RETIRED) {
+            return Err(VerifierError::DeviceRetired(DeviceRetired { device_id }));
+        }
+        if self.is_device_registered(device_id) {
+            return Err(VerifierError::DeviceAlreadyRegistered(
+                DeviceAlreadyRegistered { device_id },
+            ));
+        }
+
+        let index = self.registration_count.get() + U256::from(1);
+        self.registration_count.set(index);
+        self.registration_index.setter(device_id).set(index);
+
+        let prior_count = self.shadow_verification_counts.get(device_id);
+        self.shadow_verification_counts.delete(device_id);
+        if self.preserve_on_reregister.get() {
+            self.device_verification_counts
+                .setter(device_id)
+                .set(prior_count);
+        }
+
+        let default_score = self.default_trust_score.get();
+        if !default_score.is_zero() && !self.get_flag(device_id, FLAG_HAS_SCORE) {
+            self.apply_score(device_id, default_score);
+        }
+        self.device_owners.setter(device_id).set(owner);
+        let score = self.device_trust_scores.get(device_id);
+        self.trust_score_sum.set(self.trust_score_sum.get() + score);
+        self.shift_histogram(score, true);
+        self.shift_fleet_trust(device_id, score, true);
+        self.device_class.setter(device_id).set(class);
+        self.device_list.push(device_id);
+        let position = U256::from(self.device_list.len());
+        self.device_list_positions.setter(device_id).set(position);
+        let mut owned = self.owner_devices.setter(owner);
+        owned.push(device_id);
+        let position = U256::from(owned.len());
+        self.owner_device_positions.setter(device_id).set(position);
+        self.set_flag(device_id, FLAG_REGISTERED, true);
+        self.collect_deposit(device_id)
+    }
+
+    /// Remove a device from L2. Only the device owner may deregister it.
+    ///
+    /// Clears the owner, the registration flag, and the trust score so a
+    /// later re-registration starts from a clean slate, and refunds the
+    /// registration deposit to the device owner.
+    ///
+    /// Reverts with `PendingRewardsExist` while the device has unclaimed
+    /// rewards, unless `force` is set, which forfeits them and emits
+    /// `RewardsForfeited`.
+    pub fn deregister_device(
+        &mut self,
+        device_id: FixedBytes<32>,
+        force: bool,
+    ) -> Result<(), VerifierError> {
+        self.when_not_paused()?;
+        self.only_device_owner(device_id)?;
+        let pending = self.pending_rewards.get(device_id);
+        if !pending.is_zero() && !force {
+            return Err(VerifierError::PendingRewardsExist(PendingRewardsExist {
+                amount: pending,
+            }));
+        }
+        self.remove_device(device_id)
+    }
+
+    /// Propagate an L1 removal (anyone may call). `proof` is the packed
+    /// Merkle proof, per `merkle.rs`, that the committed state root holds
+    /// the device's removal leaf. The device is then deregistered as by
+    /// its owner with `force`: pending rewards are forfeited and the
+    /// deposit is refunded to the owner.
+    pub fn deregister_with_proof(
+        &mut self,
+        device_id: FixedBytes<32>,
+        proof: Vec<u8>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_paused()?;
+        if !self.is_device_registered(device_id) {
+            return Err(VerifierError::DeviceNotRegistered(DeviceNotRegistered {
+                device_id,
+            }));
+        }
+        let algo = self.merkle_hash_algo.get().to::<u64>();
+        let removed = merkle::decode_proof(&proof).is_some_and(|proof| {
+            merkle::removal_leaf(self.vm(), algo, device_id).is_some_and(|leaf| {
+                merkle::verify_proof(self.vm(), algo, leaf, &proof, self.state_root.get())
+            })
+        });
+        if !removed {
+            return Err(VerifierError::InvalidStateProof(InvalidStateProof {}));
+        }
+        self.remove_device(device_id)
+    }
+
+    /// Permanently retire a device id (owner-only), e.g. once the hardware
+    /// is destroyed. Unlike deregistration this cannot be undone: the id
+    /// can never be registered again and never verifies.
+    pub fn retire_device(&mut self, device_id: FixedBytes<32>) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.set_flag(device_id, FLAG_RETIRED, true);
+        self.clear_memo(device_id);
+        Ok(())
+    }
+
+    /// Exchange the owners of two registered devices (owner-only), e.g. to
+    /// reassign hardware between fleets. Each device takes the other's
+    /// slot in `get_owner_devices`, so both owners keep their device
+    /// counts. A no-op when both have the same owner.
+    pub fn swap_device_owners(
+        &mut self,
+        device_a: FixedBytes<32>,
+        device_b: FixedBytes<32>,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.when_not_decommissioned()?;
+        let owner_a = self.registered_owner(device_a)?;
+        let owner_b = self.registered_owner(device_b)?;
+        if owner_a == owner_b {
+            return Ok(());
+        }
+
+        let position_a = self.owner_device_positions.get(device_a);
+        let position_b = self.owner_device_positions.get(device_b);
+        if let Some(mut slot) = self
+            .owner_devices
+            .setter(owner_a)
+            .setter(position_a.to::<usize>() - 1)
+        {
+            slot.set(device_b);
+        }
+        if let Some(mut slot) = self
+            .owner_devices
+            .setter(owner_b)
+            .setter(position_b.to::<usize>() - 1)
+        {
+            slot.set(device_a);
+        }
+        self.owner_device_positions.setter(device_a).set(position_b);
+        self.owner_device_positions.setter(device_b).set(position_a);
+        self.device_owners.setter(device_a).set(owner_b);
+        self.device_owners.setter(device_b).set(owner_a);
+        Ok(())
+    }
+
+    /// Move up to `limit` of `old`'s devices to `new` (owner-only), e.g.
+    /// when a partner acquires a fleet, emitting `OwnershipTransferred`
+    /// for each. `limit` is capped at `MAX_PAGE_LIMIT` (0 selects the cap).
+    ///
+    /// Devices are taken from the end of `old`'s list, so the call itself
+    /// is the cursor: it returns how many devices `old` still owns, and
+    /// the caller repeats it until that comes back as 0.
+    pub fn reassign_owner(
+        &mut self,
+        old: Address,
+        new: Address,
+        limit: U256,
+    ) -> Result<U256, VerifierError> {
+        self.only_owner()?;
+        self.when_not_decommissioned()?;
+        if new.is_zero() {
+            return Err(VerifierError::InvalidOwner(InvalidOwner {}));
+        }
+        if old == new {
+            return Ok(U256::ZERO);
+        }
+
+        let remaining = self.owner_devices.get(old).len();
+        let page = page::PageCursor::new(U256::ZERO, limit, remaining);
+        for _ in page.indices() {
+            let Some(device_id) = self.owner_devices.setter(old).pop() else {
+                break;
+            };
+            let mut owned = self.owner_devices.setter(new);
+            owned.push(device_id);
+            let position = U256::from(owned.len());
+            self.owner_device_positions.setter(device_id).set(position);
+            self.device_owners.setter(device_id).set(new);
+            log(
+                self.vm(),
+                OwnershipTransferred {
+                    device_id,
+                    previous_owner: old,
+                    new_owner: new,
+                },
+            );
+        }
+        Ok(U256::from(self.owner_devices.get(old).len()))
+    }
+
+    /// Revoke many devices at once (owner-only), e.g. after a supply-chain
+    /// compromise. Emits `DeviceRevoked` for each newly revoked device;
+    /// already-revoked ids are skipped. At most `MAX_REVOKE_BATCH` ids.
+    pub fn revoke_devices_batch(
+        &mut self,
+        device_ids: Vec<FixedBytes<32>>,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if device_ids.len() > MAX_REVOKE_BATCH {
+            return Err(VerifierError::BatchTooLarge(BatchTooLarge {
+                length: U256::from(device_ids.len()),
+            }));
+        }
+        for device_id in device_ids {
+            self.revoke(device_id);
+        }
+        Ok(())
+    }
+
+    /// Revoke a device by revealing its leaked attestation private key
+    /// (anyone). `leaked_key` is the 32-byte secret; if it derives the
+    /// device's attestation key, or the address behind its key commitment,
+    /// the device is revoked with `DeviceRevoked` and the caller is paid
+    /// `compromise_bounty` from the fee pool (capped at the pool). Anyone
+    /// watching the mempool learns the key too, so the bounty goes to
+    /// whoever lands first. An already revoked device is a no-op.
+    pub fn revoke_with_key_proof(
+        &mut self,
+        device_id: FixedBytes<32>,
+        leaked_key: Vec<u8>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.registered_owner(device_id)?;
+        let derived = signature::address_of_secret_key(self.vm(), &leaked_key).unwrap_or_default();
+        let key = self.device_attestation_keys.get(device_id);
+        let commitment = self.device_key_commitment.get(device_id);
+        let matches = !derived.is_zero()
+            && if !key.is_zero() {
+                derived == key
+            } else {
+                !commitment.is_zero()
+                    && self.vm().native_keccak256(derived.as_slice()) == commitment
+            };
+        if !matches {
+            return Err(VerifierError::KeyProofMismatch(KeyProofMismatch {
+                derived,
+            }));
+        }
+        if !self.revoke(device_id) {
+            return Ok(());
+        }
+
+        let pool = self.fee_pool.get();
+        let bounty = self.compromise_bounty.get().min(pool);
+        self.fee_pool.set(pool - bounty);
+        let reporter = self.vm().msg_sender();
+        self.settle_device_fee(reporter, bounty)
+    }
+
+    /// Set the ETH bounty for `revoke_with_key_proof` (owner-only, 0 = none).
+    pub fn set_compromise_bounty(&mut self, bounty: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::COMPROMISE_BOUNTY,
+            self.compromise_bounty.get(),
+            bounty,
+        );
+        self.compromise_bounty.set(bounty);
+        Ok(())
+    }
+
+    /// View: Get `compromise_bounty`
+    pub fn get_compromise_bounty(&self) -> Result<U256, VerifierError> {
+        Ok(self.compromise_bounty.get())
+    }
+
+    /// Force a device's verifications to pass or fail regardless of the
+    /// normal checks, for incident response and testing (owner-only).
+    /// `mode` is one of `OVERRIDE_*`; `OVERRIDE_NORMAL` lifts the override.
+    pub fn set_verification_override(
+        &mut self,
+        device_id: FixedBytes<32>,
+        mode: u8,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if mode > OVERRIDE_FORCE_FAIL {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.set_flag(device_id, FLAG_FORCE_PASS, mode == OVERRIDE_FORCE_PASS);
+        self.set_flag(device_id, FLAG_FORCE_FAIL, mode == OVERRIDE_FORCE_FAIL);
+        Ok(())
+    }
+
+    /// Set the address whose signature every attestation of this device
+    /// must carry (device owner or relayer). The zero address is rejected
+    /// with `InvalidSigner`, since no signature can recover to it.
+    pub fn set_device_attestation_key(
+        &mut self,
+        device_id: FixedBytes<32>,
+        key: Address,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.only_device_owner_or_relayer(device_id)?;
+        if key.is_zero() {
+            return Err(VerifierError::InvalidSigner(InvalidSigner { signer: key }));
+        }
+        self.install_device_key(device_id, key);
+        Ok(())
+    }
+
+    /// Rotate a device's attestation key, possibly to another signature
+    /// scheme (device owner or relayer). `signature` must be by the
+    /// device's current secp256k1 key, or the key behind its commitment,
+    /// over `key_rotation_digest(device_id, new_scheme, new_key, nonce)`,
+    /// proving the new key is a continuation of the old one; each proof is
+    /// single-use.
+    ///
+    /// For `KEY_SCHEME_SECP256K1`, `new_key` is the key's address in its
+    /// low 20 bytes and the rotation behaves like
+    /// `set_device_attestation_key`. `KEY_SCHEME_ED25519` is refused: no
+    /// ed25519 verifier exists on-chain, so a device rotated to it could
+    /// never attest again.
+    ///
+    /// Reverts with `UnknownKeyScheme`, with `InvalidSigner` on a zero or
+    /// malformed key, and with `BadSignature` on an invalid proof.
+    pub fn rotate_key_and_scheme(
+        &mut self,
+        device_id: FixedBytes<32>,
+        new_scheme: u8,
+        new_key: FixedBytes<32>,
+        signature: Vec<u8>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.only_device_owner_or_relayer(device_id)?;
+        let address = Address::from_slice(&new_key[12..]);
+        let valid_key = match new_scheme {
+            KEY_SCHEME_SECP256K1 => new_key[..12] == [0u8; 12] && !address.is_zero(),
+            scheme => return Err(VerifierError::UnknownKeyScheme(UnknownKeyScheme { scheme })),
+        };
+        if !valid_key {
+            return Err(VerifierError::InvalidSigner(InvalidSigner {
+                signer: address,
+            }));
+        }
+
+        let nonce = self.key_rotation_nonces.get(device_id);
+        let digest = self.key_rotation_digest(device_id, new_scheme, new_key, nonce);
+        let current_scheme = self.device_key_schemes.get(device_id);
+        let key = self.device_attestation_keys.get(device_id);
+        let commitment = self.device_key_commitment.get(device_id);
+        let continuous = current_scheme == U256::from(KEY_SCHEME_SECP256K1)
+            && signature::recover_signer(self.vm(), digest, &signature).is_some_and(|signer| {
+                if !key.is_zero() {
+                    signer == key
+                } else {
+                    !commitment.is_zero()
+                        && self.vm().native_keccak256(signer.as_slice()) == commitment
+                }
+            });
+        if !continuous {
+            return Err(VerifierError::BadSignature(BadSignature {}));
+        }
+        self.key_rotation_nonces
+            .setter(device_id)
+            .set(nonce + U256::from(1));
+
+        self.install_device_key(device_id, address);
+        Ok(())
+    }
+
+    /// View: Get a device's `(KEY_SCHEME_*, key)`: the attestation key
+    /// address in the low 20 bytes for secp256k1, the public key for ed25519
+    pub fn get_device_key_scheme(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<(u8, FixedBytes<32>), VerifierError> {
+        let scheme = self.device_key_schemes.get(device_id).to::<u8>();
+        let key = if scheme == KEY_SCHEME_SECP256K1 {
+            self.device_attestation_keys.get(device_id).into_word()
+        } else {
+            self.device_ed25519_keys.get(device_id)
+        };
+        Ok((scheme, key))
+    }
+
+    /// View: Nonce the next `rotate_key_and_scheme` proof must cover
+    pub fn get_key_rotation_nonce(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.key_rotation_nonces.get(device_id))
+    }
+
+    /// Set how long a device's previous attestation key keeps verifying
+    /// after `set_device_attestation_key` replaces it (owner-only), so
+    /// attestations signed just before a rotation still land. Zero stops
+    /// accepting it immediately.
+    pub fn set_device_key_grace(&mut self, grace: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(config::DEVICE_KEY_GRACE, self.device_key_grace.get(), grace);
+        self.device_key_grace.set(grace);
+        Ok(())
+    }
+
+    /// View: Get `(previous_key, rotated_at, grace)` for a device
+    pub fn get_device_previous_key(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<(Address, U256, U256), VerifierError> {
+        Ok((
+            self.device_previous_key.get(device_id),
+            self.device_key_rotated_at.get(device_id),
+            self.device_key_grace.get(),
+        ))
+    }
+
+    /// Replace a multi-key device's whole signer set and quorum threshold
+    /// in one call (device owner or relayer).
+    ///
+    /// The set is informational: it is recorded for off-chain verifiers
+    /// reading `get_device_signers`, and attestation verification here
+    /// still checks only the device's single attestation key.
+    ///
+    /// Signers must be non-zero and distinct, at most
+    /// `MAX_SIGNERS_PER_DEVICE` of them, and `threshold` must be in
+    /// `1..=signers.len()`; an empty set with threshold 0 clears it.
+    pub fn set_device_signers(
+        &mut self,
+        device_id: FixedBytes<32>,
+        signers: Vec<Address>,
+        threshold: U256,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.only_device_owner_or_relayer(device_id)?;
+        if signers.len() > MAX_SIGNERS_PER_DEVICE {
+            return Err(VerifierError::TooManySigners(TooManySigners {
+                max: U256::from(MAX_SIGNERS_PER_DEVICE),
+            }));
+        }
+        if threshold > U256::from(signers.len()) || (threshold.is_zero() && !signers.is_empty()) {
+            return Err(VerifierError::InvalidThreshold(InvalidThreshold {
+                threshold,
+            }));
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if signer.is_zero() || signers[..i].contains(signer) {
+                return Err(VerifierError::InvalidSigner(InvalidSigner {
+                    signer: *signer,
+                }));
+            }
+        }
+
+        let mut set = self.device_signers.setter(device_id);
+        set.erase();
+        for signer in signers {
+            set.push(signer);
+        }
+        self.device_signer_threshold
+            .setter(device_id)
+            .set(threshold);
+        Ok(())
+    }
+
+    /// Add one signer to a device's set (device owner or relayer), keeping
+    /// the quorum threshold; a first signer gets threshold 1.
+    pub fn add_delegate_signer(
+        &mut self,
+        device_id: FixedBytes<32>,
+        signer: Address,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.only_device_owner_or_relayer(device_id)?;
+        let set = self.device_signers.get(device_id);
+        if signer.is_zero() || (0..set.len()).any(|i| set.get(i) == Some(signer)) {
+            return Err(VerifierError::InvalidSigner(InvalidSigner { signer }));
+        }
+        if set.len() >= MAX_SIGNERS_PER_DEVICE {
+            return Err(VerifierError::TooManySigners(TooManySigners {
+                max: U256::from(MAX_SIGNERS_PER_DEVICE),
+            }));
+        }
+
+        self.device_signers.setter(device_id).push(signer);
+        if self.device_signer_threshold.get(device_id).is_zero() {
+            self.device_signer_threshold
+                .setter(device_id)
+                .set(U256::from(1));
+        }
+        Ok(())
+    }
+
+    /// View: Get a device's signer set and quorum threshold
+    pub fn get_device_signers(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<(Vec<Address>, U256), VerifierError> {
+        let set = self.device_signers.get(device_id);
+        let signers = (0..set.len()).filter_map(|i| set.get(i)).collect();
+        Ok((signers, self.device_signer_threshold.get(device_id)))
+    }
+
+    /// Issue a fresh challenge for a device (device owner or relayer),
+    /// replacing any outstanding one. The TEE must return it as the CBOR
+    /// `challenge` field of its next attestation.
+    pub fn issue_challenge(
+        &mut self,
+        device_id: FixedBytes<32>,
+    ) -> Result<FixedBytes<32>, VerifierError> {
+        self.when_not_decommissioned()?;
+        self.only_device_owner_or_relayer(device_id)?;
+        let nonce = self.challenge_nonces.get(device_id);
+        self.challenge_nonces
+            .setter(device_id)
+            .set(nonce + U256::from(1));
+
+        let mut message = Vec::with_capacity(16 + 20 + 32 * 3);
+        message.extend_from_slice(b"CERTID_CHALLENGE");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(&nonce.to_be_bytes::<32>());
+        message.extend_from_slice(&U256::from(self.vm().block_timestamp()).to_be_bytes::<32>());
+        let challenge = self.vm().native_keccak256(&message);
+        self.outstanding_challenges.setter(device_id).set(challenge);
+        Ok(challenge)
+    }
+
+    /// View: Get a device's outstanding challenge (zero if none)
+    pub fn get_outstanding_challenge(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<FixedBytes<32>, VerifierError> {
+        Ok(self.outstanding_challenges.get(device_id))
+    }
+
+    /// Attach a metadata blob of at most `MAX_METADATA_LEN` bytes to a
+    /// device (device owner or relayer). Replaces any previous metadata;
+    /// cleared on deregistration.
+    pub fn set_device_metadata(
+        &mut self,
+        device_id: FixedBytes<32>,
+        data: Vec<u8>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        self.only_device_owner_or_relayer(device_id)?;
+        if data.len() > MAX_METADATA_LEN {
+            return Err(VerifierError::MetadataTooLarge(MetadataTooLarge {
+                length: U256::from(data.len()),
+            }));
+        }
+        self.device_metadata.setter(device_id).set_bytes(data);
+        Ok(())
+    }
+
+    /// Label a device, e.g. with its deployment cohort (device owner or
+    /// relayer). Adding a tag the device already has is a no-op.
+    pub fn add_tag(
+        &mut self,
+        device_id: FixedBytes<32>,
+        tag: FixedBytes<32>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.only_device_owner_or_relayer(device_id)?;
+        if self.device_tags.getter(device_id).get(tag) {
+            return Ok(());
+        }
+        self.device_tags.setter(device_id).setter(tag).set(true);
+        let mut tags = self.device_tag_list.setter(device_id);
+        tags.push(tag);
+        let position = U256::from(tags.len());
+        self.device_tag_positions
+            .setter(device_id)
+            .setter(tag)
+            .set(position);
+        Ok(())
+    }
+
+    /// Remove a label from a device (device owner or relayer). Removing a
+    /// tag the device does not have is a no-op.
+    pub fn remove_tag(
+        &mut self,
+        device_id: FixedBytes<32>,
+        tag: FixedBytes<32>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.only_device_owner_or_relayer(device_id)?;
+        let position = self.device_tag_positions.getter(device_id).get(tag);
+        if position.is_zero() {
+            return Ok(());
+        }
+
+        let index = position.to::<usize>() - 1;
+        let mut tags = self.device_tag_list.setter(device_id);
+        let last_index = tags.len() - 1;
+        let last = tags.get(last_index).unwrap_or_default();
+        if index != last_index {
+            if let Some(mut slot) = tags.setter(index) {
+                slot.set(last);
+            }
+        }
+        tags.erase_last();
+        let mut positions = self.device_tag_positions.setter(device_id);
+        if index != last_index {
+            positions.setter(last).set(position);
+        }
+        positions.delete(tag);
+        self.device_tags.setter(device_id).delete(tag);
+        Ok(())
+    }
+
+    /// Update the Trust Score of a registered device.
+    ///
+    /// Called by the CertID Bridge Relayer after the Cosmos L1 recalculates
+    /// the deterministic trust score (see x/hardware/keeper/scoring.go);
+    /// only relayers and the owner may call (anyone, before `initialize`),
+    /// and only while no validator quorum is configured. With an `input_scale` set, `new_score` is on that scale and is
+    /// rescaled to 0–100; values above the scale revert. The change is then
+    /// clamped to `max_score_delta` from the previous score; a device's
+    /// first score is taken as is.
+    pub fn update_trust_score(
+        &mut self,
+        device_id: FixedBytes<32>,
+        new_score: U256,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        let caller = self.vm().msg_sender();
+        let uninitialized = self.owner.get().is_zero() && !self.renounced.get();
+        if !uninitialized && caller != self.owner.get() && !self.relayers.get(caller) {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        let score = self.relayed_score(device_id, new_score)?;
+        self.apply_score(device_id, score);
+        if self.relayers.get(caller) {
+            self.note_relayer_activity();
+        }
+        Ok(())
+    }
+
+    /// Update a registered device's score with proof that the Cosmos
+    /// validator set agreed on it.
+    ///
+    /// `consensus_proof` is a concatenation of 65-byte validator signatures
+    /// over `consensus_digest(device_id, new_score, seq)`; at least
+    /// `consensus_threshold` distinct validators must have signed, and
+    /// signatures from anyone else are ignored. `seq` shares the device's
+    /// bridge sequence, so it must exceed the last applied one. The score
+    /// is then rescaled and clamped as in `update_trust_score`.
+    pub fn update_trust_score_with_consensus(
+        &mut self,
+        device_id: FixedBytes<32>,
+        new_score: U256,
+        seq: U256,
+        consensus_proof: Vec<u8>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        self.registered_owner(device_id)?;
+        if seq <= self.bridge_seqs.get(device_id) {
+            return Err(VerifierError::StaleSequence(StaleSequence { seq }));
+        }
+        if !consensus_proof
+            .len()
+            .is_multiple_of(signature::SIGNATURE_LEN)
+        {
+            return Err(VerifierError::BadSignature(BadSignature {}));
+        }
+
+        let digest = self.consensus_digest(device_id, new_score, seq);
+        let mut signers: Vec<Address> = Vec::new();
+        for sig in consensus_proof.chunks(signature::SIGNATURE_LEN) {
+            let Some(signer) = signature::recover_signer(self.vm(), digest, sig) else {
+                continue;
+            };
+            if !signers.contains(&signer) && self.is_consensus_validator(signer) {
+                signers.push(signer);
+            }
+        }
+        let threshold = self.consensus_threshold.get();
+        if threshold.is_zero() || U256::from(signers.len()) < threshold {
+            return Err(VerifierError::QuorumNotMet(QuorumNotMet {
+                signers: U256::from(signers.len()),
+            }));
+        }
+
+        let score = self.clamp_score_delta(device_id, self.rescale_score(new_score)?);
+        self.bridge_seqs.setter(device_id).set(seq);
+        self.apply_score(device_id, score);
+        if self.relayers.get(self.vm().msg_sender()) {
+            self.note_relayer_activity();
+        }
+        Ok(())
+    }
+
+    /// Apply a score update signed on the Cosmos side by a bridge key.
+    ///
+    /// Callable by anyone, so the relayer that submits it need not be
+    /// trusted. `message` uses the layout documented in `bridge.rs` and must
+    /// name this chain and this contract; `seq` must exceed the device's last applied sequence and `expiry`
+    /// must not have passed. Like `update_trust_score`, it reverts with
+    /// `ConsensusRequired` while a validator quorum is configured, and the
+    /// score is rescaled and clamped to `max_score_delta`.
+    pub fn apply_bridge_message(
+        &mut self,
+        message: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        let decoded = bridge::decode_bridge_message(&message)
+            .filter(|decoded| {
+                decoded.chain_id == U256::from(self.vm().chain_id())
+                    && decoded.verifier == self.vm().contract_address()
+            })
+            .ok_or(VerifierError::MalformedBridgeMessage(
+                MalformedBridgeMessage {},
+            ))?;
+
+        let hash = self.vm().native_keccak256(&message);
+        let digest = signature::eth_signed_message_hash(self.vm(), hash);
+        let signer = signature::recover_signer(self.vm(), digest, &signature);
+        if !signer.is_some_and(|signer| self.bridge_keys.get(signer)) {
+            return Err(VerifierError::BadSignature(BadSignature {}));
+        }
+
+        let device_id = decoded.device_id;
+        self.registered_owner(device_id)?;
+        let seq = U256::from(decoded.seq);
+        if seq <= self.bridge_seqs.get(device_id) {
+            return Err(VerifierError::StaleSequence(StaleSequence { seq }));
+        }
+        if self.vm().block_timestamp() > decoded.expiry {
+            return Err(VerifierError::MessageExpired(MessageExpired {
+                expiry: decoded.expiry,
+            }));
+        }
+        let score = self.relayed_score(device_id, decoded.score)?;
+
+        self.bridge_seqs.setter(device_id).set(seq);
+        self.apply_score(device_id, score);
+        if self.relayers.get(self.vm().msg_sender()) {
+            self.note_relayer_activity();
+        }
+        Ok(())
+    }
+
+    /// Apply a relayer-issued score certificate (anyone may call,
+    /// typically the device owner, who then pays the gas).
+    ///
+    /// `signature` is a relayer's 65-byte signature over
+    /// `score_certificate_digest(device_id, score, seq)`. `seq` shares the
+    /// device's bridge sequence, so it must exceed the last applied one
+    /// and each certificate applies at most once. As with
+    /// `update_trust_score`, certificates revert with `ConsensusRequired`
+    /// while a validator quorum is configured, and the score is rescaled
+    /// and clamped to `max_score_delta`.
+    pub fn submit_score_certificate(
+        &mut self,
+        device_id: FixedBytes<32>,
+        score: U256,
+        seq: U256,
+        signature: Vec<u8>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        self.registered_owner(device_id)?;
+        if seq <= self.bridge_seqs.get(device_id) {
+            return Err(VerifierError::StaleSequence(StaleSequence { seq }));
+        }
+
+        let digest = self.score_certificate_digest(device_id, score, seq);
+        let signer = signature::recover_signer(self.vm(), digest, &signature);
+        if !signer.is_some_and(|signer| self.is_relayer_signer(signer)) {
+            return Err(VerifierError::BadSignature(BadSignature {}));
+        }
+
+        let score = self.relayed_score(device_id, score)?;
+        self.bridge_seqs.setter(device_id).set(seq);
+        self.apply_score(device_id, score);
+        Ok(())
+    }
+
+    /// Schedule a score to take effect at `effective_at` (owner or
+    /// relayer), replacing any staged one. The score goes through the same
+    /// consensus gate, rescaling and delta clamp as `update_trust_score`,
+    /// against the current score. From `effective_at` on, reads see it,
+    /// and the device's next verification or a `commit_staged_score`
+    /// writes it through to the aggregates.
+    pub fn stage_trust_score(
+        &mut self,
+        device_id: FixedBytes<32>,
+        score: U256,
+        effective_at: U256,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        let caller = self.vm().msg_sender();
+        if caller != self.owner.get() && !self.relayers.get(caller) {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        self.registered_owner(device_id)?;
+        let score = self.relayed_score(device_id, score)?;
+        if effective_at.is_zero() {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.staged_scores.setter(device_id).set(score);
+        self.staged_score_effective_at
+            .setter(device_id)
+            .set(effective_at);
+        Ok(())
+    }
+
+    /// Write an effective staged score into storage (anyone may call).
+    /// Reverts with `StagedScoreNotReady` if none is staged or it is not
+    /// yet effective.
+    pub fn commit_staged_score(&mut self, device_id: FixedBytes<32>) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        if !self.settle_staged_score(device_id) {
+            return Err(VerifierError::StagedScoreNotReady(StagedScoreNotReady {
+                effective_at: self.staged_score_effective_at.get(device_id),
+            }));
+        }
+        Ok(())
+    }
+
+    /// View: Get `(score, effective_at)` of a device's staged score
+    /// (`effective_at` = 0 when none is staged)
+    pub fn get_staged_score(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<(U256, U256), VerifierError> {
+        Ok((
+            self.staged_scores.get(device_id),
+            self.staged_score_effective_at.get(device_id),
+        ))
+    }
+
+    /// Restore a wrongly slashed device's score (device owner only).
+    ///
+    /// `signature` is a relayer's 65-byte `r || s || v` EIP-191 signature
+    /// over `keccak256(abi.encodePacked("CERTID_RESTORE", address(this),
+    /// device_id, score, nonce))`, where `nonce` is `get_restore_nonce`.
+    /// On success the score is set, the failure counter is reset, and the
+    /// nonce is bumped so the signature cannot be replayed.
+    pub fn restore_score(
+        &mut self,
+        device_id: FixedBytes<32>,
+        score: U256,
+        signature: Vec<u8>,
+    ) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        self.only_device_owner(device_id)?;
+        Self::score_to_u8(score)?;
+
+        let nonce = self.restore_nonces.get(device_id);
+        let digest = self.restore_digest(device_id, score, nonce);
+        let signer = signature::recover_signer(self.vm(), digest, &signature);
+        if !signer.is_some_and(|signer| self.is_relayer_signer(signer)) {
+            return Err(VerifierError::BadSignature(BadSignature {}));
+        }
+
+        self.restore_nonces
+            .setter(device_id)
+            .set(nonce + U256::from(1));
+        self.apply_score(device_id, score);
+        self.failure_count.delete(device_id);
+        Ok(())
+    }
+
+    /// Verify a TEE Attestation — the "Stylus Magic".
+    ///
+    /// This is the high-value operation that justifies using Stylus over
+    /// Solidity. Cryptographic signature verification in WASM is ~10x
+    /// cheaper than the equivalent EVM opcodes.
+    ///
+    /// Grant Pilot behavior:
+    ///   - Checks if the device is registered and has a valid trust score
+    ///     under the current policy (threshold and score grace), with the
+    ///     device class's threshold and interval taking precedence
+    ///   - Increments the global verification counter
+    ///
+    /// Devices with an attestation key or key commitment must append a
+    /// 65-byte signature over `attestation_digest` to the payload; the
+    /// recovered signer must equal the key (or, within `device_key_grace`
+    /// of a key change, the previous key), or hash to the commitment.
+    ///
+    /// Reverts on oversized payloads, on verifications closer together
+    /// than the policy's `min_interval`, on a payload nonce that is reused
+    /// or outside `nonce_window`, with `CounterRollback` once the device
+    /// has reported a hardware counter and the payload's is not higher, with `InsufficientFee` when
+    /// `msg_value` is below the fee for the payload's attestation type,
+    /// and with `InvalidCertChain` when the payload carries a certificate
+    /// chain that does not lead from the device signer to a trusted root.
+    ///
+    /// Production behavior (Phase 3):
+    ///   - Verifies the `attestation_data` signature against the manufacturer's
+    ///     public key (ARM TrustZone / Apple Secure Enclave)
+    #[payable]
+    pub fn verify_tee_attestation(
+        &mut self,
+        device_id: FixedBytes<32>,
+        attestation_data: Vec<u8>,
+    ) -> Result<bool, VerifierError> {
+        let status = self.run_verification(device_id, &attestation_data, false)?;
+        Ok(status == STATUS_VERIFIED)
+    }
+
+    /// Highest-assurance variant of `verify_tee_attestation`, returning a
+    /// `STATUS_*` code instead of a bool.
+    ///
+    /// On top of every regular check, the device must carry a key or key
+    /// commitment and sign the payload, and must have verified within
+    /// `strict_liveness_window` seconds. A revoked or retired device gets
+    /// `STATUS_REJECTED` before any payload check or fee. Otherwise the
+    /// first failing check decides the status, in the order unregistered
+    /// (`STATUS_REJECTED`), signature, score, freshness, then challenge and
+    /// claims; a verified call has the same effects as the regular one.
+    /// Contract-wide freezes (pause, relayer silence) revert before any
+    /// device check.
+    #[payable]
+    pub fn verify_tee_attestation_strict(
+        &mut self,
+        device_id: FixedBytes<32>,
+        attestation_data: Vec<u8>,
+    ) -> Result<u8, VerifierError> {
+        self.run_verification(device_id, &attestation_data, true)
+    }
+
+    /// Verify a device whose HSM signs a 32-byte digest directly rather
+    /// than a payload. `digest` must equal `attestation_digest_for`, which
+    /// binds the device id and its next digest nonce, and `signature` must
+    /// recover from it to the device's key (or previous key within
+    /// `device_key_grace`) or key commitment; devices with neither fail
+    /// the signature check. A pass consumes the nonce.
+    ///
+    /// Otherwise behaves like `verify_tee_attestation` with an empty
+    /// payload, so policies that need payload fields (claims, models,
+    /// challenges, beacons) fail or revert as they would there. Reverts
+    /// with `DigestMismatch` on any other digest.
+    #[payable]
+    pub fn verify_tee_attestation_digest(
+        &mut self,
+        device_id: FixedBytes<32>,
+        digest: FixedBytes<32>,
+        signature: Vec<u8>,
+    ) -> Result<bool, VerifierError> {
+        let now = U256::from(self.vm().block_timestamp());
+        self.verification_guards(now)?;
+        self.when_within_balance_cap()?;
+        let flags = self.device_flags.get(device_id);
+        if let Some(status) = self.apply_override(device_id, flags) {
+            return Ok(status == STATUS_VERIFIED);
+        }
+        if self.reject_inactive(device_id, flags).is_some() {
+            return Ok(false);
+        }
+        let flags = self.settle_staged_score_flags(device_id, flags);
+
+        let nonce = self.digest_nonces.get(device_id);
+        let expected = self.device_digest(device_id, nonce);
+        if digest != expected {
+            return Err(VerifierError::DigestMismatch(DigestMismatch { expected }));
+        }
+        let signed = signature::recover_signer(self.vm(), digest, &signature)
+            .is_some_and(|signer| self.is_device_signer(device_id, signer));
+        let status = self.evaluate_payload(device_id, None, signed, false, now, flags)?;
+        if status == STATUS_VERIFIED {
+            self.digest_nonces
+                .setter(device_id)
+                .set(nonce + U256::from(1));
+            self.last_attestation_hashes.setter(device_id).set(digest);
+        }
+        Ok(status == STATUS_VERIFIED)
+    }
+
+    /// View: The digest a device's next `verify_tee_attestation_digest`
+    /// must carry: `keccak256("CERTID_DIGEST" || contract || device_id ||
+    /// nonce)`, signed as-is with no `personal_sign` prefix
+    pub fn attestation_digest_for(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<FixedBytes<32>, VerifierError> {
+        Ok(self.device_digest(device_id, self.digest_nonces.get(device_id)))
+    }
+
+    /// View: Run each gate of `verify_tee_attestation` independently and
+    /// report `(registered, above_threshold, sig_valid, not_revoked,
+    /// not_expired, final)`, for debugging why a verification would fail.
+    /// `final` is the regular path's verdict: every gate plus the contract
+    /// guards and payload checks (size, decoding, epoch, model, challenge,
+    /// claims, rate limit, nonce, hardware counter), with owner overrides
+    /// applied. Fees are not simulated.
+    pub fn simulate_verification(
+        &self,
+        device_id: FixedBytes<32>,
+        attestation_data: Vec<u8>,
+    ) -> Result<(bool, bool, bool, bool, bool, bool), VerifierError> {
+        let now = U256::from(self.vm().block_timestamp());
+        let flags = self.device_flags.get(device_id);
+        let (payload, sig_valid, keyed, signer) =
+            self.check_attestation_signature(device_id, &attestation_data);
+        let sig_valid = sig_valid
+            && cbor::decode_attestation(payload)
+                .and_then(|a| a.cert_chain)
+                .is_none_or(|chain| self.check_cert_chain(device_id, signer, chain).is_ok());
+        let (threshold, _) = self.device_policy(device_id);
+
+        let registered = self.is_device_registered(device_id);
+        let above_threshold = flags.bit(FLAG_HAS_SCORE)
+            && self.effective_score(device_id) >= self.score_floor(threshold);
+        let not_revoked = !flags.bit(FLAG_REVOKED) && !flags.bit(FLAG_RETIRED);
+        let not_expired = self.is_score_fresh(device_id, now);
+
+        let mode = Self::override_mode(flags);
+        let guards_pass = self.verification_guards(now).is_ok();
+        let final_verdict = guards_pass
+            && match mode {
+                OVERRIDE_NORMAL => {
+                    registered
+                        && above_threshold
+                        && sig_valid
+                        && not_revoked
+                        && not_expired
+                        && self.simulate_payload_checks(
+                            device_id,
+                            &attestation_data,
+                            payload,
+                            keyed,
+                            flags,
+                            now,
+                        )
+                }
+                mode => mode == OVERRIDE_FORCE_PASS,
+            };
+        Ok((
+            registered,
+            above_threshold,
+            sig_valid,
+            not_revoked,
+            not_expired,
+            final_verdict,
+        ))
+    }
+
+    /// Verify every `(device_ids[i], attestations[i])` pair, all or
+    /// nothing: each pair is first dry-run as in `simulate_verification`,
+    /// and if any would fail the call returns false with no effects.
+    /// Otherwise all are verified for real and the call returns true.
+    ///
+    /// Reverts with `LengthMismatch` on vectors of different lengths,
+    /// `BatchTooLarge` past `MAX_VERIFY_ALL` pairs, on the contract guards
+    /// and on any revert of the regular path. Not payable, so a device
+    /// whose verification carries a fee reverts with `InsufficientFee`.
+    pub fn verify_all(
+        &mut self,
+        device_ids: Vec<FixedBytes<32>>,
+        attestations: Vec<Vec<u8>>,
+    ) -> Result<bool, VerifierError> {
+        if device_ids.len() != attestations.len() {
+            return Err(VerifierError::LengthMismatch(LengthMismatch {
+                device_ids: U256::from(device_ids.len()),
+                attestations: U256::from(attestations.len()),
+            }));
+        }
+        if device_ids.len() > MAX_VERIFY_ALL {
+            return Err(VerifierError::BatchTooLarge(BatchTooLarge {
+                length: U256::from(device_ids.len()),
+            }));
+        }
+        self.verification_guards(U256::from(self.vm().block_timestamp()))?;
+        for (device_id, attestation_data) in device_ids.iter().zip(&attestations) {
+            if !self
+                .simulate_verification(*device_id, attestation_data.clone())?
+                .5
+            {
+                return Ok(false);
+            }
+        }
+        for (device_id, attestation_data) in device_ids.into_iter().zip(attestations) {
+            if self.run_verification(device_id, &attestation_data, false)? != STATUS_VERIFIED {
+                return Err(VerifierError::VerifyAllFailed(VerifyAllFailed {
+                    device_id,
+                }));
+            }
+        }
+        Ok(true)
+    }
+
+    /// Verify an attestation and apply `new_score` under bridge sequence
+    /// `seq` in one transaction (relayer-only), as `verify_tee_attestation`
+    /// followed by `update_trust_score`: the score is rescaled and clamped
+    /// the same way, and the call reverts with `ConsensusRequired` while a
+    /// consensus threshold is set.
+    ///
+    /// All or nothing: reverts with `DeviceNotRegistered`, `InvalidScore`
+    /// or `StaleSequence` before verifying, on any revert of the regular
+    /// path, and with `AttestationNotVerified` when the attestation does
+    /// not verify, so a failed call leaves neither the score nor the
+    /// counters behind.
+    #[payable]
+    pub fn relayer_verify_and_update(
+        &mut self,
+        device_id: FixedBytes<32>,
+        attestation_data: Vec<u8>,
+        new_score: U256,
+        seq: U256,
+    ) -> Result<(), VerifierError> {
+        let caller = self.vm().msg_sender();
+        if !self.relayers.get(caller) {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        if !self.consensus_threshold.get().is_zero() {
+            return Err(VerifierError::ConsensusRequired(ConsensusRequired {}));
+        }
+        self.registered_owner(device_id)?;
+        let score = self.rescale_score(new_score)?;
+        if seq <= self.bridge_seqs.get(device_id) {
+            return Err(VerifierError::StaleSequence(StaleSequence { seq }));
+        }
+
+        let status = self.run_verification(device_id, &attestation_data, false)?;
+        if status != STATUS_VERIFIED {
+            return Err(VerifierError::AttestationNotVerified(
+                AttestationNotVerified { device_id, status },
+            ));
+        }
+        self.bridge_seqs.setter(device_id).set(seq);
+        let score = self.clamp_score_delta(device_id, score);
+        self.apply_score(device_id, score);
+        self.note_relayer_activity();
+        Ok(())
+    }
+
+    /// Memoize signature checks within a block (owner-only, off by
+    /// default). While on, verifying a device again in the same block with
+    /// the same attestation reuses the first call's signature, endorsement
+    /// and certificate-chain verdict instead of recovering them again.
+    /// Every device check, fee, counter and reward still applies, and a
+    /// key change, revocation, retirement or score update drops the memo.
+    pub fn set_verification_memo(&mut self, enabled: bool) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::MEMOIZE_VERIFICATIONS,
+            U256::from(self.memoize_verifications.get()),
+            U256::from(enabled),
+        );
+        self.memoize_verifications.set(enabled);
+        Ok(())
+    }
+
+    /// View: Whether signature checks are memoized within a block
+    pub fn get_verification_memo(&self) -> Result<bool, VerifierError> {
+        Ok(self.memoize_verifications.get())
+    }
+
+    /// Set the strict path's liveness window in seconds (owner-only).
+    pub fn set_strict_liveness_window(&mut self, window: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::STRICT_LIVENESS_WINDOW,
+            self.strict_liveness_window.get(),
+            window,
+        );
+        self.strict_liveness_window.set(window);
+        Ok(())
+    }
+
+    /// View: Get the strict path's liveness window
+    pub fn get_strict_liveness_window(&self) -> Result<U256, VerifierError> {
+        Ok(self.strict_liveness_window.get())
+    }
+
+    /// Let attestation nonces arrive out of order (owner-only): a payload
+    /// nonce is accepted anywhere in `[next, next + window]` if it has not
+    /// been used. 0 requires exactly the next nonce; at most
+    /// `MAX_NONCE_WINDOW`.
+    pub fn set_nonce_window(&mut self, window: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        if window > U256::from(MAX_NONCE_WINDOW) {
+            return Err(VerifierError::InvalidPolicy(InvalidPolicy {}));
+        }
+        self.config_changed(config::NONCE_WINDOW, self.nonce_window.get(), window);
+        self.nonce_window.set(window);
+        Ok(())
+    }
+
+    /// View: Get `nonce_window`
+    pub fn get_nonce_window(&self) -> Result<U256, VerifierError> {
+        Ok(self.nonce_window.get())
+    }
+
+    /// View: Lowest attestation nonce a device has not consumed yet
+    pub fn get_attestation_nonce(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.attestation_nonces.get(device_id))
+    }
+
+    /// View: Get `(tracked, counter)`: whether the device has reported a
+    /// TEE hardware counter, and the highest one accepted
+    pub fn get_hw_counter(&self, device_id: FixedBytes<32>) -> Result<(bool, U256), VerifierError> {
+        Ok((
+            self.get_flag(device_id, FLAG_HAS_HW_COUNTER),
+            self.device_hw_counters.get(device_id),
+        ))
+    }
+
+    /// Mint a device's pending rewards to its owner (device owner only).
+    ///
+    /// Reverts with `RewardMintFailed` if the token still refuses, leaving
+    /// the pending balance intact for another attempt.
+    pub fn claim_reward(&mut self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        self.only_device_owner(device_id)?;
+        let owner = self.device_owners.get(device_id);
+        self.pay_pending_reward(device_id, owner)
+    }
+
+    /// Mint a device's pending rewards to `to` on the owner's behalf, so a
+    /// service can claim gaslessly. Callable by anyone holding the owner's
+    /// signature over `claim_digest(device_id, to, nonce)`; each
+    /// authorization is single-use.
+    pub fn claim_reward_with_sig(
+        &mut self,
+        device_id: FixedBytes<32>,
+        to: Address,
+        signature: Vec<u8>,
+    ) -> Result<U256, VerifierError> {
+        let owner = self.registered_owner(device_id)?;
+        let nonce = self.claim_nonces.get(device_id);
+        let digest = self.claim_digest(device_id, to, nonce);
+        if signature::recover_signer(self.vm(), digest, &signature) != Some(owner) {
+            return Err(VerifierError::BadSignature(BadSignature {}));
+        }
+
+        self.claim_nonces
+            .setter(device_id)
+            .set(nonce + U256::from(1));
+        self.pay_pending_reward(device_id, to)
+    }
+
+    /// Pure: Decode a CBOR attestation payload into
+    /// `(challenge, timestamp, claims)`. See `cbor.rs` for the accepted subset.
+    pub fn parse_attestation(
+        &self,
+        data: Vec<u8>,
+    ) -> Result<(FixedBytes<32>, u64, U256), VerifierError> {
+        let attestation = self.decode_attestation(&data)?;
+        Ok((
+            attestation.challenge,
+            attestation.timestamp,
+            attestation.claims,
+        ))
+    }
+
+    /// View: Get the trust score for a device, or its staged score once
+    /// that is effective
+    pub fn get_device_trust(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.effective_score(device_id))
+    }
+
+    /// View: Whether a device has been scored since registration
+    pub fn has_score(&self, device_id: FixedBytes<32>) -> Result<bool, VerifierError> {
+        Ok(self.get_flag(device_id, FLAG_HAS_SCORE))
+    }
+
+    /// View: Coarse `TIER_*` badge for a device's effective score, which
+    /// is 0 once the score has outlived the policy's grace
+    pub fn get_trust_tier(&self, device_id: FixedBytes<32>) -> Result<u8, VerifierError> {
+        let now = U256::from(self.vm().block_timestamp());
+        if !self.is_score_fresh(device_id, now) {
+            return Ok(TIER_NONE);
+        }
+        let score = self.effective_score(device_id);
+        let (silver_min, gold_min) = self.tier_boundaries();
+        Ok(if score >= gold_min {
+            TIER_GOLD
+        } else if score >= silver_min {
+            TIER_SILVER
+        } else if !score.is_zero() {
+            TIER_BRONZE
+        } else {
+            TIER_NONE
+        })
+    }
+
+    /// View: Get the owner address of a device
+    pub fn get_device_owner(&self, device_id: FixedBytes<32>) -> Result<Address, VerifierError> {
+        Ok(self.device_owners.get(device_id))
+    }
+
+    /// View: Get a device's secp256k1 attestation key address (zero if
+    /// unset or the device uses another key scheme)
+    pub fn get_device_attestation_key(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<Address, VerifierError> {
+        Ok(self.device_attestation_keys.get(device_id))
+    }
+
+    /// View: Last applied bridge message sequence number for a device
+    pub fn get_bridge_seq(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.bridge_seqs.get(device_id))
+    }
+
+    /// View: Rewards owed to a device whose mint failed
+    pub fn get_pending_reward(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.pending_rewards.get(device_id))
+    }
+
+    /// View: Whether the device id is permanently retired
+    pub fn is_retired(&self, device_id: FixedBytes<32>) -> Result<bool, VerifierError> {
+        Ok(self.get_flag(device_id, FLAG_RETIRED))
+    }
+
+    /// View: Whether the device has been revoked
+    pub fn is_revoked(&self, device_id: FixedBytes<32>) -> Result<bool, VerifierError> {
+        Ok(self.get_flag(device_id, FLAG_REVOKED))
+    }
+
+    /// View: Get the number of revoked device ids, including deregistered
+    /// ones, since revocation survives deregistration
+    pub fn get_revoked_count(&self) -> Result<U256, VerifierError> {
+        Ok(self.revoked_count.get())
+    }
+
+    /// View: Get a device's `OVERRIDE_*` verification mode
+    pub fn get_verification_override(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<u8, VerifierError> {
+        Ok(Self::override_mode(self.device_flags.get(device_id)))
+    }
+
+    /// View: Get a device's class (0 = global policy)
+    pub fn get_device_class(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.device_class.get(device_id))
+    }
+
+    /// View: Get the metadata blob attached to a device (empty if none)
+    pub fn get_device_metadata(&self, device_id: FixedBytes<32>) -> Result<Vec<u8>, VerifierError> {
+        Ok(self.device_metadata.get(device_id).get_bytes())
+    }
+
+    /// View: Whether a device carries `tag`
+    pub fn has_tag(
+        &self,
+        device_id: FixedBytes<32>,
+        tag: FixedBytes<32>,
+    ) -> Result<bool, VerifierError> {
+        Ok(self.device_tags.getter(device_id).get(tag))
+    }
+
+    /// View: Successful verifications of a device since registration
+    pub fn get_device_verification_count(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<U256, VerifierError> {
+        Ok(self.device_verification_counts.get(device_id))
+    }
+
+    /// View: Everything about a device in one call, as a `DeviceAudit`:
+    /// `(owner, score, attestation_nonce, last_verified_at,
+    /// verification_count, flags, last_attestation_hash)`. The score is
+    /// `get_device_trust`'s, and `flags` packs the `AUDIT_*` bits.
+    pub fn get_device_audit(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<DeviceAudit, VerifierError> {
+        let flags = self.device_flags.get(device_id);
+        let mut audit_flags = 0;
+        if flags.bit(FLAG_REVOKED) {
+            audit_flags |= AUDIT_REVOKED;
+        }
+        if flags.bit(FLAG_RETIRED) {
+            audit_flags |= AUDIT_RETIRED;
+        }
+        Ok((
+            self.device_owners.get(device_id),
+            self.effective_score(device_id),
+            self.attestation_nonces.get(device_id),
+            self.last_verified_at.get(device_id),
+            self.device_verification_counts.get(device_id),
+            audit_flags,
+            self.last_attestation_hashes.get(device_id),
+        ))
+    }
+
+    /// Emit `MilestoneReached` each time a device's verification count
+    /// reaches a multiple of `interval` (owner-only, 0 = off).
+    pub fn set_milestone_interval(&mut self, interval: U256) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.config_changed(
+            config::MILESTONE_INTERVAL,
+            self.milestone_interval.get(),
+            interval,
+        );
+        self.milestone_interval.set(interval);
+        Ok(())
+    }
+
+    /// View: Get `milestone_interval` (0 = off)
+    pub fn get_milestone_interval(&self) -> Result<U256, VerifierError> {
+        Ok(self.milestone_interval.get())
+    }
+
+    /// View: Consecutive failed verifications of a device
+    pub fn get_failure_count(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.failure_count.get(device_id))
+    }
+
+    /// View: Nonce the next `register_device_authorized` signature must cover
+    pub fn get_registration_nonce(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.registration_nonces.get(device_id))
+    }
+
+    /// View: Nonce the next `claim_reward_with_sig` signature must cover
+    pub fn get_claim_nonce(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.claim_nonces.get(device_id))
+    }
+
+    /// View: Nonce the next `restore_score` signature must cover
+    pub fn get_restore_nonce(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        Ok(self.restore_nonces.get(device_id))
+    }
+
+    /// View: Whether a device is registered, answered from its packed flags
+    pub fn is_registered(&self, device_id: FixedBytes<32>) -> Result<bool, VerifierError> {
+        Ok(self.is_device_registered(device_id))
+    }
+
+    /// View: Get a single sortable ranking value for a device.
+    ///
+    /// Packs `(score << 160) | (2^160 - 1 - registration_index)`. Sorting
+    /// keys in descending order ranks higher scores first and, among equal
+    /// scores, earlier registrations first. Unregistered devices return 0.
+    pub fn get_rank_key(&self, device_id: FixedBytes<32>) -> Result<U256, VerifierError> {
+        let index = self.registration_index.get(device_id);
+        if index.is_zero() {
+            return Ok(U256::ZERO);
+        }
+        let index_mask = (U256::from(1) << 160) - U256::from(1);
+        let score = self.effective_score(device_id);
+        Ok((score << 160) | (index_mask - (index & index_mask)))
+    }
+
+    /// View: Number of entries currently retained in the verification log
+    pub fn get_verification_log_count(&self) -> Result<U256, VerifierError> {
+        Ok(self
+            .verification_log_total
+            .get()
+            .min(self.verification_log_limit()))
+    }
+
+    /// View: Successful verifications in roughly the last `window_seconds`,
+    /// summed over whole hourly buckets (so the current partial hour always
+    /// counts), up to the last `THROUGHPUT_BUCKETS` hours
+    pub fn verifications_in_window(&self, window_seconds: U256) -> Result<U256, VerifierError> {
+        let bucket = U256::from(THROUGHPUT_BUCKET_SECONDS);
+        let now = U256::from(self.vm().block_timestamp());
+        let current = now / bucket;
+        let oldest = now.saturating_sub(window_seconds) / bucket;
+        let span = (current - oldest).min(U256::from(THROUGHPUT_BUCKETS - 1));
+
+        let mut total = U256::ZERO;
+        for offset in 0..=span.to::<u64>() {
+            let hour = current - U256::from(offset);
+            let slot = hour % U256::from(THROUGHPUT_BUCKETS);
+            if self.throughput_hours.get(slot) == hour {
+                total += self.throughput_counts.get(slot);
+            }
+        }
+        Ok(total)
+    }
+
+    /// View: Get `(device_id, timestamp)` of a retained verification log
+    /// entry; index 0 is the oldest
+    pub fn get_verification_log_at(
+        &self,
+        index: U256,
+    ) -> Result<(FixedBytes<32>, U256), VerifierError> {
+        let total = self.verification_log_total.get();
+        let capacity = self.verification_log_limit();
+        let count = total.min(capacity);
+        if index >= count {
+            return Err(VerifierError::IndexOutOfBounds(IndexOutOfBounds { index }));
+        }
+        let slot = (total - count + index) % capacity;
+        Ok((
+            self.verification_log_devices.get(slot),
+            self.verification_log_times.get(slot),
+        ))
+    }
+
+    /// View: Number of currently registered devices
+    pub fn get_device_count(&self) -> Result<U256, VerifierError> {
+        Ok(U256::from(self.device_list.len()))
+    }
+
+    /// View: Get the registered device at `index` in the enumeration.
+    /// Order is not stable across deregistrations.
+    pub fn get_device_at(&self, index: U256) -> Result<FixedBytes<32>, VerifierError> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| self.device_list.get(i))
+            .ok_or(VerifierError::IndexOutOfBounds(IndexOutOfBounds { index }))
+    }
+
+    /// View: Page through registered devices; returns `(devices,
+    /// next_index)` as described in `page.rs`
+    pub fn get_devices(
+        &self,
+        start: U256,
+        limit: U256,
+    ) -> Result<(Vec<FixedBytes<32>>, U256), VerifierError> {
+        let cursor = page::PageCursor::new(start, limit, self.device_list.len());
+        let devices = cursor
+            .indices()
+            .filter_map(|i| self.device_list.get(i))
+            .collect();
+        Ok((devices, cursor.next_index()))
+    }
+
+    /// View: Owners, scores and revoked flags of up to `MAX_PAGE_LIMIT`
+    /// devices, as parallel vectors aligned with `device_ids`. Absent
+    /// devices show the zero owner.
+    pub fn get_devices_batch(
+        &self,
+        device_ids: Vec<FixedBytes<32>>,
+    ) -> Result<DeviceRows, VerifierError> {
+        if device_ids.len() > page::MAX_PAGE_LIMIT {
+            return Err(VerifierError::BatchTooLarge(BatchTooLarge {
+                length: U256::from(device_ids.len()),
+            }));
+        }
+        let owners = device_ids
+            .iter()
+            .map(|id| self.device_owners.get(*id))
+            .collect();
+        let scores = device_ids
+            .iter()
+            .map(|id| self.effective_score(*id))
+            .collect();
+        let revoked = device_ids
+            .iter()
+            .map(|id| self.get_flag(*id, FLAG_REVOKED))
+            .collect();
+        Ok((owners, scores, revoked))
+    }
+
+    /// View: Next attestation nonce (`get_attestation_nonce`) of up to
+    /// `MAX_PAGE_LIMIT` devices, in input order; unregistered devices
+    /// show 0.
+    pub fn get_device_nonces(
+        &self,
+        device_ids: Vec<FixedBytes<32>>,
+    ) -> Result<Vec<U256>, VerifierError> {
+        if device_ids.len() > page::MAX_PAGE_LIMIT {
+            return Err(VerifierError::BatchTooLarge(BatchTooLarge {
+                length: U256::from(device_ids.len()),
+            }));
+        }
+        Ok(device_ids
+            .iter()
+            .map(|id| {
+                if self.is_device_registered(*id) {
+                    self.attestation_nonces.get(*id)
+                } else {
+                    U256::ZERO
+                }
+            })
+            .collect())
+    }
+
+    /// View: Number of devices registered to `owner`
+    pub fn get_owner_device_count(&self, owner: Address) -> Result<U256, VerifierError> {
+        Ok(U256::from(self.owner_devices.get(owner).len()))
+    }
+
+    /// View: Page through the devices registered to `owner`; returns
+    /// `(devices, next_index)` as described in `page.rs`
+    pub fn get_owner_devices(
+        &self,
+        owner: Address,
+        start: U256,
+        limit: U256,
+    ) -> Result<(Vec<FixedBytes<32>>, U256), VerifierError> {
+        let owned = self.owner_devices.get(owner);
+        let cursor = page::PageCursor::new(start, limit, owned.len());
+        let devices = cursor.indices().filter_map(|i| owned.get(i)).collect();
+        Ok((devices, cursor.next_index()))
+    }
+
+    /// View: Page through registered devices and return `(problems,
+    /// next_index)`, where problems have a score below `min_trust` or were
+    /// last updated more than `max_age` seconds ago (0 = ignore age).
+    /// See `page.rs` for the paging contract.
+    pub fn scan_problem_devices(
+        &self,
+        start: U256,
+        limit: U256,
+        min_trust: U256,
+        max_age: U256,
+    ) -> Result<(Vec<FixedBytes<32>>, U256), VerifierError> {
+        let now = U256::from(self.vm().block_timestamp());
+        let cursor = page::PageCursor::new(start, limit, self.device_list.len());
+
+        let mut problems = Vec::new();
+        for device_id in cursor.indices().filter_map(|i| self.device_list.get(i)) {
+            let low_trust = self.effective_score(device_id) < min_trust;
+            let stale = !max_age.is_zero()
+                && now > self.score_updated_at.get(device_id).saturating_add(max_age);
+            if low_trust || stale {
+                problems.push(device_id);
+            }
+        }
+        Ok((problems, cursor.next_index()))
+    }
+
+    /// View: Get `(device_count, trust_score_sum, average_trust,
+    /// total_verifications)` over registered devices; the average rounds
+    /// down and is 0 with no devices
+    pub fn get_aggregate_stats(&self) -> Result<(U256, U256, U256, U256), VerifierError> {
+        let count = U256::from(self.device_list.len());
+        let sum = self.trust_score_sum.get();
+        let average = sum.checked_div(count).unwrap_or_default();
+        Ok((count, sum, average, self.total_verifications.get()))
+    }
+
+    /// View: Registered-device counts per score bucket, as
+    /// `SCORE_HISTOGRAM_BUCKETS` entries (0–9, 10–19, …, 90–99, 100)
+    pub fn get_score_histogram(&self) -> Result<Vec<U256>, VerifierError> {
+        Ok((0..SCORE_HISTOGRAM_BUCKETS)
+            .map(|bucket| self.score_histogram.get(U256::from(bucket)))
+            .collect())
+    }
+
+    /// View: Lowest and highest trust score among registered devices, or
+    /// (0, 0) with none registered
+    pub fn get_score_range(&self) -> Result<(U256, U256), VerifierError> {
+        Ok((self.min_score.get(), self.max_score.get()))
+    }
+
+    /// Assign a registered device to a manufacturer (owner-only), e.g. the
+    /// hash of its root key; zero unassigns it. Only assigned devices
+    /// count towards `get_fleet_trust`.
+    pub fn set_device_manufacturer(
+        &mut self,
+        device_id: FixedBytes<32>,
+        manufacturer: FixedBytes<32>,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        self.registered_owner(device_id)?;
+        let score = self.device_trust_scores.get(device_id);
+        self.shift_fleet_trust(device_id, score, false);
+        self.device_manufacturers
+            .setter(device_id)
+            .set(manufacturer);
+        self.shift_fleet_trust(device_id, score, true);
+        if !manufacturer.is_zero() && !self.manufacturer_listed.get(manufacturer) {
+            self.manufacturer_listed.setter(manufacturer).set(true);
+            self.manufacturer_list.push(manufacturer);
+        }
+        Ok(())
+    }
+
+    /// View: Get a device's manufacturer id (zero = unassigned)
+    pub fn get_device_manufacturer(
+        &self,
+        device_id: FixedBytes<32>,
+    ) -> Result<FixedBytes<32>, VerifierError> {
+        Ok(self.device_manufacturers.get(device_id))
+    }
+
+    /// Set how much a manufacturer's devices weigh in `get_fleet_trust`
+    /// (owner-only; 0 leaves them out).
+    pub fn set_manufacturer_weight(
+        &mut self,
+        manufacturer: FixedBytes<32>,
+        weight: U256,
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        let previous = self.manufacturer_weights.get(manufacturer);
+        let sum = self.manufacturer_score_sums.get(manufacturer);
+        let count = self.manufacturer_device_counts.get(manufacturer);
+        self.weighted_score_sum
+            .set(self.weighted_score_sum.get() - previous * sum + weight * sum);
+        self.total_trust_weight
+            .set(self.total_trust_weight.get() - previous * count + weight * count);
+        self.manufacturer_weights.setter(manufacturer).set(weight);
+        Ok(())
+    }
+
+    /// View: Get a manufacturer's `get_fleet_trust` weight
+    pub fn get_manufacturer_weight(
+        &self,
+        manufacturer: FixedBytes<32>,
+    ) -> Result<U256, VerifierError> {
+        Ok(self.manufacturer_weights.get(manufacturer))
+    }
+
+    /// View: Average score of the registered devices, each weighted by
+    /// its manufacturer's weight and rounded down (0 when the weights
+    /// total 0)
+    pub fn get_fleet_trust(&self) -> Result<U256, VerifierError> {
+        Ok(self
+            .weighted_score_sum
+            .get()
+            .checked_div(self.total_trust_weight.get())
+            .unwrap_or_default())
+    }
+
+    /// Set every device in a page of the device list to `score`, clamped
+    /// to `MAX_TRUST_SCORE` (owner-only), e.g. to reset a test fleet, and
+    /// return the next index. See `page.rs` for the paging contract.
+    pub fn set_all_scores(
+        &mut self,
+        start: U256,
+        limit: U256,
+        score: U256,
+    ) -> Result<U256, VerifierError> {
+        self.only_owner()?;
+        self.when_not_decommissioned()?;
+        let score = score.min(U256::from(MAX_TRUST_SCORE));
+        let cursor = page::PageCursor::new(start, limit, self.device_list.len());
+        for i in cursor.indices() {
+            if let Some(device_id) = self.device_list.get(i) {
+                self.apply_score(device_id, score);
+            }
+        }
+        Ok(cursor.next_index())
+    }
+
+    /// Recompute `trust_score_sum`, the score histogram and the
+    /// `get_fleet_trust` totals from the device list (owner-only), one page
+    /// per call, and return the next index.
+    /// A call at `start` = 0 begins a new rebuild; later calls must pass
+    /// the returned index. The call whose page reaches the end commits the
+    /// recomputed totals and returns 0; until then the live aggregates are
+    /// untouched. Run it while paused, since registrations between pages
+    /// would be missed or counted twice. See `page.rs` for `limit`.
+    pub fn rebuild_aggregates(&mut self, start: U256, limit: U256) -> Result<U256, VerifierError> {
+        self.only_owner()?;
+        if start.is_zero() {
+            self.rebuild_score_sum.set(U256::ZERO);
+            self.rebuild_weighted_sum.set(U256::ZERO);
+            self.rebuild_total_weight.set(U256::ZERO);
+            self.rebuild_round
+                .set(self.rebuild_round.get() + U256::from(1));
+            for bucket in 0..SCORE_HISTOGRAM_BUCKETS {
+                self.rebuild_histogram.delete(U256::from(bucket));
+            }
+            for score in 0..=MAX_TRUST_SCORE {
+                self.rebuild_score_counts.delete(U256::from(score));
+            }
+        } else if start != self.rebuild_next.get() {
+            return Err(VerifierError::IndexOutOfBounds(IndexOutOfBounds {
+                index: start,
+            }));
+        }
+
+        let cursor = page::PageCursor::new(start, limit, self.device_list.len());
+        let mut sum = self.rebuild_score_sum.get();
+        let mut weighted = self.rebuild_weighted_sum.get();
+        let mut total_weight = self.rebuild_total_weight.get();
+        let round = self.rebuild_round.get();
+        for device_id in cursor.indices().filter_map(|i| self.device_list.get(i)) {
+            let score = self.device_trust_scores.get(device_id);
+            sum += score;
+            let manufacturer = self.device_manufacturers.get(device_id);
+            if !manufacturer.is_zero() {
+                let weight = self.manufacturer_weights.get(manufacturer);
+                weighted += weight * score;
+                total_weight += weight;
+                let (manufacturer_sum, count) = self.rebuild_manufacturer_totals(manufacturer);
+                self.rebuild_manufacturer_rounds
+                    .setter(manufacturer)
+                    .set(round);
+                self.rebuild_manufacturer_sums
+                    .setter(manufacturer)
+                    .set(manufacturer_sum + score);
+                self.rebuild_manufacturer_counts
+                    .setter(manufacturer)
+                    .set(count + U256::from(1));
+            }
+            let bucket = score / U256::from(10);
+            let count = self.rebuild_histogram.get(bucket);
+            self.rebuild_histogram
+                .setter(bucket)
+                .set(count + U256::from(1));
+            let count = self.rebuild_score_counts.get(score);
+            self.rebuild_score_counts
+                .setter(score)
+                .set(count + U256::from(1));
+        }
+        self.rebuild_score_sum.set(sum);
+        self.rebuild_weighted_sum.set(weighted);
+        self.rebuild_total_weight.set(total_weight);
+
+        let next = cursor.next_index();
+        self.rebuild_next.set(next);
+        if next.is_zero() {
+            self.trust_score_sum.set(sum);
+            self.weighted_score_sum.set(weighted);
+            self.total_trust_weight.set(total_weight);
+            for i in 0..self.manufacturer_list.len() {
+                let manufacturer = self.manufacturer_list.get(i).unwrap_or_default();
+                let (manufacturer_sum, count) = self.rebuild_manufacturer_totals(manufacturer);
+                self.manufacturer_score_sums
+                    .setter(manufacturer)
+                    .set(manufacturer_sum);
+                self.manufacturer_device_counts
+                    .setter(manufacturer)
+                    .set(count);
+            }
+            for bucket in (0..SCORE_HISTOGRAM_BUCKETS).map(U256::from) {
+                let count = self.rebuild_histogram.get(bucket);
+                self.score_histogram.setter(bucket).set(count);
+            }
+            let (mut min, mut max) = (None, U256::ZERO);
+            for score in (0..=MAX_TRUST_SCORE).map(U256::from) {
+                let count = self.rebuild_score_counts.get(score);
+                self.score_counts.setter(score).set(count);
+                if !count.is_zero() {
+                    min = min.or(Some(score));
+                    max = score;
+                }
+            }
+            self.min_score.set(min.unwrap_or_default());
+            self.max_score.set(max);
+        }
+        Ok(next)
+    }
+
+    /// Record the current aggregates (owner-only), e.g. before a pause, so
+    /// `verify_stats_unchanged` can later confirm nothing moved.
+    pub fn snapshot_stats(&mut self) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        let digest = self.stats_digest();
+        self.stats_snapshot.set(digest);
+        Ok(())
+    }
+
+    /// View: Whether the aggregates still match the last `snapshot_stats`
+    /// (false if none was taken)
+    pub fn verify_stats_unchanged(&self) -> Result<bool, VerifierError> {
+        let snapshot = self.stats_snapshot.get();
+        Ok(!snapshot.is_zero() && snapshot == self.stats_digest())
+    }
+
+    /// Start a new pilot phase's statistics (owner-only): zeroes the
+    /// verification total, this epoch's score-decrease count and the
+    /// throughput buckets, and emits `StatisticsReset` with the old total.
+    /// Registrations, scores and per-device history are untouched.
+    pub fn reset_statistics(&mut self) -> Result<(), VerifierError> {
+        self.only_owner()?;
+        let total_verifications = self.total_verifications.get();
+        self.total_verifications.set(U256::ZERO);
+        self.epoch_verification_snapshots
+            .setter(self.verification_epoch.get())
+            .set(U256::ZERO);
+        self.score_decrease_count.set(U256::ZERO);
+        for slot in 0..THROUGHPUT_BUCKETS {
+            self.throughput_hours.delete(U256::from(slot));
+            self.throughput_counts.delete(U256::from(slot));
+        }
+        log(
+            self.vm(),
+            StatisticsReset {
+                total_verifications,
+            },
+        );
+        Ok(())
+    }
+
+    /// View: Get total successful attestation verifications
+    pub fn get_total_verifications(&self) -> Result<U256, VerifierError> {
+        Ok(self.total_verifications.get())
+    }
+
+    /// View: Block timestamp at which the contract was initialized (zero
+    /// before `initialize`)
+    pub fn get_deployed_at(&self) -> Result<U256, VerifierError> {
+        Ok(self.deployed_at.get())
+    }
+
+    /// View: Seconds elapsed since initialization (zero before `initialize`)
+    pub fn uptime_seconds(&self) -> Result<U256, VerifierError> {
+        if self.owner.get().is_zero() && !self.renounced.get() {
+            return Ok(U256::ZERO);
+        }
+        let now = U256::from(self.vm().block_timestamp());
+        Ok(now.saturating_sub(self.deployed_at.get()))
+    }
+
+    /// View: 4-byte selectors of every public method, so clients can check
+    /// they are talking to this contract without an ABI file
+    pub fn function_selectors(&self) -> Result<Vec<FixedBytes<4>>, VerifierError> {
+        Ok(selectors::FUNCTION_SELECTORS
+            .iter()
+            .map(|selector| FixedBytes::from(*selector))
+            .collect())
+    }
+
+    /// View: EIP-712 domain separator over `EIP712_NAME`, `EIP712_VERSION`,
+    /// the chain id and this contract's address, for clients building
+    /// typed-data signatures
+    pub fn domain_separator(&self) -> Result<FixedBytes<32>, VerifierError> {
+        Ok(self.eip712_domain_separator())
+    }
+
+    /// Pure: Stable numeric code of the custom error with 4-byte
+    /// `selector` (0 = not a verifier error), for clients that cannot
+    /// decode custom errors. See `VerifierError::code`.
+    pub fn error_code_of(&self, selector: FixedBytes<4>) -> Result<u32, VerifierError> {
+        Ok(errors::error_code_of(selector.0))
+    }
+}
+
+impl CertIDVerifier {
+    fn only_owner(&self) -> Result<(), VerifierError> {
+        let caller = self.vm().msg_sender();
+        let owner = self.owner.get();
+        if owner.is_zero() || caller != owner {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        Ok(())
+    }
+
+    fn decode_attestation<'a>(
+        &self,
+        data: &'a [u8],
+    ) -> Result<cbor::Attestation<'a>, VerifierError> {
+        cbor::decode_attestation(data)
+            .ok_or(VerifierError::MalformedAttestation(MalformedAttestation {}))
+    }
+
+    /// Require a registered device and return its owner.
+    fn registered_owner(&self, device_id: FixedBytes<32>) -> Result<Address, VerifierError> {
+        if !self.is_device_registered(device_id) {
+            return Err(VerifierError::DeviceNotRegistered(DeviceNotRegistered {
+                device_id,
+            }));
+        }
+        Ok(self.device_owners.get(device_id))
+    }
+
+    fn only_device_owner(&self, device_id: FixedBytes<32>) -> Result<(), VerifierError> {
+        let owner = self.registered_owner(device_id)?;
+        let caller = self.vm().msg_sender();
+        if caller != owner {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        Ok(())
+    }
+
+    fn only_device_owner_or_relayer(&self, device_id: FixedBytes<32>) -> Result<(), VerifierError> {
+        let owner = self.registered_owner(device_id)?;
+        let caller = self.vm().msg_sender();
+        if caller != owner && !self.relayers.get(caller) {
+            return Err(VerifierError::Unauthorized(Unauthorized { caller }));
+        }
+        Ok(())
+    }
+
+    /// Add or remove one device at `score` in the score histogram and the
+    /// score range.
+    fn shift_histogram(&mut self, score: U256, add: bool) {
+        let bucket = score / U256::from(10);
+        let count = self.score_histogram.get(bucket);
+        let count = if add {
+            count + U256::from(1)
+        } else {
+            count - U256::from(1)
+        };
+        self.score_histogram.setter(bucket).set(count);
+        self.shift_score_range(score, add);
+    }
+
+    /// A manufacturer's `(score sum, device count)` so far in the current
+    /// `rebuild_aggregates`.
+    fn rebuild_manufacturer_totals(&self, manufacturer: FixedBytes<32>) -> (U256, U256) {
+        if self.rebuild_manufacturer_rounds.get(manufacturer) != self.rebuild_round.get() {
+            return (U256::ZERO, U256::ZERO);
+        }
+        (
+            self.rebuild_manufacturer_sums.get(manufacturer),
+            self.rebuild_manufacturer_counts.get(manufacturer),
+        )
+    }
+
+    /// Add or remove one device at `score` in its manufacturer's totals
+    /// and the weighted `get_fleet_trust` sums.
+    fn shift_fleet_trust(&mut self, device_id: FixedBytes<32>, score: U256, add: bool) {
+        let manufacturer = self.device_manufacturers.get(device_id);
+        if manufacturer.is_zero() {
+            return;
+        }
+        let weight = self.manufacturer_weights.get(manufacturer);
+        let sum = self.manufacturer_score_sums.get(manufacturer);
+        let count = self.manufacturer_device_counts.get(manufacturer);
+        let (weighted, total) = (self.weighted_score_sum.get(), self.total_trust_weight.get());
+        if add {
+            self.manufacturer_score_sums
+                .setter(manufacturer)
+                .set(sum + score);
+            self.manufacturer_device_counts
+                .setter(manufacturer)
+                .set(count + U256::from(1));
+            self.weighted_score_sum.set(weighted + weight * score);
+            self.total_trust_weight.set(total + weight);
+        } else {
+            self.manufacturer_score_sums
+                .setter(manufacturer)
+                .set(sum - score);
+            self.manufacturer_device_counts
+                .setter(manufacturer)
+                .set(count - U256::from(1));
+            self.weighted_score_sum.set(weighted - weight * score);
+            self.total_trust_weight.set(total - weight);
+        }
+    }
+
+    /// Keep `min_score`/`max_score` in step with `score_counts`.
+    ///
+    /// Only removing the last device at an extreme rescans, walking
+    /// `score_counts` from the old extreme toward the other one. Scores
+    /// are at most `MAX_TRUST_SCORE`, so a rescan reads at most 101 slots
+    /// whatever the fleet size.
+    fn shift_score_range(&mut self, score: U256, add: bool) {
+        let (min, max) = (self.min_score.get(), self.max_score.get());
+        let count = self.score_counts.get(score);
+        if add {
+            // The extremes always hold a device unless the fleet is empty
+            let empty = self.score_counts.get(min).is_zero();
+            self.score_counts.setter(score).set(count + U256::from(1));
+            if empty || score < min {
+                self.min_score.set(score);
+            }
+            if empty || score > max {
+                self.max_score.set(score);
+            }
+            return;
+        }
+
+        self.score_counts.setter(score).set(count - U256::from(1));
+        if count > U256::from(1) || (score != min && score != max) {
+            return;
+        }
+        let mut held = (min.to::<u64>()..=max.to::<u64>())
+            .map(U256::from)
+            .filter(|&s| s != score && !self.score_counts.get(s).is_zero());
+        if score == min {
+            let next = held.next();
+            self.min_score.set(next.unwrap_or_default());
+            if next.is_none() {
+                self.max_score.set(U256::ZERO);
+            }
+        } else {
+            let next = held.next_back();
+            self.max_score.set(next.unwrap_or_default());
+        }
+    }
+
+    /// Count a score decrease against the current epoch, and pause once
+    /// the count passes `score_decrease_alarm`.
+    fn note_score_decrease(&mut self) {
+        let decreases = self.score_decreases_this_epoch().unwrap_or_default() + U256::from(1);
+        let epoch = self.current_epoch.get();
+        self.score_decrease_epoch.set(epoch);
+        self.score_decrease_count.set(decreases);
+
+        let alarm = self.score_decrease_alarm.get();
+        if !alarm.is_zero() && decreases > alarm && !self.paused.get() {
+            self.paused.set(true);
+            log(self.vm(), AnomalyDetected { epoch, decreases });
+        }
+    }
+
+    /// Write a device's staged score through once it is effective, and
+    /// return whether there was one to write.
+    fn settle_staged_score(&mut self, device_id: FixedBytes<32>) -> bool {
+        let effective_at = self.staged_score_effective_at.get(device_id);
+        let now = U256::from(self.vm().block_timestamp());
+        if effective_at.is_zero() || now < effective_at {
+            return false;
+        }
+        let score = self.staged_scores.get(device_id);
+        self.staged_scores.delete(device_id);
+        self.staged_score_effective_at.delete(device_id);
+        self.apply_score(device_id, score);
+        true
+    }
+
+    /// `settle_staged_score` for a verification holding the device's
+    /// `flags` word in memory, returning the word as it now is in storage.
+    fn settle_staged_score_flags(&mut self, device_id: FixedBytes<32>, mut flags: U256) -> U256 {
+        if self.settle_staged_score(device_id) {
+            flags.set_bit(FLAG_HAS_SCORE, true);
+        }
+        flags
+    }
+
+    /// A device's stored score, or its staged score once effective.
+    fn effective_score(&self, device_id: FixedBytes<32>) -> U256 {
+        let effective_at = self.staged_score_effective_at.get(device_id);
+        if !effective_at.is_zero() && U256::from(self.vm().block_timestamp()) >= effective_at {
+            return self.staged_scores.get(device_id);
+        }
+        self.device_trust_scores.get(device_id)
+    }
+
+    /// Move a registered device's score toward the verification outcome
+    /// when the moving average is on. Rounding is toward the outcome, so a
+    /// run of passes or failures reaches 100 or 0 rather than stalling a
+    /// point short.
+    fn apply_verification_outcome(&mut self, device_id: FixedBytes<32>, score: U256, passed: bool) {
+        let alpha = self.ewma_alpha.get();
+        if alpha.is_zero() || !self.is_device_registered(device_id) {
+            return;
+        }
+        // Both terms are at most EWMA_SCALE * MAX_TRUST_SCORE
+        let scale = U256::from(EWMA_SCALE);
+        let score = score.min(U256::from(MAX_TRUST_SCORE));
+        let mut weighted = (scale - alpha) * score;
+        if passed {
+            weighted += alpha * U256::from(MAX_TRUST_SCORE) + scale - U256::from(1);
+        }
+        self.write_score(device_id, weighted / scale, false);
+    }
+
+    /// Write a device's trust score and stamp the update time.
+    fn apply_score(&mut self, device_id: FixedBytes<32>, score: U256) {
+        self.write_score(device_id, score, true);
+    }
+
+    /// `apply_score`, counting a decrease towards `score_decrease_alarm`
+    /// only with `alarm`. Verification outcomes pass false, since any
+    /// caller can fail verifications.
+    fn write_score(&mut self, device_id: FixedBytes<32>, score: U256, alarm: bool) {
+        let now = U256::from(self.vm().block_timestamp());
+        if self.is_device_registered(device_id) {
+            let previous = self.device_trust_scores.get(device_id);
+            self.trust_score_sum
+                .set(self.trust_score_sum.get() - previous + score);
+            self.shift_histogram(previous, false);
+            self.shift_histogram(score, true);
+            self.shift_fleet_trust(device_id, previous, false);
+            self.shift_fleet_trust(device_id, score, true);
+            if alarm && score < previous {
+                self.note_score_decrease();
+            }
+        }
+        self.device_trust_scores.setter(device_id).set(score);
+        self.clear_memo(device_id);
+        self.set_flag(device_id, FLAG_HAS_SCORE, true);
+        self.score_updated_at.setter(device_id).set(now);
+    }
+
+    /// Take the configured registration deposit from the caller and record
+    /// it against the device.
+    fn collect_deposit(&mut self, device_id: FixedBytes<32>) -> Result<(), VerifierError> {
+        let token = self.deposit_token.get();
+        let amount = self.registration_deposit.get();
+        let expected_value = if token.is_zero() { amount } else { U256::ZERO };
+        let value = self.vm().msg_value();
+        if value != expected_value {
+            return Err(VerifierError::IncorrectDeposit(IncorrectDeposit { value }));
+        }
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        self.device_deposits.setter(device_id).set(amount);
+        self.device_deposit_tokens.setter(device_id).set(token);
+        if !token.is_zero() {
+            let calldata = interfaces::IERC20::transferFromCall {
+                from: self.vm().msg_sender(),
+                to: self.vm().contract_address(),
+                amount,
+            }
+            .abi_encode();
+            self.call_erc20(token, &calldata)?;
+        }
+        Ok(())
+    }
+
+    /// Check `msg_value` covers the fee for `attestation_type` plus the
+    /// device's own fee, and add all but the device fee to the fee pool;
+    /// any overpayment is pooled too. Returns the device fee, which the
+    /// caller settles with `settle_device_fee`.
+    fn collect_verification_fee(
+        &mut self,
+        device_id: FixedBytes<32>,
+        attestation_type: u64,
+    ) -> Result<U256, VerifierError> {
+        let device_fee = self.verification_fee.get(device_id);
+        let required = self
+            .fee_by_type
+            .get(U256::from(attestation_type))
+            .saturating_add(device_fee);
+        let value = self.vm().msg_value();
+        if value < required {
+            return Err(VerifierError::InsufficientFee(InsufficientFee { required }));
+        }
+        let pool = self.fee_pool.get();
+        self.fee_pool.set(pool.saturating_add(value - device_fee));
+        Ok(device_fee)
+    }
+
+    /// Send a held device fee to `to`: the device owner on success, the
+    /// caller otherwise.
+    fn settle_device_fee(&mut self, to: Address, fee: U256) -> Result<(), VerifierError> {
+        if fee.is_zero() {
+            return Ok(());
+        }
+        self.vm()
+            .transfer_eth(to, fee)
+            .map_err(|_| VerifierError::FeeTransferFailed(FeeTransferFailed { to }))
+    }
+
+    /// Return a device's deposit to `to`, in ETH if `token` is zero.
+    fn refund_deposit(
+        &mut self,
+        to: Address,
+        token: Address,
+        amount: U256,
+    ) -> Result<(), VerifierError> {
+        if amount.is_zero() {
+            return Ok(());
+        }
+        if token.is_zero() {
+            return self.vm().transfer_eth(to, amount).map_err(|_| {
+                VerifierError::DepositTransferFailed(DepositTransferFailed { token })
+            });
+        }
+        let calldata = interfaces::IERC20::transferCall { to, amount }.abi_encode();
+        self.call_erc20(token, &calldata)
+    }
+
+    /// Call an ERC-20 method returning `bool`. Tokens that return nothing
+    /// are accepted; a revert or a `false` return fails.
+    fn call_erc20(&mut self, token: Address, calldata: &[u8]) -> Result<(), VerifierError> {
+        let ok = match self.vm().call(&Call::new(), token, calldata) {
+            Ok(ret) => {
+                ret.is_empty() || (ret.len() == 32 && U256::from_be_slice(&ret) == U256::from(1))
+            }
+            Err(_) => false,
+        };
+        if !ok {
+            return Err(VerifierError::DepositTransferFailed(
+                DepositTransferFailed { token },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Call `mint(to, amount)` on the reward token; `false` if it reverts.
+    fn mint_reward(&mut self, to: Address, amount: U256) -> bool {
+        let calldata = interfaces::IRewardToken::mintCall { to, amount }.abi_encode();
+        let token = self.reward_token.get();
+        self.vm().call(&Call::new(), token, &calldata).is_ok()
+    }
+
+    /// Mint a device's whole pending reward to `to`. Reverts with
+    /// `RewardMintFailed` and keeps the balance if the token refuses.
+    fn pay_pending_reward(
+        &mut self,
+        device_id: FixedBytes<32>,
+        to: Address,
+    ) -> Result<U256, VerifierError> {
+        let amount = self.pending_rewards.get(device_id);
+        if amount.is_zero() {
+            return Ok(U256::ZERO);
+        }
+
+        self.pending_rewards.delete(device_id);
+        if !self.mint_reward(to, amount) {
+            self.pending_rewards.setter(device_id).set(amount);
+            return Err(VerifierError::RewardMintFailed(RewardMintFailed { amount }));
+        }
+        Ok(amount)
+    }
+
+    /// Best-effort reward for a successful verification. A reverting token
+    /// must not undo the verification, so the amount is parked in
+    /// `pending_rewards` and `RewardFailed` is emitted instead.
+    fn pay_reward(&mut self, device_id: FixedBytes<32>) {
+        let amount = self.reward_amount.get();
+        let owner = self.device_owners.get(device_id);
+        if self.reward_token.get().is_zero() || amount.is_zero() || owner.is_zero() {
+            return;
+        }
+        if !self.mint_reward(owner, amount) {
+            let pending = self.pending_rewards.get(device_id);
+            self.pending_rewards.setter(device_id).set(pending + amount);
+            log(self.vm(), RewardFailed { device_id, amount });
+        }
+    }
+
+    /// Map a score on `input_scale` to 0–100 using the rounding mode.
+    fn rescale_score(&self, raw: U256) -> Result<U256, VerifierError> {
+        let scale = self.input_scale.get();
+        if scale.is_zero() {
+            return Self::score_to_u8(raw).map(U256::from);
+        }
+        if raw > scale {
+            return Err(VerifierError::InvalidScore(InvalidScore { score: raw }));
+        }
+
+        let scaled = raw * U256::from(MAX_TRUST_SCORE);
+        if self.rounding_mode.get() == U256::from(ROUND_NEAREST) {
+            Ok((scaled + scale / U256::from(2)) / scale)
+        } else {
+            Ok(scaled / scale)
+        }
+    }
+
+    /// `new_score` under the controls every relayer-submitted score goes
+    /// through: `ConsensusRequired` while a validator quorum is
+    /// configured, then `rescale_score` and `clamp_score_delta`.
+    fn relayed_score(
+        &self,
+        device_id: FixedBytes<32>,
+        new_score: U256,
+    ) -> Result<U256, VerifierError> {
+        if !self.consensus_threshold.get().is_zero() {
+            return Err(VerifierError::ConsensusRequired(ConsensusRequired {}));
+        }
+        Ok(self.clamp_score_delta(device_id, self.rescale_score(new_score)?))
+    }
+
+    /// Clamp `score` to within `max_score_delta` of the device's previous
+    /// score, if it has one.
+    fn clamp_score_delta(&self, device_id: FixedBytes<32>, score: U256) -> U256 {
+        let cap = self.max_score_delta.get();
+        if cap.is_zero() || self.score_updated_at.get(device_id).is_zero() {
+            return score;
+        }
+        let previous = self.device_trust_scores.get(device_id);
+        score.clamp(previous.saturating_sub(cap), previous.saturating_add(cap))
+    }
+
+    /// Best-effort `report(device_id, verdict)` to the aggregator, if set.
+    /// A revert emits `AggregatorNotifyFailed` instead of failing the
+    /// verification.
+    fn notify_aggregator(&mut self, device_id: FixedBytes<32>, verdict: bool) {
+        let aggregator = self.aggregator.get();
+        if aggregator.is_zero() {
+            return;
+        }
+        let calldata =
+            interfaces::IVerificationAggregator::reportCall { device_id, verdict }.abi_encode();
+        if self.vm().call(&Call::new(), aggregator, &calldata).is_err() {
+            log(self.vm(), AggregatorNotifyFailed { device_id, verdict });
+        }
+    }
+
+    /// keccak256(device_count || trust_score_sum || total_verifications)
+    fn stats_digest(&self) -> B256 {
+        let mut message = Vec::with_capacity(32 * 3);
+        message.extend_from_slice(&U256::from(self.device_list.len()).to_be_bytes::<32>());
+        message.extend_from_slice(&self.trust_score_sum.get().to_be_bytes::<32>());
+        message.extend_from_slice(&self.total_verifications.get().to_be_bytes::<32>());
+        self.vm().native_keccak256(&message)
+    }
+
+    /// Revoke a device and emit `DeviceRevoked`, or return false if it
+    /// already was.
+    fn revoke(&mut self, device_id: FixedBytes<32>) -> bool {
+        if self.get_flag(device_id, FLAG_REVOKED) {
+            return false;
+        }
+        self.set_flag(device_id, FLAG_REVOKED, true);
+        self.clear_memo(device_id);
+        self.revoked_count
+            .set(self.revoked_count.get() + U256::from(1));
+        log(self.vm(), DeviceRevoked { device_id });
+        true
+    }
+
+    /// Read bit `bit` of a device's `device_flags`.
+    fn get_flag(&self, device_id: FixedBytes<32>, bit: usize) -> bool {
+        self.device_flags.get(device_id).bit(bit)
+    }
+
+    /// Set or clear bit `bit` of a device's `device_flags`, leaving the
+    /// other flags untouched. An unchanged bit is not written back.
+    fn set_flag(&mut self, device_id: FixedBytes<32>, bit: usize, value: bool) {
+        let mut flags = self.device_flags.get(device_id);
+        if flags.bit(bit) == value {
+            return;
+        }
+        flags.set_bit(bit, value);
+        self.device_flags.setter(device_id).set(flags);
+    }
+
+    /// Narrow a score to `u8`, reverting with `InvalidScore` above
+    /// `MAX_TRUST_SCORE` rather than truncating.
+    fn score_to_u8(score: U256) -> Result<u8, VerifierError> {
+        u8::try_from(score)
+            .ok()
+            .filter(|&narrow| u64::from(narrow) <= MAX_TRUST_SCORE)
+            .ok_or(VerifierError::InvalidScore(InvalidScore { score }))
+    }
+
+    /// `OVERRIDE_*` mode encoded in a `device_flags` word.
+    fn override_mode(flags: U256) -> u8 {
+        if flags.bit(FLAG_FORCE_PASS) {
+            OVERRIDE_FORCE_PASS
+        } else if flags.bit(FLAG_FORCE_FAIL) {
+            OVERRIDE_FORCE_FAIL
+        } else {
+            OVERRIDE_NORMAL
+        }
+    }
+
+    /// Deregister a device: forfeit its pending rewards, clear it, and
+    /// refund its deposit and stake to the owner.
+    fn remove_device(&mut self, device_id: FixedBytes<32>) -> Result<(), VerifierError> {
+        let pending = self.pending_rewards.get(device_id);
+        if !pending.is_zero() {
+            self.pending_rewards.delete(device_id);
+            log(
+                self.vm(),
+                RewardsForfeited {
+                    device_id,
+                    amount: pending,
+                },
+            );
+        }
+        let owner = self.device_owners.get(device_id);
+        let amount = self.device_deposits.get(device_id);
+        let token = self.device_deposit_tokens.get(device_id);
+        let stake = self.device_stake.get(device_id);
+        self.device_deposits.delete(device_id);
+        self.device_deposit_tokens.delete(device_id);
+        self.device_stake.delete(device_id);
+        self.clear_device(device_id);
+        self.refund_deposit(owner, token, amount)?;
+        self.refund_deposit(owner, Address::ZERO, stake)
+    }
+
+    /// Erase all per-device state written since registration.
+    fn clear_device(&mut self, device_id: FixedBytes<32>) {
+        let owner = self.device_owners.get(device_id);
+        self.remove_from_owner_devices(owner, device_id);
+        self.device_owners.delete(device_id);
+        let score = self.device_trust_scores.get(device_id);
+        self.trust_score_sum.set(self.trust_score_sum.get() - score);
+        self.shift_histogram(score, false);
+        self.shift_fleet_trust(device_id, score, false);
+        self.device_manufacturers.delete(device_id);
+        self.device_trust_scores.delete(device_id);
+        self.set_flag(device_id, FLAG_HAS_SCORE, false);
+        self.set_flag(device_id, FLAG_FORCE_PASS, false);
+        self.set_flag(device_id, FLAG_FORCE_FAIL, false);
+        self.score_updated_at.delete(device_id);
+        self.last_verified_at.delete(device_id);
+        self.last_attestation_hashes.delete(device_id);
+        self.registration_index.delete(device_id);
+        self.failure_count.delete(device_id);
+        let count = self.device_verification_counts.get(device_id);
+        self.shadow_verification_counts.setter(device_id).set(count);
+        self.device_verification_counts.delete(device_id);
+        self.device_metadata.delete(device_id);
+        self.verification_fee.delete(device_id);
+        self.staged_scores.delete(device_id);
+        self.staged_score_effective_at.delete(device_id);
+        self.clear_memo(device_id);
+        self.device_quota.delete(device_id);
+        self.device_class.delete(device_id);
+        self.device_attestation_keys.delete(device_id);
+        self.device_previous_key.delete(device_id);
+        self.device_key_rotated_at.delete(device_id);
+        self.device_key_commitment.delete(device_id);
+        self.device_key_schemes.delete(device_id);
+        self.device_ed25519_keys.delete(device_id);
+        self.device_signers.setter(device_id).erase();
+        self.device_signer_threshold.delete(device_id);
+        self.outstanding_challenges.delete(device_id);
+        self.clear_tags(device_id);
+        self.remove_from_device_list(device_id);
+        self.set_flag(device_id, FLAG_REGISTERED, false);
+    }
+
+    /// Shared body of `verify_tee_attestation` and its strict variant.
+    fn run_verification(
+        &mut self,
+        device_id: FixedBytes<32>,
+        attestation_data: &[u8],
+        strict: bool,
+    ) -> Result<u8, VerifierError> {
+        let now = U256::from(self.vm().block_timestamp());
+        self.verification_guards(now)?;
+        self.when_within_balance_cap()?;
+        // Every gating flag comes from this one read; the word is kept in
+        // step with storage below rather than read again
+        let flags = self.device_flags.get(device_id);
+        if let Some(status) = self.apply_override(device_id, flags) {
+            return Ok(status);
+        }
+        if let Some(status) = self.reject_inactive(device_id, flags) {
+            return Ok(status);
+        }
+        let flags = self.settle_staged_score_flags(device_id, flags);
+
+        // 1. Reject payloads the policy does not allow
+        if U256::from(attestation_data.len()) > self.attestation_len_limit() {
+            return Err(VerifierError::AttestationTooLarge(AttestationTooLarge {
+                length: U256::from(attestation_data.len()),
+            }));
+        }
+
+        let status = self.evaluate_attestation(device_id, attestation_data, strict, now, flags)?;
+        if status == STATUS_VERIFIED {
+            let hash = self.vm().native_keccak256(attestation_data);
+            self.last_attestation_hashes.setter(device_id).set(hash);
+        }
+        Ok(status)
+    }
+
+    /// The contract-wide guards every verification checks first.
+    fn verification_guards(&self, now: U256) -> Result<(), VerifierError> {
+        self.when_not_decommissioned()?;
+        self.when_not_paused()?;
+        if self.verification_disabled.get() {
+            return Err(VerifierError::VerificationDisabled(VerificationDisabled {}));
+        }
+        self.when_relayer_alive(now)?;
+        self.when_enough_relayers()?;
+        self.when_oracle_approves()
+    }
+
+    /// The status an owner override forces, logging `OverrideApplied`, or
+    /// `None` in normal mode. An override supersedes every device check,
+    /// and leaves counters, rewards and the log untouched. Value sent with
+    /// the call goes to the fee pool.
+    fn apply_override(&mut self, device_id: FixedBytes<32>, flags: U256) -> Option<u8> {
+        let mode = Self::override_mode(flags);
+        if mode == OVERRIDE_NORMAL {
+            return None;
+        }
+        let value = self.vm().msg_value();
+        self.fee_pool.set(self.fee_pool.get().saturating_add(value));
+        log(self.vm(), OverrideApplied { device_id, mode });
+        Some(if mode == OVERRIDE_FORCE_PASS {
+            STATUS_VERIFIED
+        } else {
+            STATUS_REJECTED
+        })
+    }
+
+    /// `STATUS_REJECTED` for a revoked or retired device, recorded as a
+    /// failure before any payload check or fee, or `None` otherwise. Value
+    /// sent with the call goes to the fee pool.
+    fn reject_inactive(&mut self, device_id: FixedBytes<32>, flags: U256) -> Option<u8> {
+        if !flags.bit(FLAG_REVOKED) && !flags.bit(FLAG_RETIRED) {
+            return None;
+        }
+        let value = self.vm().msg_value();
+        self.fee_pool.set(self.fee_pool.get().saturating_add(value));
+        let score = self.effective_score(device_id);
+        self.record_failure(device_id, score);
+        Some(STATUS_REJECTED)
+    }
+
+    /// Whether the payload checks of `evaluate_attestation` that are not
+    /// reported as their own `simulate_verification` gate would pass.
+    fn simulate_payload_checks(
+        &self,
+        device_id: FixedBytes<32>,
+        attestation_data: &[u8],
+        payload: &[u8],
+        keyed: bool,
+        flags: U256,
+        now: U256,
+    ) -> bool {
+        if U256::from(attestation_data.len()) > self.attestation_len_limit()
+            || (attestation_data.is_empty() && (keyed || self.empty_payload_disallowed.get()))
+        {
+            return false;
+        }
+        let attestation = if payload.is_empty() {
+            None
+        } else {
+            match cbor::decode_attestation(payload) {
+                Some(attestation) => Some(attestation),
+                None => return false,
+            }
+        };
+
+        let epoch = attestation.map_or(0, |a| a.epoch);
+        let model = attestation.and_then(|a| a.model).unwrap_or_default();
+        let outstanding = self.outstanding_challenges.get(device_id);
+        let challenged = !self.require_onchain_challenge.get()
+            || (!outstanding.is_zero() && attestation.is_some_and(|a| a.challenge == outstanding));
+        let claims = attestation.map_or(U256::ZERO, |a| a.claims);
+        if U256::from(epoch).saturating_add(self.epoch_grace.get()) < self.current_epoch.get()
+            || (self.enforce_models.get() && !self.allowed_models.get(model))
+            || self.check_beacon(attestation.as_ref()).is_err()
+            || self.check_seed(attestation.as_ref()).is_err()
+            || self.check_manufacture_date(attestation.as_ref()).is_err()
+            || !challenged
+            || !self.asserts_required_claims(claims)
+        {
+            return false;
+        }
+
+        let last_verified = self.last_verified_at.get(device_id);
+        let (_, interval) = self.device_policy(device_id);
+        let rate_limited = !interval.is_zero()
+            && !last_verified.is_zero()
+            && now < last_verified.saturating_add(interval);
+        let nonce_ok = attestation
+            .and_then(|a| a.nonce)
+            .is_none_or(|nonce| self.attestation_nonce_offset(device_id, nonce).is_ok());
+        let stored = self.device_hw_counters.get(device_id);
+        let counter_ok = !flags.bit(FLAG_HAS_HW_COUNTER)
+            || attestation
+                .and_then(|a| a.hw_counter)
+                .is_some_and(|counter| U256::from(counter) > stored);
+        let has_quota = !self.quota_enabled.get() || !self.device_quota.get(device_id).is_zero();
+        !rate_limited && nonce_ok && counter_ok && has_quota
+    }
+
+    /// `keccak256(memo_generation || attestation_data)`.
+    fn memo_key(&self, attestation_data: &[u8]) -> B256 {
+        let mut message = Vec::with_capacity(32 + attestation_data.len());
+        message.extend_from_slice(&self.memo_generation.get().to_be_bytes::<32>());
+        message.extend_from_slice(attestation_data);
+        self.vm().native_keccak256(&message)
+    }
+
+    /// `(payload length, signed, keyed)` memoized for `key` in the current
+    /// block, if any; the word packs them as `length << 2 | keyed << 1 |
+    /// signed`.
+    fn memoized_signature(
+        &self,
+        device_id: FixedBytes<32>,
+        key: B256,
+    ) -> Option<(usize, bool, bool)> {
+        let block = U256::from(self.vm().block_number());
+        if self.memo_blocks.get(device_id) != block || self.memo_keys.get(device_id) != key {
+            return None;
+        }
+        let word = self.memo_signatures.get(device_id);
+        Some(((word >> 2_usize).to::<usize>(), word.bit(0), word.bit(1)))
+    }
+
+    fn memoize_signature(
+        &mut self,
+        device_id: FixedBytes<32>,
+        key: B256,
+        (length, signed, keyed): (usize, bool, bool),
+    ) {
+        let block = U256::from(self.vm().block_number());
+        let word = U256::from(length) << 2 | U256::from(keyed) << 1 | U256::from(signed);
+        self.memo_blocks.setter(device_id).set(block);
+        self.memo_keys.setter(device_id).set(key);
+        self.memo_signatures.setter(device_id).set(word);
+    }
+
+    /// Drop a device's memoized signature verdict.
+    fn clear_memo(&mut self, device_id: FixedBytes<32>) {
+        self.memo_blocks.delete(device_id);
+        self.memo_keys.delete(device_id);
+        self.memo_signatures.delete(device_id);
+    }
+
+    /// Add a verification to the global and device counters, first
+    /// snapshotting the total if the mirrored epoch has moved on since the
+    /// last one was counted.
+    fn count_verification(&mut self, device_id: FixedBytes<32>) {
+        let total = self.total_verifications.get();
+        let epoch = self.current_epoch.get();
+        let previous = self.verification_epoch.get();
+        if epoch != previous {
+            self.epoch_verification_ends.setter(previous).set(total);
+            self.epoch_verification_snapshots.setter(epoch).set(total);
+            self.verification_epoch.set(epoch);
+        }
+        self.total_verifications.set(total + U256::from(1));
+        let count = self.device_verification_counts.get(device_id) + U256::from(1);
+        self.device_verification_counts.setter(device_id).set(count);
+
+        let interval = self.milestone_interval.get();
+        if !interval.is_zero() && (count % interval).is_zero() {
+            log(self.vm(), MilestoneReached { device_id, count });
+        }
+    }
+
+    /// Steps 2 onwards of `run_verification`: the signature, payload and
+    /// device checks, and the effects of the verdict.
+    fn evaluate_attestation(
+        &mut self,
+        device_id: FixedBytes<32>,
+        attestation_data: &[u8],
+        strict: bool,
+        now: U256,
+        flags: U256,
+    ) -> Result<u8, VerifierError> {
+        // 2. Split off and check the signature, for devices with a key.
+        //    Within one block a repeated attestation can reuse the first
+        //    call's verdict, certificate chain included; only this step
+        //    is memoized.
+        let memo_key = self
+            .memoize_verifications
+            .get()
+            .then(|| self.memo_key(attestation_data));
+        let memoized = memo_key.and_then(|key| self.memoized_signature(device_id, key));
+        let (payload, signed, keyed, signer) = match memoized {
+            Some((length, signed, keyed)) => (&attestation_data[..length], signed, keyed, None),
+            None => self.check_attestation_signature(device_id, attestation_data),
+        };
+        if attestation_data.is_empty() && (keyed || self.empty_payload_disallowed.get()) {
+            return Err(VerifierError::MalformedAttestation(MalformedAttestation {}));
+        }
+        let verdict = (payload.len(), signed, keyed);
+        let signed = signed && (keyed || !strict);
+
+        // 3. Decode the CBOR payload; an empty payload takes the pilot path
+        //    and asserts no claims. A certificate chain in it must lead
+        //    from the device signer to a trusted root.
+        let attestation = if payload.is_empty() {
+            None
+        } else {
+            Some(self.decode_attestation(payload)?)
+        };
+        if memoized.is_none() {
+            if let Some(chain) = attestation.and_then(|a| a.cert_chain) {
+                self.check_cert_chain(device_id, signer, chain)?;
+            }
+            if let Some(key) = memo_key {
+                self.memoize_signature(device_id, key, verdict);
+            }
+        }
+        self.evaluate_payload(device_id, attestation, signed, strict, now, flags)
+    }
+
+    /// Steps 4 onwards of `run_verification`, once the payload is decoded
+    /// and the signature verdict (including the strict path's need for a
+    /// key) is known.
+    fn evaluate_payload(
+        &mut self,
+        device_id: FixedBytes<32>,
+        attestation: Option<cbor::Attestation<'_>>,
+        signed: bool,
+        strict: bool,
+        now: U256,
+        flags: U256,
+    ) -> Result<u8, VerifierError> {
+        let claims = attestation.map_or(U256::ZERO, |a| a.claims);
+
+        // With model enforcement on, the payload must name an allowlisted
+        // hardware model
+        if self.enforce_models.get() {
+            let model = attestation.and_then(|a| a.model).unwrap_or_default();
+            if !self.allowed_models.get(model) {
+                return Err(VerifierError::ModelNotAllowed(ModelNotAllowed { model }));
+            }
+        }
+        self.check_beacon(attestation.as_ref())?;
+        self.check_seed(attestation.as_ref())?;
+        self.check_manufacture_date(attestation.as_ref())?;
+
+        // Spend one unit of quota whatever the verdict
+        if self.quota_enabled.get() {
+            let quota = self.device_quota.get(device_id);
+            if quota.is_zero() {
+                return Err(VerifierError::QuotaExhausted(QuotaExhausted { device_id }));
+            }
+            self.device_quota
+                .setter(device_id)
+                .set(quota - U256::from(1));
+        }
+
+        // Charge the fee for the payload's attestation type, and hold the
+        // device owner's fee until the outcome is known
+        let device_fee = self
+            .collect_verification_fee(device_id, attestation.map_or(0, |a| a.attestation_type))?;
+
+        // Once epochs are mirrored, the attestation must come from a recent
+        // one; payloads without an epoch count as epoch 0
+        let epoch = attestation.map_or(0, |a| a.epoch);
+        if U256::from(epoch).saturating_add(self.epoch_grace.get()) < self.current_epoch.get() {
+            return Err(VerifierError::StaleEpoch(StaleEpoch { epoch }));
+        }
+
+        // With on-chain challenges required, the payload must echo the
+        // device's outstanding challenge
+        let challenged = !self.require_onchain_challenge.get() || {
+            let outstanding = self.outstanding_challenges.get(device_id);
+            !outstanding.is_zero() && attestation.is_some_and(|a| a.challenge == outstanding)
+        };
+
+        // 4. Rate-limit repeated verifications of the same device
+        let last_verified = self.last_verified_at.get(device_id);
+        let (threshold, interval) = self.device_policy(device_id);
+        if !interval.is_zero()
+            && !last_verified.is_zero()
+            && now < last_verified.saturating_add(interval)
+        {
+            return Err(VerifierError::VerificationTooFrequent(
+                VerificationTooFrequent { device_id },
+            ));
+        }
+
+        // Payloads carrying a nonce must use an unconsumed one in the
+        // device's window; it is consumed once the verification passes
+        let nonce_offset = match attestation.and_then(|a| a.nonce) {
+            Some(nonce) => Some(self.attestation_nonce_offset(device_id, nonce)?),
+            None => None,
+        };
+
+        // Once a device has reported a hardware counter, every attestation
+        // must carry a strictly higher one
+        let hw_counter = attestation.and_then(|a| a.hw_counter);
+        if flags.bit(FLAG_HAS_HW_COUNTER) {
+            let stored = self.device_hw_counters.get(device_id);
+            if hw_counter.is_none_or(|counter| U256::from(counter) <= stored) {
+                return Err(VerifierError::CounterRollback(CounterRollback { stored }));
+            }
+        }
+
+        // 5. Fetch current trust score, honouring an effective staged one
+        let score = self.effective_score(device_id);
+
+        // 6. Grant Pilot: Check if device is registered with valid score
+        //    and the payload asserts every claim the policy requires. The
+        //    strict path also needs a real signature and recent liveness.
+        //    Checks short-circuit, so storage behind a failed one is never
+        //    read, and the regular path skips the strict-only reads. An
+        //    unregistered device is rejected before anything else, so it
+        //    never reports a lesser failure such as a low score; revoked
+        //    and retired ones never get here.
+        let status = if !flags.bit(FLAG_REGISTERED) {
+            STATUS_REJECTED
+        } else if !signed {
+            STATUS_BAD_SIGNATURE
+        } else if !flags.bit(FLAG_HAS_SCORE) || score < self.score_floor(threshold) {
+            STATUS_LOW_SCORE
+        } else if !self.is_score_fresh(device_id, now)
+            || (strict && !self.is_recently_verified(last_verified, now))
+        {
+            STATUS_STALE
+        } else if !challenged || !self.asserts_required_claims(claims) {
+            STATUS_REJECTED
+        } else {
+            STATUS_VERIFIED
+        };
+        if status == STATUS_VERIFIED {
+            self.last_verified_at.setter(device_id).set(now);
+            if let Some(offset) = nonce_offset {
+                self.consume_attestation_nonce(device_id, offset);
+            }
+            if let Some(counter) = hw_counter {
+                self.device_hw_counters
+                    .setter(device_id)
+                    .set(U256::from(counter));
+                if !flags.bit(FLAG_HAS_HW_COUNTER) {
+                    let mut flags = flags;
+                    flags.set_bit(FLAG_HAS_HW_COUNTER, true);
+                    self.device_flags.setter(device_id).set(flags);
+                }
+            }
+
+            // Increment global verification counter
+            self.count_verification(device_id);
+            self.failure_count.delete(device_id);
+            self.outstanding_challenges.delete(device_id);
+            self.append_verification_log(device_id, now);
+            self.record_throughput(now);
+
+            self.apply_verification_outcome(device_id, score, true);
+            self.pay_reward(device_id);
+            self.notify_aggregator(device_id, true);
+            let owner = self.device_owners.get(device_id);
+            self.settle_device_fee(owner, device_fee)?;
+            return Ok(STATUS_VERIFIED);
+        }
+
+        self.record_failure(device_id, score);
+        let caller = self.vm().msg_sender();
+        self.settle_device_fee(caller, device_fee)?;
+        Ok(status)
+    }
+
+    /// Count a failed verification and report it. Only a relayer's or the
+    /// owner's failures move the moving average.
+    fn record_failure(&mut self, device_id: FixedBytes<32>, score: U256) {
+        if self.is_device_registered(device_id) {
+            let failures = self.failure_count.get(device_id);
+            self.failure_count
+                .setter(device_id)
+                .set(failures + U256::from(1));
+        }
+        let caller = self.vm().msg_sender();
+        if self.relayers.get(caller) || caller == self.owner.get() {
+            self.apply_verification_outcome(device_id, score, false);
+        }
+        self.notify_aggregator(device_id, false);
+    }
+
+    fn clear_tags(&mut self, device_id: FixedBytes<32>) {
+        let count = self.device_tag_list.get(device_id).len();
+        for index in 0..count {
+            let tag = self
+                .device_tag_list
+                .get(device_id)
+                .get(index)
+                .unwrap_or_default();
+            self.device_tags.setter(device_id).delete(tag);
+            self.device_tag_positions.setter(device_id).delete(tag);
+        }
+        self.device_tag_list.setter(device_id).erase();
+    }
+
+    /// Whether `signer` may sign for a relayer: a relayer, the current
+    /// relayer key, or the previous key within its rotation grace.
+    fn is_relayer_signer(&self, signer: Address) -> bool {
+        if self.relayers.get(signer) || signer == self.current_relayer_key.get() {
+            return true;
+        }
+        let expires = self
+            .relayer_key_rotated_at
+            .get()
+            .saturating_add(self.key_rotation_grace.get());
+        signer == self.previous_relayer_key.get()
+            && U256::from(self.vm().block_timestamp()) <= expires
+    }
+
+    /// Emit `ConfigChanged` for the parameter `key` from `config.rs`.
+    fn config_changed(&self, key: FixedBytes<32>, old_value: U256, new_value: U256) {
+        log(
+            self.vm(),
+            ConfigChanged {
+                key,
+                old_value,
+                new_value,
+            },
+        );
+    }
+
+    /// Make `new_key` the current relayer key, keeping the old one as the
+    /// previous key for `key_rotation_grace`.
+    fn install_relayer_key(&mut self, new_key: Address) -> Result<(), VerifierError> {
+        if new_key.is_zero() {
+            return Err(VerifierError::InvalidSigner(InvalidSigner {
+                signer: new_key,
+            }));
+        }
+        self.previous_relayer_key
+            .set(self.current_relayer_key.get());
+        self.current_relayer_key.set(new_key);
+        self.relayer_key_rotated_at
+            .set(U256::from(self.vm().block_timestamp()));
+        Ok(())
+    }
+
+    fn note_relayer_activity(&mut self) {
+        self.last_relayer_heartbeat
+            .set(U256::from(self.vm().block_timestamp()));
+    }
+
+    fn when_relayer_alive(&self, now: U256) -> Result<(), VerifierError> {
+        let silence = self.max_relayer_silence.get();
+        let last_heartbeat = self.last_relayer_heartbeat.get();
+        if !silence.is_zero() && now > last_heartbeat.saturating_add(silence) {
+            return Err(VerifierError::RelayerSilent(RelayerSilent {
+                last_heartbeat,
+            }));
+        }
+        Ok(())
+    }
+
+    fn when_enough_relayers(&self) -> Result<(), VerifierError> {
+        let count = self.relayer_count.get();
+        if count < self.min_relayers.get() {
+            return Err(VerifierError::InsufficientRelayers(InsufficientRelayers {
+                count,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Position of `nonce` in the device's nonce window, or `InvalidNonce`
+    /// if it is below the next nonce, past the window, or already used.
+    fn attestation_nonce_offset(
+        &self,
+        device_id: FixedBytes<32>,
+        nonce: u64,
+    ) -> Result<usize, VerifierError> {
+        let next = self.attestation_nonces.get(device_id);
+        let offset = U256::from(nonce)
+            .checked_sub(next)
+            .filter(|offset| *offset <= self.nonce_window.get())
+            .map(|offset| offset.to::<usize>());
+        match offset {
+            Some(offset) if !self.attestation_nonce_bitmaps.get(device_id).bit(offset) => {
+                Ok(offset)
+            }
+            _ => Err(VerifierError::InvalidNonce(InvalidNonce { nonce })),
+        }
+    }
+
+    /// Mark the nonce at `offset` used, then slide the window past every
+    /// consumed nonce at its start.
+    fn consume_attestation_nonce(&mut self, device_id: FixedBytes<32>, offset: usize) {
+        let mut bitmap = self.attestation_nonce_bitmaps.get(device_id);
+        bitmap.set_bit(offset, true);
+        let consumed = bitmap.trailing_ones();
+        let next = self.attestation_nonces.get(device_id);
+        self.attestation_nonces
+            .setter(device_id)
+            .set(next + U256::from(consumed));
+        self.attestation_nonce_bitmaps
+            .setter(device_id)
+            .set(bitmap >> consumed);
+    }
+
+    fn asserts_required_claims(&self, claims: U256) -> bool {
+        let required = self.required_claims.get();
+        claims & required == required
+    }
+
+    /// Whether a device last verified within `strict_liveness_window`.
+    fn is_recently_verified(&self, last_verified: U256, now: U256) -> bool {
+        let window = self.strict_liveness_window.get();
+        window.is_zero()
+            || (!last_verified.is_zero() && now <= last_verified.saturating_add(window))
+    }
+
+    /// Lowest passing score under `threshold`: the threshold itself, but
+    /// at least 1 unless explicit zero scores are accepted.
+    fn score_floor(&self, threshold: U256) -> U256 {
+        if self.accept_zero_score.get() {
+            threshold
+        } else {
+            threshold.max(U256::from(1))
+        }
+    }
+
+    fn tier_boundaries(&self) -> (U256, U256) {
+        let silver_min = self.silver_min.get();
+        if silver_min.is_zero() {
+            (U256::from(DEFAULT_SILVER_MIN), U256::from(DEFAULT_GOLD_MIN))
+        } else {
+            (silver_min, self.gold_min.get())
+        }
+    }
+
+    fn renounce_delay(&self) -> U256 {
+        let timelock = self.renounce_timelock.get();
+        if timelock.is_zero() {
+            U256::from(DEFAULT_RENOUNCE_TIMELOCK)
+        } else {
+            timelock
+        }
+    }
+
+    fn verification_log_limit(&self) -> U256 {
+        let capacity = self.verification_log_capacity.get();
+        if capacity.is_zero() {
+            U256::from(DEFAULT_VERIFICATION_LOG_CAPACITY)
+        } else {
+            capacity
+        }
+    }
+
+    /// Count a successful verification in the current hour's bucket,
+    /// resetting a bucket left over from `THROUGHPUT_BUCKETS` hours ago.
+    fn record_throughput(&mut self, now: U256) {
+        let hour = now / U256::from(THROUGHPUT_BUCKET_SECONDS);
+        let slot = hour % U256::from(THROUGHPUT_BUCKETS);
+        let count = if self.throughput_hours.get(slot) == hour {
+            self.throughput_counts.get(slot)
+        } else {
+            self.throughput_hours.setter(slot).set(hour);
+            U256::ZERO
+        };
+        self.throughput_counts
+            .setter(slot)
+            .set(count + U256::from(1));
+    }
+
+    /// Append to the verification log, overwriting the oldest entry once
+    /// the ring is full.
+    fn append_verification_log(&mut self, device_id: FixedBytes<32>, now: U256) {
+        let total = self.verification_log_total.get();
+        let slot = total % self.verification_log_limit();
+        self.verification_log_devices.setter(slot).set(device_id);
+        self.verification_log_times.setter(slot).set(now);
+        self.verification_log_total.set(total + U256::from(1));
+    }
+
+    /// Swap-remove a device from `device_list`, if present.
+    fn remove_from_device_list(&mut self, device_id: FixedBytes<32>) {
+        let position = self.device_list_positions.get(device_id);
+        if position.is_zero() {
+            return;
+        }
+
+        let index = position.to::<usize>() - 1;
+        let last_index = self.device_list.len() - 1;
+        if index != last_index {
+            let last = self.device_list.get(last_index).unwrap_or_default();
+            if let Some(mut slot) = self.device_list.setter(index) {
+                slot.set(last);
+            }
+            self.device_list_positions.setter(last).set(position);
+        }
+        self.device_list.erase_last();
+        self.device_list_positions.delete(device_id);
+    }
+
+    /// With `min_manufacture_date` set, require the attestation to carry a
+    /// manufacture date no earlier than it; a missing date counts as 0.
+    fn check_manufacture_date(
+        &self,
+        attestation: Option<&cbor::Attestation>,
+    ) -> Result<(), VerifierError> {
+        let cutoff = self.min_manufacture_date.get();
+        if cutoff.is_zero() {
+            return Ok(());
+        }
+        let manufactured_at = attestation.and_then(|a| a.manufactured_at).unwrap_or(0);
+        if U256::from(manufactured_at) < cutoff {
+            return Err(VerifierError::DeviceTooOld(DeviceTooOld {
+                manufactured_at,
+            }));
+        }
+        Ok(())
+    }
+
+    fn check_seed(&self, attestation: Option<&cbor::Attestation>) -> Result<(), VerifierError> {
+        if !self.require_epoch_seed.get() {
+            return Ok(());
+        }
+        let epoch = self.current_epoch.get();
+        let expected = self.epoch_seeds.get(epoch);
+        if expected.is_zero() || attestation.and_then(|a| a.seed) != Some(expected) {
+            return Err(VerifierError::StaleSeed(StaleSeed { epoch }));
+        }
+        Ok(())
+    }
+
+    /// With `beacon_window` set, require the attestation's beacon to name a
+    /// block at most that many blocks back, with the hash `ArbSys` records
+    /// for it.
+    fn check_beacon(&self, attestation: Option<&cbor::Attestation>) -> Result<(), VerifierError> {
+        let window = self.beacon_window.get();
+        if window.is_zero() {
+            return Ok(());
+        }
+        let Some((block, hash)) = attestation.and_then(|a| a.beacon) else {
+            return Err(VerifierError::StaleBeacon(StaleBeacon {
+                block: U256::ZERO,
+            }));
+        };
+        let block = U256::from(block);
+        let stale = || VerifierError::StaleBeacon(StaleBeacon { block });
+        let current = self
+            .arb_sys_call(&interfaces::IArbSys::arbBlockNumberCall {}.abi_encode())
+            .ok_or_else(stale)?;
+        if block >= current || current - block > window {
+            return Err(stale());
+        }
+        let calldata = interfaces::IArbSys::arbBlockHashCall { arbBlockNum: block }.abi_encode();
+        if self.arb_sys_call(&calldata) != Some(U256::from_be_bytes(hash.0)) {
+            return Err(stale());
+        }
+        Ok(())
+    }
+
+    /// Static-call `ArbSys` for a single 32-byte word, or `None` on failure.
+    fn arb_sys_call(&self, calldata: &[u8]) -> Option<U256> {
+        let ret = self
+            .vm()
+            .static_call(&Call::new(), interfaces::ARB_SYS, calldata)
+            .ok()?;
+        (ret.len() == 32).then(|| U256::from_be_slice(&ret))
+    }
+
+    /// Ask the policy oracle, if set, to approve; a revert, malformed
+    /// return, or `false` rejects.
+    fn when_oracle_approves(&self) -> Result<(), VerifierError> {
+        let oracle = self.policy_oracle.get();
+        if oracle.is_zero() {
+            return Ok(());
+        }
+        let calldata = interfaces::IPolicyOracle::checkCall {}.abi_encode();
+        let approved = self
+            .vm()
+            .static_call(&Call::new(), oracle, &calldata)
+            .ok()
+            .and_then(|ret| {
+                interfaces::IPolicyOracle::checkCall::abi_decode_returns(&ret, true).ok()
+            })
+            .is_some_and(|ret| ret._0);
+        if !approved {
+            return Err(VerifierError::OracleRejected(OracleRejected { oracle }));
+        }
+        Ok(())
+    }
+
+    fn when_not_decommissioned(&self) -> Result<(), VerifierError> {
+        let successor = self.successor.get();
+        if !successor.is_zero() {
+            return Err(VerifierError::Decommissioned(Decommissioned { successor }));
+        }
+        Ok(())
+    }
+
+    /// Swap-remove a device from its owner's `owner_devices` list.
+    fn remove_from_owner_devices(&mut self, owner: Address, device_id: FixedBytes<32>) {
+        let position = self.owner_device_positions.get(device_id);
+        if position.is_zero() {
+            return;
+        }
+
+        let index = position.to::<usize>() - 1;
+        let mut owned = self.owner_devices.setter(owner);
+        let last_index = owned.len() - 1;
+        let last = owned.get(last_index).unwrap_or_default();
+        if index != last_index {
+            if let Some(mut slot) = owned.setter(index) {
+                slot.set(last);
+            }
+        }
+        owned.erase_last();
+        if index != last_index {
+            self.owner_device_positions.setter(last).set(position);
+        }
+        self.owner_device_positions.delete(device_id);
+    }
+
+    fn when_not_paused(&self) -> Result<(), VerifierError> {
+        if self.paused.get() {
+            return Err(VerifierError::ContractPaused(ContractPaused {}));
+        }
+        Ok(())
+    }
+
+    /// Revert when ETH sent by a payable call left the balance, which
+    /// already includes it, above `max_contract_balance`.
+    fn when_within_balance_cap(&self) -> Result<(), VerifierError> {
+        let cap = self.max_contract_balance.get();
+        if cap.is_zero() || self.vm().msg_value().is_zero() {
+            return Ok(());
+        }
+        if self.vm().balance(self.vm().contract_address()) > cap {
+            return Err(VerifierError::BalanceCapExceeded(BalanceCapExceeded {
+                cap,
+            }));
+        }
+        Ok(())
+    }
+
+    /// `(threshold, interval)` for a device: its class policy if one is
+    /// set, else the global policy. The threshold is not yet clamped to 1.
+    fn device_policy(&self, device_id: FixedBytes<32>) -> (U256, U256) {
+        let class = self.device_class.get(device_id);
+        if !class.is_zero() && self.class_policy_set.get(class) {
+            (
+                self.class_threshold.get(class),
+                self.class_min_interval.get(class),
+            )
+        } else {
+            (self.min_threshold.get(), self.min_interval.get())
+        }
+    }
+
+    fn attestation_len_limit(&self) -> U256 {
+        let limit = self.max_attestation_len.get();
+        if limit.is_zero() {
+            U256::from(MAX_ATTESTATION_LEN)
+        } else {
+            limit
+        }
+    }
+
+    fn is_score_fresh(&self, device_id: FixedBytes<32>, now: U256) -> bool {
+        let grace = self.score_grace.get();
+        grace.is_zero() || now <= self.score_updated_at.get(device_id).saturating_add(grace)
+    }
+
+    /// Digest a device's attestation key signs:
+    /// `personal_sign(keccak256("CERTID_ATTEST" || contract || device_id || payload))`.
+    fn attestation_digest(&self, device_id: FixedBytes<32>, payload: &[u8]) -> B256 {
+        let mut message = Vec::with_capacity(13 + 20 + 32 + payload.len());
+        message.extend_from_slice(b"CERTID_ATTEST");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(payload);
+        let hash = self.vm().native_keccak256(&message);
+        signature::eth_signed_message_hash(self.vm(), hash)
+    }
+
+    /// Whether `signer` is the device's attestation key, its previous key
+    /// within the rotation grace, or hashes to its key commitment. Always
+    /// false for a device with neither key nor commitment.
+    fn is_device_signer(&self, device_id: FixedBytes<32>, signer: Address) -> bool {
+        let key = self.device_attestation_keys.get(device_id);
+        if !key.is_zero() {
+            return signer == key || self.is_previous_device_key(device_id, signer);
+        }
+        let commitment = self.device_key_commitment.get(device_id);
+        !commitment.is_zero() && self.vm().native_keccak256(signer.as_slice()) == commitment
+    }
+
+    /// Digest `verify_tee_attestation_digest` expects for `nonce`.
+    fn device_digest(&self, device_id: FixedBytes<32>, nonce: U256) -> B256 {
+        let mut message = Vec::with_capacity(13 + 20 + 32 + 32);
+        message.extend_from_slice(b"CERTID_DIGEST");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(&nonce.to_be_bytes::<32>());
+        self.vm().native_keccak256(&message)
+    }
+
+    /// Whether `signer` is the device's previous attestation key and still
+    /// inside its rotation grace.
+    fn is_previous_device_key(&self, device_id: FixedBytes<32>, signer: Address) -> bool {
+        let expires = self
+            .device_key_rotated_at
+            .get(device_id)
+            .saturating_add(self.device_key_grace.get());
+        signer == self.device_previous_key.get(device_id)
+            && U256::from(self.vm().block_timestamp()) <= expires
+    }
+
+    /// `keccak256(abi.encode(EIP712Domain typehash, keccak256(name),
+    /// keccak256(version), chainid, address(this)))`.
+    fn eip712_domain_separator(&self) -> B256 {
+        let vm = self.vm();
+        let typehash = vm.native_keccak256(
+            b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+        );
+        let mut message = Vec::with_capacity(32 * 5);
+        message.extend_from_slice(typehash.as_slice());
+        message.extend_from_slice(vm.native_keccak256(EIP712_NAME.as_bytes()).as_slice());
+        message.extend_from_slice(vm.native_keccak256(EIP712_VERSION.as_bytes()).as_slice());
+        message.extend_from_slice(&U256::from(vm.chain_id()).to_be_bytes::<32>());
+        message.extend_from_slice(
+            B256::left_padding_from(vm.contract_address().as_slice()).as_slice(),
+        );
+        vm.native_keccak256(&message)
+    }
+
+    /// Digest each validator signs for a consensus score update:
+    /// `personal_sign(keccak256("CERTID_CONSENSUS" || contract || device_id || score || seq))`.
+    fn consensus_digest(&self, device_id: FixedBytes<32>, score: U256, seq: U256) -> B256 {
+        let mut message = Vec::with_capacity(16 + 20 + 32 * 3);
+        message.extend_from_slice(b"CERTID_CONSENSUS");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(&score.to_be_bytes::<32>());
+        message.extend_from_slice(&seq.to_be_bytes::<32>());
+        let hash = self.vm().native_keccak256(&message);
+        signature::eth_signed_message_hash(self.vm(), hash)
+    }
+
+    /// Digest a relayer signs for a score certificate:
+    /// `personal_sign(keccak256("CERTID_SCORE" || contract || device_id || score || seq))`.
+    fn score_certificate_digest(&self, device_id: FixedBytes<32>, score: U256, seq: U256) -> B256 {
+        let mut message = Vec::with_capacity(12 + 20 + 32 * 3);
+        message.extend_from_slice(b"CERTID_SCORE");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(&score.to_be_bytes::<32>());
+        message.extend_from_slice(&seq.to_be_bytes::<32>());
+        let hash = self.vm().native_keccak256(&message);
+        signature::eth_signed_message_hash(self.vm(), hash)
+    }
+
+    fn is_consensus_validator(&self, signer: Address) -> bool {
+        let set = &self.consensus_validators;
+        (0..set.len()).any(|i| set.get(i) == Some(signer))
+    }
+
+    /// Digest a trusted root signs to endorse a device's attestation key:
+    /// `personal_sign(keccak256("CERTID_ENDORSE" || contract || device_id || key))`.
+    fn endorsement_digest(&self, device_id: FixedBytes<32>, key: Address) -> B256 {
+        let mut message = Vec::with_capacity(14 + 20 + 32 + 20);
+        message.extend_from_slice(b"CERTID_ENDORSE");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(key.as_slice());
+        let hash = self.vm().native_keccak256(&message);
+        signature::eth_signed_message_hash(self.vm(), hash)
+    }
+
+    /// Split `attestation_data` into `(payload, signed, keyed, signer)`,
+    /// where `keyed` is whether the device has a key or key commitment and
+    /// `signer` is whoever the device signature recovers to, if anyone.
+    ///
+    /// Devices on a scheme other than secp256k1 are keyed but can never
+    /// be signed, since this contract cannot check their signatures.
+    ///
+    /// Devices with neither an attestation key nor a key commitment are
+    /// unsigned: the whole input is the payload and `signed` is whether
+    /// self-signed attestations are allowed. Otherwise the trailing 65
+    /// bytes are the device signature, whose signer must be the key or hash
+    /// to the commitment. Unless self-signed attestations are allowed, that
+    /// signature is followed by another 65 bytes from a trusted root (by
+    /// keccak256 of its address) over `endorsement_digest`, or the payload
+    /// carries a certificate chain, which `evaluate_attestation` then
+    /// requires to end at a trusted root.
+    fn check_attestation_signature<'a>(
+        &self,
+        device_id: FixedBytes<32>,
+        attestation_data: &'a [u8],
+    ) -> (&'a [u8], bool, bool, Option<Address>) {
+        if self.device_key_schemes.get(device_id) != U256::from(KEY_SCHEME_SECP256K1) {
+            return (&[], false, true, None);
+        }
+        let key = self.device_attestation_keys.get(device_id);
+        let commitment = self.device_key_commitment.get(device_id);
+        let self_signed = !self.self_signed_disallowed.get();
+        if key.is_zero() && commitment.is_zero() {
+            return (attestation_data, self_signed, false, None);
+        }
+        if !self_signed {
+            if let Some(chained) = self.check_chained_signature(device_id, attestation_data) {
+                return chained;
+            }
+        }
+        let (attestation_data, endorsement) = if self_signed {
+            (attestation_data, None)
+        } else {
+            let Some(split) = attestation_data.len().checked_sub(signature::SIGNATURE_LEN) else {
+                return (&[], false, true, None);
+            };
+            let (rest, endorsement) = attestation_data.split_at(split);
+            (rest, Some(endorsement))
+        };
+        let Some(split) = attestation_data.len().checked_sub(signature::SIGNATURE_LEN) else {
+            return (&[], false, true, None);
+        };
+
+        let (payload, sig) = attestation_data.split_at(split);
+        let digest = self.attestation_digest(device_id, payload);
+        let Some(signer) = signature::recover_signer(self.vm(), digest, sig) else {
+            return (payload, false, true, None);
+        };
+        let mut signed = self.is_device_signer(device_id, signer);
+        if let Some(endorsement) = endorsement {
+            let digest = self.endorsement_digest(device_id, signer);
+            signed = signed
+                && signature::recover_signer(self.vm(), digest, endorsement).is_some_and(|root| {
+                    self.trusted_roots
+                        .get(self.vm().native_keccak256(root.as_slice()))
+                });
+        }
+        (payload, signed, true, Some(signer))
+    }
+
+    /// `check_attestation_signature` for a device-signed payload with no
+    /// trailing endorsement, if that payload carries a certificate chain.
+    fn check_chained_signature<'a>(
+        &self,
+        device_id: FixedBytes<32>,
+        attestation_data: &'a [u8],
+    ) -> Option<(&'a [u8], bool, bool, Option<Address>)> {
+        let split = attestation_data
+            .len()
+            .checked_sub(signature::SIGNATURE_LEN)?;
+        let (payload, sig) = attestation_data.split_at(split);
+        cbor::decode_attestation(payload)?.cert_chain?;
+        let digest = self.attestation_digest(device_id, payload);
+        let signer = signature::recover_signer(self.vm(), digest, sig)
+            .filter(|signer| self.is_device_signer(device_id, *signer))?;
+        Some((payload, true, true, Some(signer)))
+    }
+
+    /// Digest an intermediate CA's issuer signs to certify `ca`:
+    /// `personal_sign(keccak256("CERTID_CA" || contract || ca))`.
+    fn ca_digest(&self, ca: Address) -> B256 {
+        let mut message = Vec::with_capacity(9 + 20 + 20);
+        message.extend_from_slice(b"CERTID_CA");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(ca.as_slice());
+        let hash = self.vm().native_keccak256(&message);
+        signature::eth_signed_message_hash(self.vm(), hash)
+    }
+
+    /// Check a payload's certificate chain from `leaf`, the key the device
+    /// signature recovered to, up to a trusted root.
+    ///
+    /// `chain` is 1 to `MAX_CERT_CHAIN_LEN` 65-byte signatures, leaf
+    /// first: the first is an intermediate's over `endorsement_digest` of
+    /// the leaf (a root's, for a one-link chain), and each next one is the
+    /// previous signer's issuer over its `ca_digest`. The last signer must
+    /// be a trusted root.
+    fn check_cert_chain(
+        &self,
+        device_id: FixedBytes<32>,
+        leaf: Option<Address>,
+        chain: &[u8],
+    ) -> Result<(), VerifierError> {
+        let broken = || VerifierError::InvalidCertChain(InvalidCertChain {});
+        let links = chain.len() / signature::SIGNATURE_LEN;
+        if links == 0
+            || links > MAX_CERT_CHAIN_LEN
+            || !chain.len().is_multiple_of(signature::SIGNATURE_LEN)
+        {
+            return Err(broken());
+        }
+        let mut subject = leaf.ok_or_else(broken)?;
+        for (i, link) in chain.chunks(signature::SIGNATURE_LEN).enumerate() {
+            let digest = if i == 0 {
+                self.endorsement_digest(device_id, subject)
+            } else {
+                self.ca_digest(subject)
+            };
+            subject = signature::recover_signer(self.vm(), digest, link).ok_or_else(broken)?;
+        }
+        if !self
+            .trusted_roots
+            .get(self.vm().native_keccak256(subject.as_slice()))
+        {
+            return Err(broken());
+        }
+        Ok(())
+    }
+
+    /// Digest a device's current key signs to hand over to `new_key`:
+    /// `personal_sign(keccak256("CERTID_ROTATE" || contract || device_id ||
+    /// scheme || new_key || nonce))`, with `scheme` as one byte.
+    fn key_rotation_digest(
+        &self,
+        device_id: FixedBytes<32>,
+        scheme: u8,
+        new_key: FixedBytes<32>,
+        nonce: U256,
+    ) -> B256 {
+        let mut message = Vec::with_capacity(13 + 20 + 32 + 1 + 32 + 32);
+        message.extend_from_slice(b"CERTID_ROTATE");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.push(scheme);
+        message.extend_from_slice(new_key.as_slice());
+        message.extend_from_slice(&nonce.to_be_bytes::<32>());
+        let hash = self.vm().native_keccak256(&message);
+        signature::eth_signed_message_hash(self.vm(), hash)
+    }
+
+    /// Install a secp256k1 attestation key, keeping the old one verifying
+    /// for `device_key_grace` and leaving any other scheme.
+    fn install_device_key(&mut self, device_id: FixedBytes<32>, key: Address) {
+        let previous = self.device_attestation_keys.get(device_id);
+        if !previous.is_zero() && previous != key {
+            let now = U256::from(self.vm().block_timestamp());
+            self.device_previous_key.setter(device_id).set(previous);
+            self.device_key_rotated_at.setter(device_id).set(now);
+        }
+        self.device_attestation_keys.setter(device_id).set(key);
+        self.device_key_schemes.delete(device_id);
+        self.device_ed25519_keys.delete(device_id);
+        self.clear_memo(device_id);
+    }
+
+    /// Digest a device owner signs to authorize a delegated claim:
+    /// `personal_sign(keccak256("CERTID_CLAIM" || contract || device_id || to || nonce))`.
+    fn claim_digest(&self, device_id: FixedBytes<32>, to: Address, nonce: U256) -> B256 {
+        let mut message = Vec::with_capacity(12 + 20 + 32 + 20 + 32);
+        message.extend_from_slice(b"CERTID_CLAIM");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(to.as_slice());
+        message.extend_from_slice(&nonce.to_be_bytes::<32>());
+        let hash = self.vm().native_keccak256(&message);
+        signature::eth_signed_message_hash(self.vm(), hash)
+    }
+
+    /// Digest a relayer signs to authorize a registration:
+    /// `personal_sign(keccak256("CERTID_REGISTER" || contract || device_id || owner || nonce))`.
+    fn registration_digest(&self, device_id: FixedBytes<32>, owner: Address, nonce: U256) -> B256 {
+        let mut message = Vec::with_capacity(15 + 20 + 32 + 20 + 32);
+        message.extend_from_slice(b"CERTID_REGISTER");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(owner.as_slice());
+        message.extend_from_slice(&nonce.to_be_bytes::<32>());
+        let hash = self.vm().native_keccak256(&message);
+        signature::eth_signed_message_hash(self.vm(), hash)
+    }
+
+    fn restore_digest(&self, device_id: FixedBytes<32>, score: U256, nonce: U256) -> B256 {
+        let mut message = Vec::with_capacity(14 + 20 + 32 * 3);
+        message.extend_from_slice(b"CERTID_RESTORE");
+        message.extend_from_slice(self.vm().contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(&score.to_be_bytes::<32>());
+        message.extend_from_slice(&nonce.to_be_bytes::<32>());
+        let hash = self.vm().native_keccak256(&message);
+        signature::eth_signed_message_hash(self.vm(), hash)
+    }
+
+    fn is_device_registered(&self, device_id: FixedBytes<32>) -> bool {
+        self.get_flag(device_id, FLAG_REGISTERED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolEvent;
+    use k256::ecdsa::SigningKey;
+    use stylus_sdk::{alloy_primitives::keccak256, testing::*};
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32].into()).unwrap()
+    }
+
+    fn signer_address(key: &SigningKey) -> Address {
+        let point = key.verifying_key().to_encoded_point(false);
+        Address::from_slice(&keccak256(&point.as_bytes()[1..])[12..])
+    }
+
+    /// Sign `digest` and mock the `ecrecover` precompile to return the signer,
+    /// since the test VM has no precompiles.
+    fn sign(vm: &TestVM, key: &SigningKey, digest: B256) -> Vec<u8> {
+        let (sig, recovery_id) = key.sign_prehash_recoverable(digest.as_slice()).unwrap();
+        let mut bytes = sig.to_bytes().to_vec();
+        bytes.push(27 + recovery_id.to_byte());
+
+        let input = signature::ecrecover_input(digest, &bytes).unwrap();
+        let mut output = vec![0u8; 12];
+        output.extend_from_slice(signer_address(key).as_slice());
+        vm.mock_static_call(signature::ECRECOVER, input.to_vec(), Ok(output));
+        bytes
+    }
+
+    #[test]
+    fn test_register_and_verify() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+
+        let device_id = FixedBytes::<32>::ZERO;
+        let owner = Address::ZERO;
+
+        // Register device
+        contract.register_device(device_id, owner).unwrap();
+
+        // Set trust score to 92
+        contract
+            .update_trust_score(device_id, U256::from(92))
+            .unwrap();
+
+        // Verify attestation should succeed
+        let result = contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap();
+        assert!(result);
+
+        // Check counter incremented
+        assert_eq!(
+            contract.get_total_verifications().unwrap(),
+            U256::from(1)
+        );
+    }
+
+    #[test]
+    fn test_unregistered_device_fails() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+
+        let device_id = FixedBytes::<32>::ZERO;
+
+        // Verify without registration/score should fail
+        let result = contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap();
+        assert!(!result);
+
+        // Counter should not increment
+        assert_eq!(
+            contract.get_total_verifications().unwrap(),
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn test_register_rejects_duplicates() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        assert!(matches!(
+            contract.register_device(device_id, Address::repeat_byte(0x22)),
+            Err(VerifierError::DeviceAlreadyRegistered(_))
+        ));
+    }
+
+    #[test]
+    fn test_deregister_requires_device_owner() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let owner = Address::repeat_byte(0x11);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        // Default sender is not the device owner
+        assert!(matches!(
+            contract.deregister_device(device_id, false),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        vm.set_sender(owner);
+        contract.deregister_device(device_id, false).unwrap();
+        assert!(!contract.is_registered(device_id).unwrap());
+        assert_eq!(contract.get_device_trust(device_id).unwrap(), U256::ZERO);
+        assert!(matches!(
+            contract.deregister_device(device_id, false),
+            Err(VerifierError::DeviceNotRegistered(_))
+        ));
+    }
+
+    #[test]
+    fn test_registration_flag_matches_owner_map() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+
+        let owner = Address::repeat_byte(0x11);
+        vm.set_sender(owner);
+
+        let ids: Vec<FixedBytes<32>> = (0u16..300)
+            .map(|i| {
+                let mut id = [0u8; 32];
+                id[30..].copy_from_slice(&i.to_be_bytes());
+                FixedBytes::from(id)
+            })
+            .collect();
+
+        for id in &ids {
+            contract.register_device(*id, owner).unwrap();
+        }
+        for id in ids.iter().step_by(3) {
+            contract.deregister_device(*id, false).unwrap();
+        }
+        for id in ids.iter().step_by(6) {
+            contract.register_device(*id, owner).unwrap();
+        }
+
+        for id in &ids {
+            let in_owner_map = !contract.get_device_owner(*id).unwrap().is_zero();
+            assert_eq!(contract.is_registered(*id).unwrap(), in_owner_map);
+        }
+    }
+
+    #[test]
+    fn test_set_policy_round_trips_and_is_owner_only() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let policy = (
+            U256::from(60),
+            U256::from(3600),
+            U256::from(30),
+            U256::from(512),
+            U256::from(0b101),
+        );
+        contract
+            .set_policy(policy.0, policy.1, policy.2, policy.3, policy.4)
+            .unwrap();
+        assert_eq!(contract.get_policy().unwrap(), policy);
+
+        vm.set_sender(Address::repeat_byte(0x22));
+        assert!(matches!(
+            contract.set_policy(U256::ZERO, U256::ZERO, U256::ZERO, U256::ZERO, U256::ZERO),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        assert_eq!(contract.get_policy().unwrap(), policy);
+    }
+
+    #[test]
+    fn test_invalid_policy_field_causes_no_partial_update() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let policy = (
+            U256::from(50),
+            U256::from(600),
+            U256::from(10),
+            U256::from(256),
+            U256::from(1),
+        );
+        contract
+            .set_policy(policy.0, policy.1, policy.2, policy.3, policy.4)
+            .unwrap();
+
+        // Valid new values everywhere except an out-of-range length cap
+        let result = contract.set_policy(
+            U256::from(70),
+            U256::from(1200),
+            U256::from(20),
+            U256::from(MAX_ATTESTATION_LEN + 1),
+            U256::from(3),
+        );
+        assert!(matches!(result, Err(VerifierError::InvalidPolicy(_))));
+        assert_eq!(contract.get_policy().unwrap(), policy);
+
+        let result = contract.set_policy(
+            U256::from(MAX_TRUST_SCORE + 1),
+            U256::from(1200),
+            U256::from(20),
+            U256::from(128),
+            U256::from(3),
+        );
+        assert!(matches!(result, Err(VerifierError::InvalidPolicy(_))));
+        assert_eq!(contract.get_policy().unwrap(), policy);
+    }
+
+    #[test]
+    fn test_policy_gates_verification() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        vm.set_block_timestamp(1_000);
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(60))
+            .unwrap();
+
+        contract
+            .set_policy(
+                U256::from(70),
+                U256::from(100),
+                U256::from(10),
+                U256::from(4),
+                U256::ZERO,
+            )
+            .unwrap();
+
+        // Below threshold
+        assert!(!contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        // Oversized payload and too-soon retry both revert
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, vec![0u8; 5]),
+            Err(VerifierError::AttestationTooLarge(_))
+        ));
+        vm.set_block_timestamp(1_005);
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::VerificationTooFrequent(_))
+        ));
+
+        // Score older than the grace window no longer verifies
+        vm.set_block_timestamp(1_101);
+        assert!(!contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(1));
+    }
+
+    #[test]
+    fn test_rank_key_breaks_ties_by_registration_order() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+
+        let first = FixedBytes::<32>::repeat_byte(0x01);
+        let second = FixedBytes::<32>::repeat_byte(0x02);
+        let stronger = FixedBytes::<32>::repeat_byte(0x03);
+        let owner = Address::repeat_byte(0x11);
+        for id in [first, second, stronger] {
+            contract.register_device(id, owner).unwrap();
+        }
+        contract.update_trust_score(first, U256::from(75)).unwrap();
+        contract.update_trust_score(second, U256::from(75)).unwrap();
+        contract
+            .update_trust_score(stronger, U256::from(76))
+            .unwrap();
+
+        let first_key = contract.get_rank_key(first).unwrap();
+        let second_key = contract.get_rank_key(second).unwrap();
+        let stronger_key = contract.get_rank_key(stronger).unwrap();
+
+        // Equal scores: the earlier registration ranks higher
+        assert!(first_key > second_key);
+        assert_eq!(first_key >> 160, U256::from(75));
+        // A higher score wins regardless of registration order
+        assert!(stronger_key > first_key);
+
+        assert_eq!(
+            contract
+                .get_rank_key(FixedBytes::<32>::repeat_byte(0x04))
+                .unwrap(),
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn test_restore_score_with_relayer_signature() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let relayer = signing_key(0x42);
+        contract.add_relayer(signer_address(&relayer)).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let owner = Address::repeat_byte(0x11);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(5))
+            .unwrap();
+        contract
+            .set_policy(
+                U256::from(50),
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+            )
+            .unwrap();
+        assert!(!contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        assert_eq!(
+            contract.get_failure_count(device_id).unwrap(),
+            U256::from(1)
+        );
+
+        let score = U256::from(90);
+        let digest = contract.restore_digest(device_id, score, U256::ZERO);
+        let signature = sign(&vm, &relayer, digest);
+
+        vm.set_sender(owner);
+        contract
+            .restore_score(device_id, score, signature.clone())
+            .unwrap();
+        assert_eq!(contract.get_device_trust(device_id).unwrap(), score);
+        assert_eq!(contract.get_failure_count(device_id).unwrap(), U256::ZERO);
+        assert_eq!(
+            contract.get_restore_nonce(device_id).unwrap(),
+            U256::from(1)
+        );
+
+        // The nonce moved on, so the same signature no longer verifies
+        assert!(matches!(
+            contract.restore_score(device_id, score, signature),
+            Err(VerifierError::BadSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_restore_score_rejects_forged_signatures() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let relayer = signing_key(0x42);
+        let impostor = signing_key(0x43);
+        contract.add_relayer(signer_address(&relayer)).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let owner = Address::repeat_byte(0x11);
+        contract.register_device(device_id, owner).unwrap();
+        vm.set_sender(owner);
+
+        // Signed by a key that is not an authorized relayer
+        let score = U256::from(90);
+        let digest = contract.restore_digest(device_id, score, U256::ZERO);
+        let forged = sign(&vm, &impostor, digest);
+        assert!(matches!(
+            contract.restore_score(device_id, score, forged),
+            Err(VerifierError::BadSignature(_))
+        ));
+
+        // A relayer signature for a different score does not carry over
+        let signature = sign(&vm, &relayer, digest);
+        assert!(matches!(
+            contract.restore_score(device_id, U256::from(100), signature.clone()),
+            Err(VerifierError::BadSignature(_))
+        ));
+
+        // Only the device owner may petition
+        vm.set_sender(Address::repeat_byte(0x22));
+        assert!(matches!(
+            contract.restore_score(device_id, score, signature),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        assert_eq!(contract.get_device_trust(device_id).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_trusted_root_enumeration_tracks_adds_and_removes() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let roots: Vec<FixedBytes<32>> = (1u8..=5).map(FixedBytes::repeat_byte).collect();
+        for root in &roots {
+            contract.add_trusted_root(*root).unwrap();
+        }
+        // Duplicate add is a no-op
+        contract.add_trusted_root(roots[0]).unwrap();
+        assert_eq!(contract.get_trusted_root_count().unwrap(), U256::from(5));
+
+        // Remove from the middle, the end, and the front
+        contract.remove_trusted_root(roots[1]).unwrap();
+        contract.remove_trusted_root(roots[4]).unwrap();
+        contract.remove_trusted_root(roots[0]).unwrap();
+        // Removing an absent root is a no-op
+        contract.remove_trusted_root(roots[1]).unwrap();
+
+        let count = contract.get_trusted_root_count().unwrap();
+        assert_eq!(count, U256::from(2));
+        let mut listed: Vec<FixedBytes<32>> = (0..2)
+            .map(|i| contract.get_trusted_root_at(U256::from(i)).unwrap())
+            .collect();
+        listed.sort();
+        assert_eq!(listed, vec![roots[2], roots[3]]);
+        for root in &roots {
+            let listed = listed.contains(root);
+            assert_eq!(contract.is_trusted_root(*root).unwrap(), listed);
+        }
+        assert!(matches!(
+            contract.get_trusted_root_at(count),
+            Err(VerifierError::IndexOutOfBounds(_))
+        ));
+
+        // Re-adding after removal appends again
+        contract.add_trusted_root(roots[0]).unwrap();
+        assert_eq!(
+            contract.get_trusted_root_at(U256::from(2)).unwrap(),
+            roots[0]
+        );
+    }
+
+    #[test]
+    fn test_verify_decodes_cbor_and_enforces_required_claims() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        contract
+            .set_policy(
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                U256::from(0b11),
+            )
+            .unwrap();
+
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1_700_000_000, 0b111);
+        assert_eq!(
+            contract.parse_attestation(payload.clone()).unwrap(),
+            (
+                FixedBytes::repeat_byte(0xab),
+                1_700_000_000,
+                U256::from(0b111)
+            )
+        );
+        assert!(contract.verify_tee_attestation(device_id, payload).unwrap());
+
+        // Missing a required claim
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1_700_000_000, 0b01);
+        assert!(!contract.verify_tee_attestation(device_id, payload).unwrap());
+
+        // Truncated CBOR reverts instead of failing softly
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1_700_000_000, 0b11);
+        let truncated = payload[..payload.len() - 1].to_vec();
+        assert!(matches!(
+            contract.parse_attestation(truncated.clone()),
+            Err(VerifierError::MalformedAttestation(_))
+        ));
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, truncated),
+            Err(VerifierError::MalformedAttestation(_))
+        ));
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(1));
+    }
+
+    #[test]
+    fn test_verification_kill_switch_is_independent_of_pause() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        assert!(contract.is_verification_enabled().unwrap());
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let owner = Address::repeat_byte(0x11);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+
+        // Kill switch: verification reverts, registration and scoring continue
+        contract.set_verification_enabled(false).unwrap();
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::VerificationDisabled(_))
+        ));
+        let other = FixedBytes::<32>::repeat_byte(0x02);
+        contract.register_device(other, owner).unwrap();
+        contract.update_trust_score(other, U256::from(80)).unwrap();
+        assert!(!contract.paused().unwrap());
+
+        // Re-enabling verification restores it
+        contract.set_verification_enabled(true).unwrap();
+        assert!(contract.verify_tee_attestation(other, Vec::new()).unwrap());
+
+        // Global pause halts everything, independently of the kill switch
+        contract.pause().unwrap();
+        assert!(contract.is_verification_enabled().unwrap());
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::ContractPaused(_))
+        ));
+        assert!(matches!(
+            contract.update_trust_score(device_id, U256::from(10)),
+            Err(VerifierError::ContractPaused(_))
+        ));
+        assert!(matches!(
+            contract.register_device(FixedBytes::<32>::repeat_byte(0x03), owner),
+            Err(VerifierError::ContractPaused(_))
+        ));
+
+        contract.unpause().unwrap();
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.set_verification_enabled(false),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            contract.pause(),
+            Err(VerifierError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_device_metadata_set_get_cap_and_clear() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let relayer = Address::repeat_byte(0x33);
+        contract.add_relayer(relayer).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let owner = Address::repeat_byte(0x11);
+        contract.register_device(device_id, owner).unwrap();
+
+        // Device owner and relayer may both write
+        vm.set_sender(owner);
+        contract
+            .set_device_metadata(device_id, b"fw=1.2.0".to_vec())
+            .unwrap();
+        assert_eq!(
+            contract.get_device_metadata(device_id).unwrap(),
+            b"fw=1.2.0".to_vec()
+        );
+        vm.set_sender(relayer);
+        contract
+            .set_device_metadata(device_id, b"fw=1.3.0;region=eu".to_vec())
+            .unwrap();
+        assert_eq!(
+            contract.get_device_metadata(device_id).unwrap(),
+            b"fw=1.3.0;region=eu".to_vec()
+        );
+
+        // Length cap
+        contract
+            .set_device_metadata(device_id, vec![0xaa; MAX_METADATA_LEN])
+            .unwrap();
+        assert!(matches!(
+            contract.set_device_metadata(device_id, vec![0xaa; MAX_METADATA_LEN + 1]),
+            Err(VerifierError::MetadataTooLarge(_))
+        ));
+
+        // Anyone else is rejected
+        vm.set_sender(Address::repeat_byte(0x44));
+        assert!(matches!(
+            contract.set_device_metadata(device_id, Vec::new()),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        // Deregistration clears it
+        vm.set_sender(owner);
+        contract.deregister_device(device_id, false).unwrap();
+        assert!(contract.get_device_metadata(device_id).unwrap().is_empty());
+        assert!(matches!(
+            contract.set_device_metadata(device_id, Vec::new()),
+            Err(VerifierError::DeviceNotRegistered(_))
+        ));
+    }
+
+    fn bridge_message(
+        vm: &TestVM,
+        device_id: FixedBytes<32>,
+        score: u64,
+        seq: u64,
+        expiry: u64,
+    ) -> Vec<u8> {
+        let mut message = bridge::BRIDGE_MESSAGE_TAG.to_vec();
+        message.extend_from_slice(&U256::from(vm.chain_id()).to_be_bytes::<32>());
+        message.extend_from_slice(vm.contract_address().as_slice());
+        message.extend_from_slice(device_id.as_slice());
+        message.extend_from_slice(&U256::from(score).to_be_bytes::<32>());
+        message.extend_from_slice(&seq.to_be_bytes());
+        message.extend_from_slice(&expiry.to_be_bytes());
+        message
+    }
+
+    fn sign_bridge_message(vm: &TestVM, key: &SigningKey, message: &[u8]) -> Vec<u8> {
+        let digest = signature::eth_signed_message_hash(vm, keccak256(message));
+        sign(vm, key, digest)
+    }
+
+    #[test]
+    fn test_apply_bridge_message_updates_score() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+        vm.set_block_timestamp(1_000);
+
+        let bridge_key = signing_key(0x42);
+        contract
+            .add_bridge_key(signer_address(&bridge_key))
+            .unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+
+        let message = bridge_message(&vm, device_id, 88, 1, 2_000);
+        assert_eq!(message.len(), bridge::BRIDGE_MESSAGE_LEN);
+        let signature = sign_bridge_message(&vm, &bridge_key, &message);
+
+        // Any account may submit a correctly signed message
+        vm.set_sender(Address::repeat_byte(0x55));
+        contract
+            .apply_bridge_message(message.clone(), signature.clone())
+            .unwrap();
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(88)
+        );
+        assert_eq!(contract.get_bridge_seq(device_id).unwrap(), U256::from(1));
+
+        // Replaying the same message is rejected by the sequence check
+        assert!(matches!(
+            contract.apply_bridge_message(message, signature),
+            Err(VerifierError::StaleSequence(_))
+        ));
+
+        // Expired messages are rejected
+        let message = bridge_message(&vm, device_id, 70, 2, 999);
+        let signature = sign_bridge_message(&vm, &bridge_key, &message);
+        assert!(matches!(
+            contract.apply_bridge_message(message, signature),
+            Err(VerifierError::MessageExpired(_))
+        ));
+
+        // A configured validator quorum rules out single-key messages
+        vm.set_sender(admin);
+        contract
+            .set_validator_set(vec![Address::repeat_byte(0x31)], U256::from(1))
+            .unwrap();
+        let message = bridge_message(&vm, device_id, 70, 2, 2_000);
+        let signature = sign_bridge_message(&vm, &bridge_key, &message);
+        assert!(matches!(
+            contract.apply_bridge_message(message, signature),
+            Err(VerifierError::ConsensusRequired(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_bridge_message_rejects_tampered_fields() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        vm.set_block_timestamp(1_000);
+
+        let bridge_key = signing_key(0x42);
+        contract
+            .add_bridge_key(signer_address(&bridge_key))
+            .unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+
+        let message = bridge_message(&vm, device_id, 40, 1, 2_000);
+        let signature = sign_bridge_message(&vm, &bridge_key, &message);
+
+        // Flip the score, seq, and expiry in turn
+        for offset in [128, 136, 144] {
+            let mut tampered = message.clone();
+            tampered[offset] ^= 0x01;
+            assert!(matches!(
+                contract.apply_bridge_message(tampered, signature.clone()),
+                Err(VerifierError::BadSignature(_))
+            ));
+        }
+
+        // Wrong length
+        assert!(matches!(
+            contract.apply_bridge_message(message[..144].to_vec(), signature.clone()),
+            Err(VerifierError::MalformedBridgeMessage(_))
+        ));
+
+        // Correctly signed, but addressed to another chain or verifier
+        for (offset, field) in [(44, "chain id"), (64, "verifier")] {
+            let mut foreign = message.clone();
+            foreign[offset] ^= 0x01;
+            let signature = sign_bridge_message(&vm, &bridge_key, &foreign);
+            assert!(
+                matches!(
+                    contract.apply_bridge_message(foreign, signature),
+                    Err(VerifierError::MalformedBridgeMessage(_))
+                ),
+                "{field}"
+            );
+        }
+
+        // Signed by a key that is not a bridge key
+        let forged = sign_bridge_message(&vm, &signing_key(0x43), &message);
+        assert!(matches!(
+            contract.apply_bridge_message(message, forged),
+            Err(VerifierError::BadSignature(_))
+        ));
+        assert_eq!(contract.get_device_trust(device_id).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_reward_mint_failure_accrues_pending_reward() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let token = Address::repeat_byte(0x77);
+        let amount = U256::from(5);
+        contract.set_reward_config(token, amount).unwrap();
+        let config_logs = vm.get_emitted_logs().len();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let owner = Address::repeat_byte(0x11);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+
+        // The token reverts (e.g. paused): verification still counts
+        let mint = interfaces::IRewardToken::mintCall { to: owner, amount }.abi_encode();
+        vm.mock_call(token, mint.clone(), Err(Vec::new()));
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(2));
+        assert_eq!(
+            contract.get_pending_reward(device_id).unwrap(),
+            U256::from(10)
+        );
+
+        let logs = &vm.get_emitted_logs()[config_logs..];
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].0[0], RewardFailed::SIGNATURE_HASH);
+        assert_eq!(logs[0].0[1], device_id);
+
+        // Claiming while the token still reverts keeps the balance
+        vm.set_sender(owner);
+        let claim = interfaces::IRewardToken::mintCall {
+            to: owner,
+            amount: U256::from(10),
+        }
+        .abi_encode();
+        vm.mock_call(token, claim.clone(), Err(Vec::new()));
+        assert!(matches!(
+            contract.claim_reward(device_id),
+            Err(VerifierError::RewardMintFailed(_))
+        ));
+
+        // Once the token recovers the claim succeeds
+        vm.mock_call(token, claim, Ok(Vec::new()));
+        assert_eq!(contract.claim_reward(device_id).unwrap(), U256::from(10));
+        assert_eq!(contract.get_pending_reward(device_id).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_uptime_tracks_block_time_since_initialize() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+
+        vm.set_block_timestamp(1_000);
+        assert_eq!(contract.get_deployed_at().unwrap(), U256::ZERO);
+        assert_eq!(contract.uptime_seconds().unwrap(), U256::ZERO);
+
+        contract.initialize().unwrap();
+        assert_eq!(contract.get_deployed_at().unwrap(), U256::from(1_000));
+        assert_eq!(contract.uptime_seconds().unwrap(), U256::ZERO);
+
+        vm.set_block_timestamp(1_250);
+        assert_eq!(contract.uptime_seconds().unwrap(), U256::from(250));
+        vm.set_block_timestamp(4_600);
+        assert_eq!(contract.uptime_seconds().unwrap(), U256::from(3_600));
+        assert_eq!(contract.get_deployed_at().unwrap(), U256::from(1_000));
+    }
+
+    #[test]
+    fn test_device_classes_verify_under_their_own_thresholds() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let (sensors, gateways) = (U256::from(1), U256::from(2));
+        contract
+            .set_policy(
+                U256::from(50),
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+            )
+            .unwrap();
+        contract
+            .set_class_policy(sensors, U256::from(30), U256::ZERO)
+            .unwrap();
+        contract
+            .set_class_policy(gateways, U256::from(90), U256::from(60))
+            .unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let sensor = FixedBytes::<32>::repeat_byte(0x01);
+        let gateway = FixedBytes::<32>::repeat_byte(0x02);
+        let phone = FixedBytes::<32>::repeat_byte(0x03);
+        contract
+            .register_device_with_class(sensor, owner, sensors)
+            .unwrap();
+        contract
+            .register_device_with_class(gateway, owner, gateways)
+            .unwrap();
+        contract.register_device(phone, owner).unwrap();
+        assert_eq!(contract.get_device_class(gateway).unwrap(), gateways);
+        assert_eq!(contract.get_device_class(phone).unwrap(), U256::ZERO);
+
+        // The same score passes for a sensor and the global default, not a gateway
+        for device in [sensor, gateway, phone] {
+            contract.update_trust_score(device, U256::from(70)).unwrap();
+        }
+        assert!(contract.verify_tee_attestation(sensor, Vec::new()).unwrap());
+        assert!(!contract
+            .verify_tee_attestation(gateway, Vec::new())
+            .unwrap());
+        assert!(contract.verify_tee_attestation(phone, Vec::new()).unwrap());
+
+        // Gateways also get their own rate limit
+        contract
+            .update_trust_score(gateway, U256::from(95))
+            .unwrap();
+        vm.set_block_timestamp(vm.block_timestamp() + 1);
+        assert!(contract
+            .verify_tee_attestation(gateway, Vec::new())
+            .unwrap());
+        assert!(matches!(
+            contract.verify_tee_attestation(gateway, Vec::new()),
+            Err(VerifierError::VerificationTooFrequent(_))
+        ));
+        assert_eq!(
+            contract.get_class_policy(gateways).unwrap(),
+            (U256::from(90), U256::from(60))
+        );
+        assert_eq!(
+            contract.get_class_policy(U256::from(7)).unwrap(),
+            (U256::from(50), U256::ZERO)
+        );
+
+        // Class 0 is reserved for the global policy
+        assert!(matches!(
+            contract.set_class_policy(U256::ZERO, U256::from(10), U256::ZERO),
+            Err(VerifierError::InvalidPolicy(_))
+        ));
+    }
+
+    #[test]
+    fn test_revoke_devices_batch_revokes_and_skips_duplicates() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<FixedBytes<32>> = (1..=3).map(FixedBytes::<32>::repeat_byte).collect();
+        for id in &ids {
+            contract.register_device(*id, owner).unwrap();
+            contract.update_trust_score(*id, U256::from(80)).unwrap();
+        }
+
+        contract.revoke_devices_batch(vec![ids[0], ids[1]]).unwrap();
+        // ids[1] is already revoked and must not emit a second event
+        contract.revoke_devices_batch(vec![ids[1], ids[2]]).unwrap();
+
+        let logs = vm.get_emitted_logs();
+        assert_eq!(logs.len(), 3);
+        for (log, id) in logs.iter().zip(&ids) {
+            assert_eq!(log.0[0], DeviceRevoked::SIGNATURE_HASH);
+            assert_eq!(log.0[1], *id);
+        }
+        for id in &ids {
+            assert!(contract.is_revoked(*id).unwrap());
+            assert!(!contract.verify_tee_attestation(*id, Vec::new()).unwrap());
+        }
+
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.revoke_devices_batch(Vec::new()),
+            Err(VerifierError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_revoke_devices_batch_enforces_cap() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let id = FixedBytes::<32>::repeat_byte(0x01);
+        assert!(matches!(
+            contract.revoke_devices_batch(vec![id; MAX_REVOKE_BATCH + 1]),
+            Err(VerifierError::BatchTooLarge(_))
+        ));
+        assert!(!contract.is_revoked(id).unwrap());
+
+        contract
+            .revoke_devices_batch(vec![id; MAX_REVOKE_BATCH])
+            .unwrap();
+        assert!(contract.is_revoked(id).unwrap());
+        assert_eq!(vm.get_emitted_logs().len(), 1);
+    }
+
+    /// Append a signature from `key` over the device's attestation digest.
+    fn sign_attestation(
+        vm: &TestVM,
+        contract: &CertIDVerifier,
+        key: &SigningKey,
+        device_id: FixedBytes<32>,
+        payload: Vec<u8>,
+    ) -> Vec<u8> {
+        let digest = contract.attestation_digest(device_id, &payload);
+        let mut data = payload;
+        data.extend(sign(vm, key, digest));
+        data
+    }
+
+    #[test]
+    fn test_key_commitment_verifies_like_raw_key() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_key = signing_key(0x41);
+        let other_key = signing_key(0x42);
+        let commitment = keccak256(signer_address(&device_key));
+
+        // Same key, registered once in the clear and once as a commitment
+        let raw = FixedBytes::<32>::repeat_byte(0x01);
+        let committed = FixedBytes::<32>::repeat_byte(0x02);
+        contract.register_device(raw, owner).unwrap();
+        vm.set_sender(owner);
+        contract
+            .set_device_attestation_key(raw, signer_address(&device_key))
+            .unwrap();
+        contract
+            .register_device_with_commitment(committed, owner, commitment)
+            .unwrap();
+
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+        for device_id in [raw, committed] {
+            vm.set_sender(admin);
+            contract
+                .update_trust_score(device_id, U256::from(80))
+                .unwrap();
+
+            // Unsigned and wrongly signed attestations fail
+            assert!(!contract
+                .verify_tee_attestation(device_id, payload.clone())
+                .unwrap());
+            let forged = sign_attestation(&vm, &contract, &other_key, device_id, payload.clone());
+            assert!(!contract.verify_tee_attestation(device_id, forged).unwrap());
+
+            let signed = sign_attestation(&vm, &contract, &device_key, device_id, payload.clone());
+            assert!(contract.verify_tee_attestation(device_id, signed).unwrap());
+            assert_eq!(contract.get_failure_count(device_id).unwrap(), U256::ZERO);
+        }
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(2));
+
+        // A signature for one device does not verify for another
+        let signed = sign_attestation(&vm, &contract, &device_key, raw, payload);
+        assert!(!contract.verify_tee_attestation(committed, signed).unwrap());
+    }
+
+    #[test]
+    fn test_empty_payload_rejected_for_keyed_devices() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+
+        let owner = Address::repeat_byte(0x11);
+        let (keyed, keyless) = (
+            FixedBytes::<32>::repeat_byte(0x01),
+            FixedBytes::<32>::repeat_byte(0x02),
+        );
+        for device_id in [keyed, keyless] {
+            contract.register_device(device_id, owner).unwrap();
+            contract
+                .update_trust_score(device_id, U256::from(80))
+                .unwrap();
+        }
+        vm.set_sender(owner);
+        contract
+            .set_device_attestation_key(keyed, signer_address(&signing_key(0x41)))
+            .unwrap();
+
+        // A keyed device's empty payload is malformed, not a failed check
+        assert!(matches!(
+            contract.verify_tee_attestation(keyed, Vec::new()),
+            Err(VerifierError::MalformedAttestation(_))
+        ));
+        assert!(matches!(
+            contract.verify_tee_attestation_strict(keyed, Vec::new()),
+            Err(VerifierError::MalformedAttestation(_))
+        ));
+        assert_eq!(contract.get_failure_count(keyed).unwrap(), U256::ZERO);
+
+        // Keyless pilot devices keep the empty path until it is turned off
+        assert!(contract.is_empty_payload_allowed().unwrap());
+        assert!(contract
+            .verify_tee_attestation(keyless, Vec::new())
+            .unwrap());
+        vm.set_sender(admin);
+        contract.set_allow_empty_payload(false).unwrap();
+        assert!(matches!(
+            contract.verify_tee_attestation(keyless, Vec::new()),
+            Err(VerifierError::MalformedAttestation(_))
+        ));
+    }
+
+    #[test]
+    fn test_rescaling_rounds_by_mode() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.set_input_scale(U256::from(1000)).unwrap();
+        let rescaled = |contract: &mut CertIDVerifier, raw: u64| {
+            contract
+                .update_trust_score(device_id, U256::from(raw))
+                .unwrap();
+            contract.get_device_trust(device_id).unwrap()
+        };
+
+        // Floor is the default
+        assert_eq!(rescaled(&mut contract, 875), U256::from(87));
+        assert_eq!(rescaled(&mut contract, 874), U256::from(87));
+        assert_eq!(rescaled(&mut contract, 1000), U256::from(100));
+
+        contract
+            .set_rounding_mode(U256::from(ROUND_NEAREST))
+            .unwrap();
+        assert_eq!(rescaled(&mut contract, 875), U256::from(88));
+        assert_eq!(rescaled(&mut contract, 874), U256::from(87));
+        assert_eq!(rescaled(&mut contract, 4), U256::ZERO);
+        assert_eq!(rescaled(&mut contract, 5), U256::from(1));
+        assert_eq!(
+            contract.get_score_scaling().unwrap(),
+            (U256::from(1000), U256::from(ROUND_NEAREST))
+        );
+
+        assert!(matches!(
+            contract.update_trust_score(device_id, U256::from(1001)),
+            Err(VerifierError::InvalidScore(_))
+        ));
+        assert!(matches!(
+            contract.set_rounding_mode(U256::from(2)),
+            Err(VerifierError::InvalidPolicy(_))
+        ));
+    }
+
+    #[test]
+    fn test_relayer_silence_freezes_verification_until_heartbeat() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let relayer = Address::repeat_byte(0x33);
+        contract.add_relayer(relayer).unwrap();
+        vm.set_block_timestamp(1_000);
+        contract.set_max_relayer_silence(U256::from(600)).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        vm.set_block_timestamp(1_600);
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        // Past the window: frozen, and only a relayer can revive it
+        vm.set_block_timestamp(1_601);
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::RelayerSilent(_))
+        ));
+        assert!(matches!(
+            contract.relayer_heartbeat(),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        vm.set_sender(relayer);
+        contract.relayer_heartbeat().unwrap();
+        assert_eq!(
+            contract.get_relayer_liveness().unwrap(),
+            (U256::from(1_601), U256::from(600))
+        );
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        // A relayer's score update also counts as a heartbeat
+        vm.set_block_timestamp(2_500);
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        vm.set_block_timestamp(3_000);
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+    }
+
+    #[test]
+    fn test_set_device_signers_replaces_set_atomically() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        vm.set_sender(owner);
+
+        let (a, b, c) = (
+            Address::repeat_byte(0xa1),
+            Address::repeat_byte(0xb2),
+            Address::repeat_byte(0xc3),
+        );
+        contract
+            .set_device_signers(device_id, vec![a, b, c], U256::from(2))
+            .unwrap();
+        assert_eq!(
+            contract.get_device_signers(device_id).unwrap(),
+            (vec![a, b, c], U256::from(2))
+        );
+
+        // A smaller replacement drops the old entries entirely
+        contract
+            .set_device_signers(device_id, vec![c], U256::from(1))
+            .unwrap();
+        assert_eq!(
+            contract.get_device_signers(device_id).unwrap(),
+            (vec![c], U256::from(1))
+        );
+    }
+
+    #[test]
+    fn test_set_device_signers_rejects_invalid_sets() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+
+        let (a, b) = (Address::repeat_byte(0xa1), Address::repeat_byte(0xb2));
+        assert!(matches!(
+            contract.set_device_signers(device_id, vec![a], U256::from(1)),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        vm.set_sender(owner);
+        contract
+            .set_device_signers(device_id, vec![a, b], U256::from(1))
+            .unwrap();
+        let rejected = [
+            (vec![a, b], U256::from(3)),
+            (vec![a, b], U256::ZERO),
+            (vec![a, b, a], U256::from(2)),
+            (vec![a, Address::ZERO], U256::from(1)),
+        ];
+        for (signers, threshold) in rejected {
+            assert!(matches!(
+                contract.set_device_signers(device_id, signers, threshold),
+                Err(VerifierError::InvalidThreshold(_) | VerifierError::InvalidSigner(_))
+            ));
+        }
+        // Failed calls leave the previous set in place
+        assert_eq!(
+            contract.get_device_signers(device_id).unwrap(),
+            (vec![a, b], U256::from(1))
+        );
+    }
+
+    #[test]
+    fn test_state_proofs_follow_hash_algo() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let leaves = [1, 2, 3, 4].map(B256::repeat_byte);
+        let (sha_root, proof) = merkle::tests::four_leaf_tree(&vm, merkle::HASH_SHA256, leaves);
+        contract.set_state_root(sha_root).unwrap();
+
+        // A sha256 tree does not verify until the algorithm matches
+        assert!(!contract
+            .verify_state_proof(leaves[0], proof.clone())
+            .unwrap());
+        contract
+            .set_merkle_hash_algo(U256::from(merkle::HASH_SHA256))
+            .unwrap();
+        assert!(contract
+            .verify_state_proof(leaves[0], proof.clone())
+            .unwrap());
+        assert!(!contract.verify_state_proof(leaves[3], proof).unwrap());
+
+        assert!(matches!(
+            contract.set_merkle_hash_algo(U256::from(2)),
+            Err(VerifierError::InvalidPolicy(_))
+        ));
+        vm.set_sender(Address::repeat_byte(0x22));
+        assert!(matches!(
+            contract.set_state_root(B256::ZERO),
+            Err(VerifierError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_verification_log_wraps_at_capacity() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract
+            .set_verification_log_capacity(U256::from(3))
+            .unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<FixedBytes<32>> = (1..=5).map(FixedBytes::<32>::repeat_byte).collect();
+        for (i, id) in ids.iter().enumerate() {
+            contract.register_device(*id, owner).unwrap();
+            contract.update_trust_score(*id, U256::from(80)).unwrap();
+            vm.set_block_timestamp(100 + i as u64);
+            assert!(contract.verify_tee_attestation(*id, Vec::new()).unwrap());
+            assert_eq!(
+                contract.get_verification_log_count().unwrap(),
+                U256::from((i + 1).min(3))
+            );
+        }
+
+        // Only the newest three remain, oldest first
+        for (index, i) in (2..5).enumerate() {
+            assert_eq!(
+                contract.get_verification_log_at(U256::from(index)).unwrap(),
+                (ids[i], U256::from(100 + i))
+            );
+        }
+        assert!(matches!(
+            contract.get_verification_log_at(U256::from(3)),
+            Err(VerifierError::IndexOutOfBounds(_))
+        ));
+
+        // Resizing starts a fresh log
+        contract.set_verification_log_capacity(U256::ZERO).unwrap();
+        assert_eq!(contract.get_verification_log_count().unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_onchain_challenge_required_when_enabled() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        // Off: the stateless path accepts any well-formed payload
+        let stateless = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+        assert!(contract
+            .verify_tee_attestation(device_id, stateless.clone())
+            .unwrap());
+
+        contract.set_require_onchain_challenge(true).unwrap();
+        assert!(contract.is_onchain_challenge_required().unwrap());
+        assert!(!contract
+            .verify_tee_attestation(device_id, stateless)
+            .unwrap());
+        assert!(!contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        vm.set_sender(owner);
+        let challenge = contract.issue_challenge(device_id).unwrap();
+        assert_eq!(
+            contract.get_outstanding_challenge(device_id).unwrap(),
+            challenge
+        );
+        let answer = cbor::tests::encode_attestation(challenge.0, 2, 0);
+        assert!(contract
+            .verify_tee_attestation(device_id, answer.clone())
+            .unwrap());
+
+        // The challenge is consumed, so the same answer cannot be replayed
+        assert_eq!(
+            contract.get_outstanding_challenge(device_id).unwrap(),
+            FixedBytes::ZERO
+        );
+        assert!(!contract.verify_tee_attestation(device_id, answer).unwrap());
+
+        // Each challenge is distinct
+        let next = contract.issue_challenge(device_id).unwrap();
+        assert_ne!(next, challenge);
+    }
+
+    #[test]
+    fn test_scan_problem_devices_returns_only_problems_in_page() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<FixedBytes<32>> = (1..=6).map(FixedBytes::<32>::repeat_byte).collect();
+        vm.set_block_timestamp(1_000);
+        for id in &ids {
+            contract.register_device(*id, owner).unwrap();
+        }
+        // ids[0], ids[3]: low trust; ids[4]: stale; the rest healthy
+        for (id, score) in ids.iter().zip([10, 90, 90, 20, 90, 90]) {
+            contract.update_trust_score(*id, U256::from(score)).unwrap();
+        }
+        vm.set_block_timestamp(5_000);
+        for id in [ids[0], ids[1], ids[2], ids[3], ids[5]] {
+            let score = contract.get_device_trust(id).unwrap();
+            contract.update_trust_score(id, score).unwrap();
+        }
+        assert_eq!(contract.get_device_count().unwrap(), U256::from(6));
+        assert_eq!(contract.get_device_at(U256::from(4)).unwrap(), ids[4]);
+
+        let scan = |start: u64, limit: u64| {
+            contract
+                .scan_problem_devices(
+                    U256::from(start),
+                    U256::from(limit),
+                    U256::from(50),
+                    U256::from(3_600),
+                )
+                .unwrap()
+        };
+        assert_eq!(scan(0, 6), (vec![ids[0], ids[3], ids[4]], U256::ZERO));
+        assert_eq!(scan(1, 3), (vec![ids[3]], U256::from(4)));
+        assert_eq!(scan(4, 100), (vec![ids[4]], U256::ZERO));
+        assert_eq!(scan(6, 10), (Vec::new(), U256::ZERO));
+
+        // Deregistered devices leave the enumeration
+        vm.set_sender(owner);
+        contract.deregister_device(ids[0], false).unwrap();
+        assert_eq!(contract.get_device_count().unwrap(), U256::from(5));
+        assert_eq!(contract.get_device_at(U256::ZERO).unwrap(), ids[5]);
+        assert!(matches!(
+            contract.get_device_at(U256::from(5)),
+            Err(VerifierError::IndexOutOfBounds(_))
+        ));
+    }
+
+    #[test]
+    fn test_erc20_registration_deposit_and_refund() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let token = Address::repeat_byte(0x55);
+        let deposit = U256::from(1_000);
+        contract.set_registration_deposit(token, deposit).unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        vm.set_sender(owner);
+        let pull = interfaces::IERC20::transferFromCall {
+            from: owner,
+            to: vm.contract_address(),
+            amount: deposit,
+        }
+        .abi_encode();
+        let refund = interfaces::IERC20::transferCall {
+            to: owner,
+            amount: deposit,
+        }
+        .abi_encode();
+        let success = U256::from(1).to_be_bytes::<32>().to_vec();
+
+        // A token returning false blocks registration
+        vm.mock_call(token, pull.clone(), Ok(vec![0u8; 32]));
+        assert!(matches!(
+            contract.register_device(FixedBytes::repeat_byte(0x0f), owner),
+            Err(VerifierError::DepositTransferFailed(_))
+        ));
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        vm.mock_call(token, pull, Ok(success.clone()));
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .register_device(FixedBytes::repeat_byte(0x02), owner)
+            .unwrap();
+
+        vm.mock_call(token, refund.clone(), Ok(success));
+        contract.deregister_device(device_id, false).unwrap();
+        assert!(!contract.is_registered(device_id).unwrap());
+
+        // A reverting refund reverts the deregistration
+        vm.mock_call(token, refund, Err(Vec::new()));
+        assert!(matches!(
+            contract.deregister_device(FixedBytes::repeat_byte(0x02), false),
+            Err(VerifierError::DepositTransferFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_eth_registration_deposit_and_refund() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let deposit = U256::from(5_000);
+        contract
+            .set_registration_deposit(Address::ZERO, deposit)
+            .unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        vm.set_sender(owner);
+        vm.set_value(U256::from(10));
+        assert!(matches!(
+            contract.register_device(FixedBytes::repeat_byte(0x0f), owner),
+            Err(VerifierError::IncorrectDeposit(_))
+        ));
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        vm.set_value(deposit);
+        vm.set_balance(vm.contract_address(), deposit);
+        contract.register_device(device_id, owner).unwrap();
+
+        vm.set_value(U256::ZERO);
+        contract.deregister_device(device_id, false).unwrap();
+        assert_eq!(vm.balance(owner), deposit);
+        assert_eq!(vm.balance(vm.contract_address()), U256::ZERO);
+    }
+
+    #[test]
+    fn test_decommissioned_contract_points_to_successor() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        assert_eq!(contract.get_successor().unwrap(), Address::ZERO);
+
+        let successor = Address::repeat_byte(0x99);
+        contract.set_successor(successor).unwrap();
+        assert_eq!(contract.get_successor().unwrap(), successor);
+
+        for result in [
+            contract.register_device(FixedBytes::repeat_byte(0x02), owner),
+            contract.update_trust_score(device_id, U256::from(80)),
+            contract
+                .verify_tee_attestation(device_id, Vec::new())
+                .map(|_| ()),
+        ] {
+            match result {
+                Err(VerifierError::Decommissioned(err)) => assert_eq!(err.successor, successor),
+                other => panic!("expected Decommissioned, got {other:?}"),
+            }
+        }
+
+        // Owners can still leave with their deposits
+        vm.set_sender(owner);
+        contract.deregister_device(device_id, false).unwrap();
+    }
+
+    #[test]
+    fn test_policy_oracle_gates_verification() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        let oracle = Address::repeat_byte(0x0a);
+        contract.set_policy_oracle(oracle).unwrap();
+        let check = interfaces::IPolicyOracle::checkCall {}.abi_encode();
+
+        vm.mock_static_call(
+            oracle,
+            check.clone(),
+            Ok(U256::from(1).to_be_bytes::<32>().to_vec()),
+        );
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        vm.mock_static_call(oracle, check, Ok(vec![0u8; 32]));
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::OracleRejected(_))
+        ));
+
+        // Unsetting the oracle restores the default behavior
+        contract.set_policy_oracle(Address::ZERO).unwrap();
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+    }
+
+    #[test]
+    fn test_score_updates_are_clamped_to_max_delta() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract.set_max_score_delta(U256::from(20)).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let update = |contract: &mut CertIDVerifier, score: u64| {
+            contract
+                .update_trust_score(device_id, U256::from(score))
+                .unwrap();
+            contract.get_device_trust(device_id).unwrap()
+        };
+
+        vm.set_block_timestamp(1);
+        assert_eq!(update(&mut contract, 10), U256::from(10));
+        assert_eq!(update(&mut contract, 90), U256::from(30));
+        assert_eq!(update(&mut contract, 45), U256::from(45));
+        assert_eq!(update(&mut contract, 0), U256::from(25));
+        assert_eq!(update(&mut contract, 0), U256::from(5));
+        assert_eq!(update(&mut contract, 0), U256::ZERO);
+
+        contract.set_max_score_delta(U256::ZERO).unwrap();
+        assert_eq!(update(&mut contract, 90), U256::from(90));
+    }
+
+    #[test]
+    fn test_retired_device_cannot_verify_or_reregister() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        contract.retire_device(device_id).unwrap();
+        assert!(contract.is_retired(device_id).unwrap());
+        assert!(!contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.retire_device(device_id),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        contract.deregister_device(device_id, false).unwrap();
+        assert!(matches!(
+            contract.register_device(device_id, owner),
+            Err(VerifierError::DeviceRetired(_))
+        ));
+        assert!(contract.is_retired(device_id).unwrap());
+    }
+
+    /// Follow `next_index` from 0 until it comes back as 0.
+    fn collect_pages(
+        mut fetch: impl FnMut(U256) -> (Vec<FixedBytes<32>>, U256),
+    ) -> (Vec<FixedBytes<32>>, usize) {
+        let (mut all, mut pages, mut start) = (Vec::new(), 0, U256::ZERO);
+        loop {
+            let (items, next) = fetch(start);
+            all.extend(items);
+            pages += 1;
+            if next.is_zero() {
+                return (all, pages);
+            }
+            start = next;
+        }
+    }
+
+    #[test]
+    fn test_pagination_covers_fleet_without_overlap() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let (alice, bob) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        let fleet: Vec<FixedBytes<32>> = (0u64..250)
+            .map(|i| FixedBytes::from(U256::from(i + 1).to_be_bytes::<32>()))
+            .collect();
+        for (i, id) in fleet.iter().enumerate() {
+            let owner = if i % 5 == 0 { bob } else { alice };
+            contract.register_device(*id, owner).unwrap();
+            contract
+                .update_trust_score(*id, U256::from(if i % 7 == 0 { 10 } else { 90 }))
+                .unwrap();
+        }
+
+        let (devices, pages) =
+            collect_pages(|start| contract.get_devices(start, U256::from(40)).unwrap());
+        assert_eq!(devices, fleet);
+        assert_eq!(pages, 7);
+
+        // Zero and oversized limits fall back to the page cap
+        let (devices, pages) =
+            collect_pages(|start| contract.get_devices(start, U256::ZERO).unwrap());
+        assert_eq!(devices, fleet);
+        assert_eq!(pages, 3);
+
+        let (problems, _) = collect_pages(|start| {
+            contract
+                .scan_problem_devices(start, U256::from(64), U256::from(50), U256::ZERO)
+                .unwrap()
+        });
+        let expected: Vec<_> = fleet.iter().step_by(7).copied().collect();
+        assert_eq!(problems, expected);
+
+        let (owned, _) = collect_pages(|start| {
+            contract
+                .get_owner_devices(bob, start, U256::from(16))
+                .unwrap()
+        });
+        let expected: Vec<_> = fleet.iter().step_by(5).copied().collect();
+        assert_eq!(owned, expected);
+        assert_eq!(
+            contract.get_owner_device_count(bob).unwrap(),
+            U256::from(50)
+        );
+        assert_eq!(
+            contract.get_owner_device_count(alice).unwrap(),
+            U256::from(200)
+        );
+
+        // Deregistration removes a device from its owner's list
+        vm.set_sender(bob);
+        contract.deregister_device(fleet[0], false).unwrap();
+        let (owned, _) = contract
+            .get_owner_devices(bob, U256::ZERO, U256::ZERO)
+            .unwrap();
+        assert_eq!(owned.len(), 49);
+        assert!(!owned.contains(&fleet[0]));
+        assert_eq!(owned[0], fleet[245]);
+    }
+
+    #[test]
+    fn test_claim_reward_with_owner_signature() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let token = Address::repeat_byte(0x77);
+        let amount = U256::from(5);
+        contract.set_reward_config(token, amount).unwrap();
+
+        let owner_key = signing_key(0x51);
+        let owner = signer_address(&owner_key);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+
+        // Park a reward by making the first mint fail
+        let mint = interfaces::IRewardToken::mintCall { to: owner, amount }.abi_encode();
+        vm.mock_call(token, mint, Err(Vec::new()));
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        // A service submits on the owner's behalf, paying out to itself
+        let service = Address::repeat_byte(0x5e);
+        vm.set_sender(service);
+        let payout = interfaces::IRewardToken::mintCall {
+            to: service,
+            amount,
+        }
+        .abi_encode();
+        vm.mock_call(token, payout, Ok(Vec::new()));
+
+        // Signed by someone else, or for a different recipient: rejected
+        let forged = sign(
+            &vm,
+            &signing_key(0x52),
+            contract.claim_digest(device_id, service, U256::ZERO),
+        );
+        assert!(matches!(
+            contract.claim_reward_with_sig(device_id, service, forged),
+            Err(VerifierError::BadSignature(_))
+        ));
+        let other = sign(
+            &vm,
+            &owner_key,
+            contract.claim_digest(device_id, Address::repeat_byte(0x99), U256::ZERO),
+        );
+        assert!(matches!(
+            contract.claim_reward_with_sig(device_id, service, other),
+            Err(VerifierError::BadSignature(_))
+        ));
+
+        let auth = sign(
+            &vm,
+            &owner_key,
+            contract.claim_digest(device_id, service, U256::ZERO),
+        );
+        assert_eq!(
+            contract
+                .claim_reward_with_sig(device_id, service, auth.clone())
+                .unwrap(),
+            amount
+        );
+        assert_eq!(contract.get_pending_reward(device_id).unwrap(), U256::ZERO);
+        assert_eq!(contract.get_claim_nonce(device_id).unwrap(), U256::from(1));
+
+        // The nonce moved on, so the same authorization cannot be replayed
+        assert!(matches!(
+            contract.claim_reward_with_sig(device_id, service, auth),
+            Err(VerifierError::BadSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_attestations_from_past_epochs_need_grace() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let relayer = Address::repeat_byte(0x33);
+        contract.add_relayer(relayer).unwrap();
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        vm.set_sender(relayer);
+        contract.set_current_epoch(6).unwrap();
+        assert!(matches!(
+            contract.set_current_epoch(5),
+            Err(VerifierError::StaleEpoch(_))
+        ));
+
+        let from_epoch_4 = cbor::tests::encode_attestation_at_epoch([0xab; 32], 1, 0, 4);
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, from_epoch_4.clone()),
+            Err(VerifierError::StaleEpoch(_))
+        ));
+        let current = cbor::tests::encode_attestation_at_epoch([0xab; 32], 1, 0, 6);
+        assert!(contract.verify_tee_attestation(device_id, current).unwrap());
+
+        vm.set_sender(Address::repeat_byte(0xde));
+        assert!(matches!(
+            contract.set_current_epoch(7),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        vm.set_sender(contract.owner().unwrap());
+        contract.set_epoch_grace(U256::from(2)).unwrap();
+        assert_eq!(
+            contract.get_epoch().unwrap(),
+            (U256::from(6), U256::from(2))
+        );
+        assert!(contract
+            .verify_tee_attestation(device_id, from_epoch_4)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_register_device_authorized_by_relayer_signature() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let relayer_key = signing_key(0x61);
+        contract.add_relayer(signer_address(&relayer_key)).unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        vm.set_sender(owner);
+
+        // Not a relayer, or signed for another owner: rejected
+        let digest = contract.registration_digest(device_id, owner, U256::ZERO);
+        let forged = sign(&vm, &signing_key(0x62), digest);
+        assert!(matches!(
+            contract.register_device_authorized(device_id, owner, forged),
+            Err(VerifierError::BadSignature(_))
+        ));
+        let attacker = Address::repeat_byte(0x66);
+        let auth = sign(&vm, &relayer_key, digest);
+        assert!(matches!(
+            contract.register_device_authorized(device_id, attacker, auth.clone()),
+            Err(VerifierError::BadSignature(_))
+        ));
+
+        contract
+            .register_device_authorized(device_id, owner, auth.clone())
+            .unwrap();
+        assert_eq!(contract.get_device_owner(device_id).unwrap(), owner);
+        assert_eq!(
+            contract.get_registration_nonce(device_id).unwrap(),
+            U256::from(1)
+        );
+
+        // Replaying the authorization after deregistration fails
+        contract.deregister_device(device_id, false).unwrap();
+        assert!(matches!(
+            contract.register_device_authorized(device_id, owner, auth),
+            Err(VerifierError::BadSignature(_))
+        ));
+    }
+
+    /// Register, verify `times` times, and deregister a device.
+    fn verify_then_deregister(
+        contract: &mut CertIDVerifier,
+        vm: &TestVM,
+        device_id: FixedBytes<32>,
+        owner: Address,
+        times: u64,
+    ) {
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        for _ in 0..times {
+            assert!(contract
+                .verify_tee_attestation(device_id, Vec::new())
+                .unwrap());
+        }
+        assert_eq!(
+            contract.get_device_verification_count(device_id).unwrap(),
+            U256::from(times)
+        );
+        let caller = vm.msg_sender();
+        vm.set_sender(owner);
+        contract.deregister_device(device_id, false).unwrap();
+        vm.set_sender(caller);
+    }
+
+    #[test]
+    fn test_reregistration_resets_history_by_default() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        verify_then_deregister(&mut contract, &vm, device_id, owner, 3);
+
+        contract.register_device(device_id, owner).unwrap();
+        assert_eq!(
+            contract.get_device_verification_count(device_id).unwrap(),
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn test_reregistration_preserves_history_when_enabled() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract.set_preserve_on_reregister(true).unwrap();
+        assert!(contract.is_preserve_on_reregister().unwrap());
+
+        let relayer_key = signing_key(0x71);
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.add_relayer(signer_address(&relayer_key)).unwrap();
+
+        // Bump the restore nonce before deregistering
+        contract.register_device(device_id, owner).unwrap();
+        let digest = contract.restore_digest(device_id, U256::from(50), U256::ZERO);
+        let restore = sign(&vm, &relayer_key, digest);
+        vm.set_sender(owner);
+        contract
+            .restore_score(device_id, U256::from(50), restore.clone())
+            .unwrap();
+        contract.deregister_device(device_id, false).unwrap();
+        vm.set_sender(contract.owner().unwrap());
+
+        verify_then_deregister(&mut contract, &vm, device_id, owner, 2);
+        contract.register_device(device_id, owner).unwrap();
+        assert_eq!(
+            contract.get_device_verification_count(device_id).unwrap(),
+            U256::from(2)
+        );
+
+        // The nonce carried over, so the old restore signature is dead
+        assert_eq!(
+            contract.get_restore_nonce(device_id).unwrap(),
+            U256::from(1)
+        );
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.restore_score(device_id, U256::from(50), restore),
+            Err(VerifierError::BadSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_aggregator_is_notified_best_effort() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let aggregator = Address::repeat_byte(0xa9);
+        contract.set_aggregator(aggregator).unwrap();
+        let config_logs = vm.get_emitted_logs().len();
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        let report = |verdict| {
+            interfaces::IVerificationAggregator::reportCall { device_id, verdict }.abi_encode()
+        };
+
+        // A healthy aggregator receives the verdict without any event
+        vm.mock_call(aggregator, report(false), Ok(Vec::new()));
+        assert!(!contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        assert_eq!(vm.get_emitted_logs().len(), config_logs);
+
+        // A reverting aggregator only costs an event
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        vm.mock_call(aggregator, report(true), Err(Vec::new()));
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(1));
+
+        let logs = &vm.get_emitted_logs()[config_logs..];
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].0[0], AggregatorNotifyFailed::SIGNATURE_HASH);
+        assert_eq!(logs[0].0[1], device_id);
+    }
+
+    #[test]
+    fn test_stats_snapshot_detects_changes_during_pause() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        assert!(!contract.verify_stats_unchanged().unwrap());
+
+        let owner = Address::repeat_byte(0x11);
+        let (a, b) = (
+            FixedBytes::<32>::repeat_byte(0x01),
+            FixedBytes::<32>::repeat_byte(0x02),
+        );
+        contract.register_device(a, owner).unwrap();
+        contract.register_device(b, owner).unwrap();
+        contract.update_trust_score(a, U256::from(80)).unwrap();
+        contract.update_trust_score(b, U256::from(45)).unwrap();
+        assert_eq!(
+            contract.get_aggregate_stats().unwrap(),
+            (U256::from(2), U256::from(125), U256::from(62), U256::ZERO)
+        );
+
+        contract.snapshot_stats().unwrap();
+        contract.pause().unwrap();
+        contract.unpause().unwrap();
+        assert!(contract.verify_stats_unchanged().unwrap());
+
+        // Re-applying the same score is not a change; a new one is
+        contract.update_trust_score(a, U256::from(80)).unwrap();
+        assert!(contract.verify_stats_unchanged().unwrap());
+        contract.update_trust_score(a, U256::from(81)).unwrap();
+        assert!(!contract.verify_stats_unchanged().unwrap());
+
+        contract.snapshot_stats().unwrap();
+        vm.set_sender(owner);
+        contract.deregister_device(b, false).unwrap();
+        assert!(!contract.verify_stats_unchanged().unwrap());
+        assert_eq!(
+            contract.get_aggregate_stats().unwrap(),
+            (U256::from(1), U256::from(81), U256::from(81), U256::ZERO)
+        );
+        assert!(matches!(
+            contract.snapshot_stats(),
+            Err(VerifierError::Unauthorized(_))
+        ));
+    }
+
+    /// `sign_attestation`, followed by `root`'s endorsement of `key`.
+    fn endorse_attestation(
+        vm: &TestVM,
+        contract: &CertIDVerifier,
+        key: &SigningKey,
+        root: &SigningKey,
+        device_id: FixedBytes<32>,
+        payload: Vec<u8>,
+    ) -> Vec<u8> {
+        let mut data = sign_attestation(vm, contract, key, device_id, payload);
+        let digest = contract.endorsement_digest(device_id, signer_address(key));
+        data.extend(sign(vm, root, digest));
+        data
+    }
+
+    #[test]
+    fn test_self_signed_attestations_need_root_endorsement_when_disallowed() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+        assert!(contract.is_self_signed_allowed().unwrap());
+
+        let owner = Address::repeat_byte(0x11);
+        let device_key = signing_key(0x41);
+        let root_key = signing_key(0x51);
+        let untrusted_key = signing_key(0x52);
+        contract
+            .add_trusted_root(keccak256(signer_address(&root_key)))
+            .unwrap();
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+
+        let ids: Vec<_> = (1..=4).map(FixedBytes::<32>::repeat_byte).collect();
+        for device_id in &ids {
+            contract.register_device(*device_id, owner).unwrap();
+            contract
+                .update_trust_score(*device_id, U256::from(80))
+                .unwrap();
+        }
+        vm.set_sender(owner);
+        for device_id in &ids[..3] {
+            contract
+                .set_device_attestation_key(*device_id, signer_address(&device_key))
+                .unwrap();
+        }
+        assert!(matches!(
+            contract.set_allow_self_signed(false),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        // Development mode: the device signature alone suffices
+        let self_signed = sign_attestation(&vm, &contract, &device_key, ids[0], payload.clone());
+        assert!(contract
+            .verify_tee_attestation(ids[0], self_signed)
+            .unwrap());
+
+        vm.set_sender(admin);
+        contract.set_allow_self_signed(false).unwrap();
+
+        // Production mode: self-signed, untrusted-root and unsigned fail
+        let self_signed = sign_attestation(&vm, &contract, &device_key, ids[1], payload.clone());
+        assert!(!contract
+            .verify_tee_attestation(ids[1], self_signed)
+            .unwrap());
+        let untrusted = endorse_attestation(
+            &vm,
+            &contract,
+            &device_key,
+            &untrusted_key,
+            ids[1],
+            payload.clone(),
+        );
+        assert!(!contract.verify_tee_attestation(ids[1], untrusted).unwrap());
+        assert!(!contract
+            .verify_tee_attestation(ids[3], payload.clone())
+            .unwrap());
+
+        // An endorsement is bound to its device
+        let mut other_device = endorse_attestation(
+            &vm,
+            &contract,
+            &device_key,
+            &root_key,
+            ids[1],
+            payload.clone(),
+        );
+        let own_payload = sign_attestation(&vm, &contract, &device_key, ids[2], payload.clone());
+        other_device.splice(..own_payload.len(), own_payload);
+        assert!(!contract
+            .verify_tee_attestation(ids[2], other_device)
+            .unwrap());
+
+        let chained = endorse_attestation(&vm, &contract, &device_key, &root_key, ids[2], payload);
+        assert!(contract.verify_tee_attestation(ids[2], chained).unwrap());
+    }
+
+    #[test]
+    fn test_function_selectors_match_solidity_signatures() {
+        let vm = TestVM::default();
+        let contract = CertIDVerifier::from(&vm);
+        let selectors = contract.function_selectors().unwrap();
+
+        let verify = keccak256("verifyTeeAttestation(bytes32,uint8[])");
+        assert!(selectors.contains(&FixedBytes::from_slice(&verify[..4])));
+        let own = keccak256("functionSelectors()");
+        assert!(selectors.contains(&FixedBytes::from_slice(&own[..4])));
+
+        let mut unique = selectors.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), selectors.len());
+    }
+
+    #[test]
+    fn test_previous_relayer_key_accepted_only_within_grace() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+
+        let old_key = signing_key(0x42);
+        let new_key = signing_key(0x43);
+        vm.set_block_timestamp(1_000);
+        contract
+            .rotate_relayer_key(signer_address(&old_key))
+            .unwrap();
+        contract.set_key_rotation_grace(U256::from(600)).unwrap();
+        contract
+            .rotate_relayer_key(signer_address(&new_key))
+            .unwrap();
+        assert_eq!(
+            contract.get_relayer_keys().unwrap(),
+            (
+                signer_address(&new_key),
+                signer_address(&old_key),
+                U256::from(1_000),
+                U256::from(600)
+            )
+        );
+
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<_> = (1..=3).map(FixedBytes::<32>::repeat_byte).collect();
+        for device_id in &ids {
+            contract.register_device(*device_id, owner).unwrap();
+        }
+        let score = U256::from(90);
+        let signed = |device_id, key: &SigningKey| {
+            sign(
+                &vm,
+                key,
+                contract.restore_digest(device_id, score, U256::ZERO),
+            )
+        };
+        let (in_flight, late, fresh) = (
+            signed(ids[0], &old_key),
+            signed(ids[1], &old_key),
+            signed(ids[2], &new_key),
+        );
+
+        vm.set_sender(owner);
+        vm.set_block_timestamp(1_600);
+        contract.restore_score(ids[0], score, in_flight).unwrap();
+
+        vm.set_block_timestamp(1_601);
+        assert!(matches!(
+            contract.restore_score(ids[1], score, late),
+            Err(VerifierError::BadSignature(_))
+        ));
+        contract.restore_score(ids[2], score, fresh).unwrap();
+
+        // Registration authorizations follow the same rule
+        let device_id = FixedBytes::<32>::repeat_byte(0x04);
+        let digest = contract.registration_digest(device_id, owner, U256::ZERO);
+        assert!(matches!(
+            contract.register_device_authorized(device_id, owner, sign(&vm, &old_key, digest)),
+            Err(VerifierError::BadSignature(_))
+        ));
+        contract
+            .register_device_authorized(device_id, owner, sign(&vm, &new_key, digest))
+            .unwrap();
+
+        assert!(matches!(
+            contract.rotate_relayer_key(owner),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        vm.set_sender(admin);
+        assert!(matches!(
+            contract.rotate_relayer_key(Address::ZERO),
+            Err(VerifierError::InvalidSigner(_))
+        ));
+    }
+
+    #[test]
+    fn test_relayer_change_waits_for_timelock() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+
+        let current = Address::repeat_byte(0x42);
+        let proposed = Address::repeat_byte(0x43);
+        contract.rotate_relayer_key(current).unwrap();
+        contract.set_relayer_timelock(U256::from(3_600)).unwrap();
+        assert!(matches!(
+            contract.rotate_relayer_key(proposed),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            contract.execute_relayer_change(),
+            Err(VerifierError::RelayerChangeNotReady(_))
+        ));
+
+        vm.set_block_timestamp(1_000);
+        contract.propose_relayer(proposed).unwrap();
+        assert_eq!(
+            contract.get_pending_relayer().unwrap(),
+            (proposed, U256::from(4_600), U256::from(3_600))
+        );
+        let (topics, data) = vm.get_emitted_logs().last().unwrap().clone();
+        assert_eq!(topics[0], RelayerChangeProposed::SIGNATURE_HASH);
+        assert_eq!(topics[1], B256::left_padding_from(proposed.as_slice()));
+        assert_eq!(U256::from_be_slice(&data), U256::from(4_600));
+
+        vm.set_block_timestamp(4_599);
+        assert!(matches!(
+            contract.execute_relayer_change(),
+            Err(VerifierError::RelayerChangeNotReady(_))
+        ));
+        assert_eq!(contract.get_relayer_keys().unwrap().0, current);
+
+        vm.set_block_timestamp(4_600);
+        vm.set_sender(Address::repeat_byte(0x99));
+        assert!(matches!(
+            contract.execute_relayer_change(),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        vm.set_sender(admin);
+        contract.execute_relayer_change().unwrap();
+        let (key, previous, rotated_at, _) = contract.get_relayer_keys().unwrap();
+        assert_eq!(
+            (key, previous, rotated_at),
+            (proposed, current, U256::from(4_600))
+        );
+        assert_eq!(
+            contract.get_pending_relayer().unwrap(),
+            (Address::ZERO, U256::ZERO, U256::from(3_600))
+        );
+    }
+
+    #[test]
+    fn test_device_tags_add_remove_and_clear_on_deregistration() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let [pilot, eu, beta] = [0xa1, 0xa2, 0xa3].map(FixedBytes::<32>::repeat_byte);
+        contract.register_device(device_id, owner).unwrap();
+
+        vm.set_sender(Address::repeat_byte(0x99));
+        assert!(matches!(
+            contract.add_tag(device_id, pilot),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        vm.set_sender(owner);
+        for tag in [pilot, eu, beta, pilot] {
+            contract.add_tag(device_id, tag).unwrap();
+        }
+        contract.remove_tag(device_id, pilot).unwrap();
+        contract.remove_tag(device_id, pilot).unwrap();
+        assert!(!contract.has_tag(device_id, pilot).unwrap());
+        assert!(contract.has_tag(device_id, eu).unwrap());
+        assert!(contract.has_tag(device_id, beta).unwrap());
+
+        // Swap-remove kept the remaining tags removable
+        contract.remove_tag(device_id, beta).unwrap();
+        assert!(!contract.has_tag(device_id, beta).unwrap());
+        contract.add_tag(device_id, pilot).unwrap();
+
+        contract.deregister_device(device_id, false).unwrap();
+        for tag in [pilot, eu, beta] {
+            assert!(!contract.has_tag(device_id, tag).unwrap());
+        }
+
+        // A re-registered device starts untagged and can be tagged again
+        contract.register_device(device_id, owner).unwrap();
+        assert!(!contract.has_tag(device_id, eu).unwrap());
+        contract.add_tag(device_id, eu).unwrap();
+        contract.remove_tag(device_id, eu).unwrap();
+        assert!(!contract.has_tag(device_id, eu).unwrap());
+    }
+
+    #[test]
+    fn test_strict_verification_fails_on_each_check_independently() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+        contract
+            .set_strict_liveness_window(U256::from(300))
+            .unwrap();
+        contract
+            .set_policy(
+                U256::from(50),
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+            )
+            .unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_key = signing_key(0x41);
+        let other_key = signing_key(0x42);
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+        let ids: Vec<_> = (1..=5).map(FixedBytes::<32>::repeat_byte).collect();
+
+        vm.set_block_timestamp(1_000);
+        for device_id in &ids {
+            contract.register_device(*device_id, owner).unwrap();
+            contract
+                .update_trust_score(*device_id, U256::from(80))
+                .unwrap();
+        }
+        vm.set_sender(owner);
+        for device_id in &ids[..4] {
+            contract
+                .set_device_attestation_key(*device_id, signer_address(&device_key))
+                .unwrap();
+            let signed = sign_attestation(&vm, &contract, &device_key, *device_id, payload.clone());
+            assert!(contract.verify_tee_attestation(*device_id, signed).unwrap());
+        }
+        // An unsigned device passes the regular path but never the strict one
+        assert!(contract.verify_tee_attestation(ids[4], Vec::new()).unwrap());
+
+        vm.set_block_timestamp(1_200);
+        let strict = |contract: &mut CertIDVerifier, device_id, key: &SigningKey| {
+            let signed = sign_attestation(&vm, contract, key, device_id, payload.clone());
+            contract
+                .verify_tee_attestation_strict(device_id, signed)
+                .unwrap()
+        };
+        assert_eq!(strict(&mut contract, ids[0], &device_key), STATUS_VERIFIED);
+        assert_eq!(
+            strict(&mut contract, ids[1], &other_key),
+            STATUS_BAD_SIGNATURE
+        );
+        assert_eq!(
+            contract
+                .verify_tee_attestation_strict(ids[4], Vec::new())
+                .unwrap(),
+            STATUS_BAD_SIGNATURE
+        );
+
+        vm.set_sender(admin);
+        contract.update_trust_score(ids[2], U256::from(10)).unwrap();
+        assert_eq!(strict(&mut contract, ids[2], &device_key), STATUS_LOW_SCORE);
+
+        vm.set_block_timestamp(1_301);
+        assert_eq!(strict(&mut contract, ids[3], &device_key), STATUS_STALE);
+        // The regular path does not need liveness, and re-arms the strict one
+        let signed = sign_attestation(&vm, &contract, &device_key, ids[3], payload.clone());
+        assert!(contract.verify_tee_attestation(ids[3], signed).unwrap());
+        assert_eq!(strict(&mut contract, ids[3], &device_key), STATUS_VERIFIED);
+
+        assert_eq!(contract.get_failure_count(ids[1]).unwrap(), U256::from(1));
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(8));
+    }
+
+    #[test]
+    fn test_zero_address_recovery_is_never_a_valid_signer() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        // A commitment to the zero address, and no relayer key rotated in
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device_with_commitment(device_id, owner, keccak256(Address::ZERO))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        // Make ecrecover "fail" by returning the zero address
+        let failed_recovery = |digest: B256| {
+            let mut sig = vec![0x01; 64];
+            sig.push(27);
+            let input = signature::ecrecover_input(digest, &sig).unwrap();
+            vm.mock_static_call(signature::ECRECOVER, input.to_vec(), Ok(vec![0u8; 32]));
+            sig
+        };
+
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+        let mut data = payload.clone();
+        data.extend(failed_recovery(
+            contract.attestation_digest(device_id, &payload),
+        ));
+        assert!(!contract.verify_tee_attestation(device_id, data).unwrap());
+
+        let score = U256::from(90);
+        let sig = failed_recovery(contract.restore_digest(device_id, score, U256::ZERO));
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.restore_score(device_id, score, sig),
+            Err(VerifierError::BadSignature(_))
+        ));
+
+        assert!(matches!(
+            contract.set_device_attestation_key(device_id, Address::ZERO),
+            Err(VerifierError::InvalidSigner(_))
+        ));
+    }
+
+    #[test]
+    fn test_signer_set_is_capped() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        vm.set_sender(owner);
+
+        let signers: Vec<_> = (1..=MAX_SIGNERS_PER_DEVICE as u8 + 1)
+            .map(Address::repeat_byte)
+            .collect();
+        for signer in &signers[..MAX_SIGNERS_PER_DEVICE] {
+            contract.add_delegate_signer(device_id, *signer).unwrap();
+        }
+        let (set, threshold) = contract.get_device_signers(device_id).unwrap();
+        assert_eq!(set, signers[..MAX_SIGNERS_PER_DEVICE]);
+        assert_eq!(threshold, U256::from(1));
+        assert!(matches!(
+            contract.add_delegate_signer(device_id, signers[0]),
+            Err(VerifierError::InvalidSigner(_))
+        ));
+        assert!(matches!(
+            contract.add_delegate_signer(device_id, signers[MAX_SIGNERS_PER_DEVICE]),
+            Err(VerifierError::TooManySigners(_))
+        ));
+
+        assert!(matches!(
+            contract.set_device_signers(device_id, signers.clone(), U256::from(2)),
+            Err(VerifierError::TooManySigners(_))
+        ));
+        contract
+            .set_device_signers(
+                device_id,
+                signers[1..].to_vec(),
+                U256::from(MAX_SIGNERS_PER_DEVICE),
+            )
+            .unwrap();
+        assert_eq!(
+            contract.get_device_signers(device_id).unwrap(),
+            (signers[1..].to_vec(), U256::from(MAX_SIGNERS_PER_DEVICE))
+        );
+    }
+
+    #[test]
+    fn test_trust_tiers_at_boundaries() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        let tier_at = |contract: &mut CertIDVerifier, score: u64| {
+            contract
+                .update_trust_score(device_id, U256::from(score))
+                .unwrap();
+            contract.get_trust_tier(device_id).unwrap()
+        };
+
+        let defaults = [
+            (0, TIER_NONE),
+            (1, TIER_BRONZE),
+            (49, TIER_BRONZE),
+            (50, TIER_SILVER),
+            (79, TIER_SILVER),
+            (80, TIER_GOLD),
+            (100, TIER_GOLD),
+        ];
+        for (score, tier) in defaults {
+            assert_eq!(tier_at(&mut contract, score), tier, "score {score}");
+        }
+
+        contract
+            .set_trust_tiers(U256::from(60), U256::from(90))
+            .unwrap();
+        assert_eq!(
+            contract.get_trust_tiers().unwrap(),
+            (U256::from(60), U256::from(90))
+        );
+        let custom = [
+            (59, TIER_BRONZE),
+            (60, TIER_SILVER),
+            (89, TIER_SILVER),
+            (90, TIER_GOLD),
+        ];
+        for (score, tier) in custom {
+            assert_eq!(tier_at(&mut contract, score), tier, "score {score}");
+        }
+
+        // A score past its grace no longer earns a badge
+        contract
+            .set_policy(
+                U256::ZERO,
+                U256::from(60),
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+            )
+            .unwrap();
+        vm.set_block_timestamp(61);
+        assert_eq!(contract.get_trust_tier(device_id).unwrap(), TIER_NONE);
+
+        for (silver, gold) in [(1, 50), (50, 50), (60, 101)] {
+            assert!(matches!(
+                contract.set_trust_tiers(U256::from(silver), U256::from(gold)),
+                Err(VerifierError::InvalidPolicy(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_previous_device_key_verifies_within_grace() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract.set_device_key_grace(U256::from(120)).unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let old_key = signing_key(0x41);
+        let new_key = signing_key(0x42);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        vm.set_sender(owner);
+        vm.set_block_timestamp(1_000);
+        contract
+            .set_device_attestation_key(device_id, signer_address(&old_key))
+            .unwrap();
+        let in_flight: Vec<_> = (1..=2)
+            .map(|ts| {
+                let payload = cbor::tests::encode_attestation([0xab; 32], ts, 0);
+                sign_attestation(&vm, &contract, &old_key, device_id, payload)
+            })
+            .collect();
+
+        vm.set_block_timestamp(2_000);
+        contract
+            .set_device_attestation_key(device_id, signer_address(&new_key))
+            .unwrap();
+        assert_eq!(
+            contract.get_device_previous_key(device_id).unwrap(),
+            (signer_address(&old_key), U256::from(2_000), U256::from(120))
+        );
+
+        vm.set_block_timestamp(2_120);
+        assert!(contract
+            .verify_tee_attestation(device_id, in_flight[0].clone())
+            .unwrap());
+        vm.set_block_timestamp(2_121);
+        assert!(!contract
+            .verify_tee_attestation(device_id, in_flight[1].clone())
+            .unwrap());
+
+        let payload = cbor::tests::encode_attestation([0xab; 32], 3, 0);
+        let current = sign_attestation(&vm, &contract, &new_key, device_id, payload);
+        assert!(contract.verify_tee_attestation(device_id, current).unwrap());
+
+        contract.deregister_device(device_id, false).unwrap();
+        assert_eq!(
+            contract.get_device_previous_key(device_id).unwrap(),
+            (Address::ZERO, U256::ZERO, U256::from(120))
+        );
+    }
+
+    #[test]
+    fn test_verifications_in_window_sums_hourly_buckets() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        let hour = 3_600;
+        let verify_at = |contract: &mut CertIDVerifier, ts: u64| {
+            vm.set_block_timestamp(ts);
+            assert!(contract
+                .verify_tee_attestation(device_id, Vec::new())
+                .unwrap());
+        };
+        // One in hour 0, two in hour 1, one in hour 2
+        for ts in [100, hour + 5, hour + 10, 2 * hour + 30] {
+            verify_at(&mut contract, ts);
+        }
+
+        let window = |contract: &CertIDVerifier, seconds: u64| {
+            contract
+                .verifications_in_window(U256::from(seconds))
+                .unwrap()
+        };
+        assert_eq!(window(&contract, 0), U256::from(1));
+        assert_eq!(window(&contract, 30), U256::from(1));
+        assert_eq!(window(&contract, 31), U256::from(3));
+        assert_eq!(window(&contract, hour), U256::from(3));
+        assert_eq!(window(&contract, 2 * hour), U256::from(4));
+        assert_eq!(window(&contract, u64::MAX), U256::from(4));
+
+        // A day later hour 2's slot is reused; the older hours fall out
+        verify_at(&mut contract, 26 * hour);
+        assert_eq!(window(&contract, 0), U256::from(1));
+        assert_eq!(window(&contract, 24 * hour), U256::from(1));
+        assert_eq!(window(&contract, u64::MAX), U256::from(1));
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(5));
+    }
+
+    #[test]
+    fn test_explicit_zero_score_is_distinct_from_unscored() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let (scored, unscored) = (
+            FixedBytes::<32>::repeat_byte(0x01),
+            FixedBytes::<32>::repeat_byte(0x02),
+        );
+        contract.register_device(scored, owner).unwrap();
+        contract.register_device(unscored, owner).unwrap();
+        contract.update_trust_score(scored, U256::ZERO).unwrap();
+        assert!(contract.has_score(scored).unwrap());
+        assert!(!contract.has_score(unscored).unwrap());
+
+        // By default a zero score still fails, like no score at all
+        for device_id in [scored, unscored] {
+            assert!(!contract
+                .verify_tee_attestation(device_id, Vec::new())
+                .unwrap());
+        }
+
+        contract.set_accept_zero_score(true).unwrap();
+        assert!(contract.verify_tee_attestation(scored, Vec::new()).unwrap());
+        assert!(!contract
+            .verify_tee_attestation(unscored, Vec::new())
+            .unwrap());
+
+        // The threshold still applies to explicit scores
+        contract
+            .set_policy(
+                U256::from(1),
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+            )
+            .unwrap();
+        assert!(!contract.verify_tee_attestation(scored, Vec::new()).unwrap());
+
+        vm.set_sender(owner);
+        contract.deregister_device(scored, false).unwrap();
+        assert!(!contract.has_score(scored).unwrap());
+    }
+
+    #[test]
+    fn test_consensus_score_updates_need_validator_quorum() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+
+        let validators: Vec<_> = (0x61..=0x63).map(signing_key).collect();
+        let outsider = signing_key(0x64);
+        contract
+            .set_validator_set(
+                validators.iter().map(signer_address).collect(),
+                U256::from(2),
+            )
+            .unwrap();
+        assert!(matches!(
+            contract.update_trust_score(device_id, U256::from(90)),
+            Err(VerifierError::ConsensusRequired(_))
+        ));
+
+        let score = U256::from(90);
+        let proof = |contract: &CertIDVerifier, keys: &[&SigningKey], seq: u64| {
+            let digest = contract.consensus_digest(device_id, score, U256::from(seq));
+            keys.iter()
+                .flat_map(|key| sign(&vm, key, digest))
+                .collect::<Vec<u8>>()
+        };
+        // One validator, repeated, plus an outsider: still one distinct vote
+        let short = proof(&contract, &[&validators[0], &validators[0], &outsider], 1);
+        let quorum = proof(&contract, &[&outsider, &validators[2], &validators[0]], 1);
+        let replay = quorum.clone();
+
+        assert!(matches!(
+            contract.update_trust_score_with_consensus(device_id, score, U256::from(1), short),
+            Err(VerifierError::QuorumNotMet(QuorumNotMet { signers })) if signers == U256::from(1)
+        ));
+        contract
+            .update_trust_score_with_consensus(device_id, score, U256::from(1), quorum)
+            .unwrap();
+        assert_eq!(contract.get_device_trust(device_id).unwrap(), score);
+        assert!(matches!(
+            contract.update_trust_score_with_consensus(device_id, score, U256::from(1), replay),
+            Err(VerifierError::StaleSequence(_))
+        ));
+
+        // Disabling consensus reopens the simple path, to relayers and the
+        // owner only, and closes this one
+        contract.set_validator_set(Vec::new(), U256::ZERO).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(40))
+            .unwrap();
+        let admin = vm.msg_sender();
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.update_trust_score(device_id, U256::from(90)),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        vm.set_sender(admin);
+        let quorum = proof(&contract, &[&validators[0], &validators[1]], 2);
+        assert!(matches!(
+            contract.update_trust_score_with_consensus(device_id, score, U256::from(2), quorum),
+            Err(VerifierError::QuorumNotMet(_))
+        ));
+    }
+
+    /// The status the verification status chain picks when every gating
+    /// condition is read up front, as before the reads short-circuited.
+    fn eager_reference_status(
+        contract: &CertIDVerifier,
+        device_id: FixedBytes<32>,
+        attestation_data: &[u8],
+        strict: bool,
+    ) -> u8 {
+        let now = U256::from(contract.vm().block_timestamp());
+        let flags = contract.device_flags.get(device_id);
+        let (payload, signed, keyed, _) =
+            contract.check_attestation_signature(device_id, attestation_data);
+        let claims = cbor::decode_attestation(payload).map_or(U256::ZERO, |a| a.claims);
+        let (threshold, _) = contract.device_policy(device_id);
+        let inactive = flags.bit(FLAG_REVOKED) || flags.bit(FLAG_RETIRED);
+        let registered = contract.is_device_registered(device_id);
+        let scored = flags.bit(FLAG_HAS_SCORE)
+            && contract.effective_score(device_id) >= contract.score_floor(threshold);
+        let fresh = contract.is_score_fresh(device_id, now);
+        let live = contract.is_recently_verified(contract.last_verified_at.get(device_id), now);
+        let claimed = contract.asserts_required_claims(claims);
+        if inactive || !registered {
+            STATUS_REJECTED
+        } else if !signed || (strict && !keyed) {
+            STATUS_BAD_SIGNATURE
+        } else if !scored {
+            STATUS_LOW_SCORE
+        } else if !fresh || (strict && !live) {
+            STATUS_STALE
+        } else if !claimed {
+            STATUS_REJECTED
+        } else {
+            STATUS_VERIFIED
+        }
+    }
+
+    #[test]
+    fn test_regular_and_strict_paths_agree_on_every_gating_condition() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+        contract
+            .set_policy(
+                U256::from(50),
+                U256::from(1_000),
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+            )
+            .unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_key = signing_key(0x41);
+        let other_key = signing_key(0x42);
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+
+        // With a keyed device and no liveness window, the strict path adds
+        // nothing, so both must reach the same verdict in every case, and
+        // the status an eager read of every condition gives
+        let cases = [
+            "ok",
+            "bad signature",
+            "unscored",
+            "low score",
+            "at threshold",
+            "revoked",
+            "retired",
+            "stale score",
+        ];
+        for (i, case) in cases.iter().enumerate() {
+            let mut verdicts = Vec::new();
+            for strict in [false, true] {
+                let device_id = FixedBytes::<32>::repeat_byte((2 * i + strict as usize + 1) as u8);
+                vm.set_sender(admin);
+                vm.set_block_timestamp(1_000);
+                contract.register_device(device_id, owner).unwrap();
+                let score = match *case {
+                    "unscored" => None,
+                    "low score" => Some(49),
+                    "at threshold" => Some(50),
+                    _ => Some(80),
+                };
+                if let Some(score) = score {
+                    contract
+                        .update_trust_score(device_id, U256::from(score))
+                        .unwrap();
+                }
+                vm.set_sender(owner);
+                contract
+                    .set_device_attestation_key(device_id, signer_address(&device_key))
+                    .unwrap();
+                vm.set_sender(admin);
+                match *case {
+                    "revoked" => contract.revoke_devices_batch(vec![device_id]).unwrap(),
+                    "retired" => contract.retire_device(device_id).unwrap(),
+                    "stale score" => vm.set_block_timestamp(2_001),
+                    _ => {}
+                }
+
+                let key = if *case == "bad signature" {
+                    &other_key
+                } else {
+                    &device_key
+                };
+                let data = sign_attestation(&vm, &contract, key, device_id, payload.clone());
+                let expected = eager_reference_status(&contract, device_id, &data, strict);
+                verdicts.push(if strict {
+                    let status = contract
+                        .verify_tee_attestation_strict(device_id, data)
+                        .unwrap();
+                    assert_eq!(status, expected, "{case}");
+                    status == STATUS_VERIFIED
+                } else {
+                    let verified = contract.verify_tee_attestation(device_id, data).unwrap();
+                    assert_eq!(verified, expected == STATUS_VERIFIED, "{case}");
+                    verified
+                });
+            }
+            assert_eq!(verdicts[0], verdicts[1], "{case}");
+            assert_eq!(
+                verdicts[0],
+                matches!(*case, "ok" | "at threshold"),
+                "{case}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_verification_overrides_supersede_normal_checks() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let (good, bad) = (
+            FixedBytes::<32>::repeat_byte(0x01),
+            FixedBytes::<32>::repeat_byte(0x02),
+        );
+        contract.register_device(good, owner).unwrap();
+        contract.register_device(bad, owner).unwrap();
+        contract.update_trust_score(good, U256::from(90)).unwrap();
+        contract.revoke_devices_batch(vec![bad]).unwrap();
+
+        contract
+            .set_verification_override(good, OVERRIDE_FORCE_FAIL)
+            .unwrap();
+        contract
+            .set_verification_override(bad, OVERRIDE_FORCE_PASS)
+            .unwrap();
+        assert!(!contract.verify_tee_attestation(good, Vec::new()).unwrap());
+        assert!(contract.verify_tee_attestation(bad, Vec::new()).unwrap());
+        assert_eq!(
+            contract
+                .verify_tee_attestation_strict(bad, Vec::new())
+                .unwrap(),
+            STATUS_VERIFIED
+        );
+        let applied = vm
+            .get_emitted_logs()
+            .iter()
+            .filter(|(topics, _)| topics[0] == OverrideApplied::SIGNATURE_HASH)
+            .count();
+        assert_eq!(applied, 3);
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::ZERO);
+        assert_eq!(contract.get_failure_count(good).unwrap(), U256::ZERO);
+
+        contract
+            .set_verification_override(good, OVERRIDE_NORMAL)
+            .unwrap();
+        contract
+            .set_verification_override(bad, OVERRIDE_NORMAL)
+            .unwrap();
+        assert!(contract.verify_tee_attestation(good, Vec::new()).unwrap());
+        assert!(!contract.verify_tee_attestation(bad, Vec::new()).unwrap());
+
+        assert!(matches!(
+            contract.set_verification_override(good, 3),
+            Err(VerifierError::InvalidPolicy(_))
+        ));
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.set_verification_override(good, OVERRIDE_FORCE_PASS),
+            Err(VerifierError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_devices_batch_returns_aligned_rows() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let (alice, bob) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        let [registered, revoked, absent] = [1, 2, 3].map(FixedBytes::<32>::repeat_byte);
+        contract.register_device(registered, alice).unwrap();
+        contract.register_device(revoked, bob).unwrap();
+        contract
+            .update_trust_score(registered, U256::from(70))
+            .unwrap();
+        contract
+            .update_trust_score(revoked, U256::from(30))
+            .unwrap();
+        contract.revoke_devices_batch(vec![revoked]).unwrap();
+
+        let (owners, scores, flags) = contract
+            .get_devices_batch(vec![absent, revoked, registered, absent])
+            .unwrap();
+        assert_eq!(owners, vec![Address::ZERO, bob, alice, Address::ZERO]);
+        assert_eq!(scores, [0, 30, 70, 0].map(U256::from).to_vec());
+        assert_eq!(flags, vec![false, true, false, false]);
+
+        assert_eq!(
+            contract.get_devices_batch(Vec::new()).unwrap(),
+            (Vec::new(), Vec::new(), Vec::new())
+        );
+        assert!(matches!(
+            contract.get_devices_batch(vec![absent; page::MAX_PAGE_LIMIT + 1]),
+            Err(VerifierError::BatchTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_verification_fee_depends_on_attestation_type() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract
+            .set_verification_fee(U256::ZERO, U256::from(100))
+            .unwrap();
+        contract
+            .set_verification_fee(U256::from(2), U256::from(300))
+            .unwrap();
+        assert_eq!(
+            contract.get_verification_fee(U256::from(2)).unwrap(),
+            U256::from(300)
+        );
+
+        let [basic, premium] = [1, 2].map(FixedBytes::<32>::repeat_byte);
+        contract
+            .register_device(basic, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .register_device(premium, Address::repeat_byte(0x22))
+            .unwrap();
+        let typed = cbor::tests::encode_attestation_of_type([0xab; 32], 1, 0, 2);
+
+        vm.set_value(U256::from(100));
+        contract.verify_tee_attestation(basic, Vec::new()).unwrap();
+        assert!(matches!(
+            contract.verify_tee_attestation(premium, typed.clone()),
+            Err(VerifierError::InsufficientFee(InsufficientFee { required }))
+                if required == U256::from(300)
+        ));
+
+        vm.set_value(U256::from(300));
+        contract.verify_tee_attestation(premium, typed).unwrap();
+        assert_eq!(contract.get_fee_pool().unwrap(), U256::from(400));
+    }
+
+    #[test]
+    fn test_withdraw_fees_pays_out_the_pool() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+
+        let [normal, forced, digest] = [1, 2, 3].map(FixedBytes::<32>::repeat_byte);
+        for device_id in [normal, forced, digest] {
+            contract
+                .register_device(device_id, Address::repeat_byte(0x11))
+                .unwrap();
+        }
+        contract
+            .set_verification_override(forced, OVERRIDE_FORCE_PASS)
+            .unwrap();
+        contract
+            .set_verification_override(digest, OVERRIDE_FORCE_FAIL)
+            .unwrap();
+
+        // Value sent on overridden verifications is pooled like any fee
+        vm.set_value(U256::from(100));
+        contract.verify_tee_attestation(normal, Vec::new()).unwrap();
+        assert!(contract.verify_tee_attestation(forced, Vec::new()).unwrap());
+        assert!(!contract
+            .verify_tee_attestation_digest(digest, FixedBytes::ZERO, Vec::new())
+            .unwrap());
+        assert_eq!(contract.get_fee_pool().unwrap(), U256::from(300));
+        vm.set_value(U256::ZERO);
+        vm.set_balance(vm.contract_address(), U256::from(300));
+
+        let treasury = Address::repeat_byte(0x77);
+        vm.set_sender(Address::repeat_byte(0x55));
+        assert!(matches!(
+            contract.withdraw_fees(treasury, U256::from(1)),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        vm.set_sender(admin);
+        assert!(matches!(
+            contract.withdraw_fees(treasury, U256::from(301)),
+            Err(VerifierError::InsufficientFeePool(InsufficientFeePool { pool }))
+                if pool == U256::from(300)
+        ));
+
+        contract.withdraw_fees(treasury, U256::from(250)).unwrap();
+        assert_eq!(contract.get_fee_pool().unwrap(), U256::from(50));
+        assert_eq!(vm.balance(treasury), U256::from(250));
+        let logs = vm.get_emitted_logs();
+        let (topics, _) = logs.last().unwrap();
+        assert_eq!(topics[0], FeesWithdrawn::SIGNATURE_HASH);
+    }
+
+    #[test]
+    fn test_deregister_with_pending_rewards_needs_force() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let token = Address::repeat_byte(0x77);
+        let amount = U256::from(5);
+        contract.set_reward_config(token, amount).unwrap();
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let owner = Address::repeat_byte(0x11);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        let mint = interfaces::IRewardToken::mintCall { to: owner, amount }.abi_encode();
+        vm.mock_call(token, mint, Err(Vec::new()));
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.deregister_device(device_id, false),
+            Err(VerifierError::PendingRewardsExist(PendingRewardsExist { amount: a }))
+                if a == amount
+        ));
+        assert!(contract.is_registered(device_id).unwrap());
+
+        contract.deregister_device(device_id, true).unwrap();
+        assert!(!contract.is_registered(device_id).unwrap());
+        assert_eq!(contract.get_pending_reward(device_id).unwrap(), U256::ZERO);
+        let logs = vm.get_emitted_logs();
+        let forfeited = logs.last().unwrap();
+        assert_eq!(forfeited.0[0], RewardsForfeited::SIGNATURE_HASH);
+        assert_eq!(forfeited.0[1], device_id);
+    }
+
+    #[test]
+    fn test_packed_device_flags_are_independent() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let bits = [
+            FLAG_REVOKED,
+            FLAG_RETIRED,
+            FLAG_HAS_SCORE,
+            FLAG_FORCE_PASS,
+            FLAG_FORCE_FAIL,
+        ];
+        for bit in bits {
+            contract.set_flag(device_id, bit, true);
+            for other in bits {
+                assert_eq!(contract.get_flag(device_id, other), other == bit);
+            }
+            contract.set_flag(device_id, bit, false);
+            assert_eq!(contract.device_flags.get(device_id), U256::ZERO);
+        }
+
+        contract.set_flag(device_id, FLAG_REVOKED, true);
+        contract
+            .set_verification_override(device_id, OVERRIDE_FORCE_FAIL)
+            .unwrap();
+        contract
+            .set_verification_override(device_id, OVERRIDE_FORCE_PASS)
+            .unwrap();
+        assert_eq!(
+            contract.get_verification_override(device_id).unwrap(),
+            OVERRIDE_FORCE_PASS
+        );
+        assert!(contract.is_revoked(device_id).unwrap());
+        assert!(!contract.is_retired(device_id).unwrap());
+    }
+
+    #[test]
+    fn test_score_histogram_tracks_registered_scores() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let scores = [5u64, 9, 42, 100, 99, 100];
+        for (i, score) in scores.iter().enumerate() {
+            let device_id = FixedBytes::<32>::repeat_byte(i as u8 + 1);
+            contract.register_device(device_id, owner).unwrap();
+            contract
+                .update_trust_score(device_id, U256::from(*score))
+                .unwrap();
+        }
+        let expected = |counts: [u64; SCORE_HISTOGRAM_BUCKETS]| -> Vec<U256> {
+            counts.iter().map(|c| U256::from(*c)).collect()
+        };
+        assert_eq!(
+            contract.get_score_histogram().unwrap(),
+            expected([2, 0, 0, 0, 1, 0, 0, 0, 0, 1, 2])
+        );
+
+        // Rescoring moves a device between buckets; deregistering drops it
+        let first = FixedBytes::<32>::repeat_byte(1);
+        contract.update_trust_score(first, U256::from(55)).unwrap();
+        vm.set_sender(owner);
+        contract
+            .deregister_device(FixedBytes::repeat_byte(4), false)
+            .unwrap();
+        assert_eq!(
+            contract.get_score_histogram().unwrap(),
+            expected([1, 0, 0, 0, 1, 1, 0, 0, 0, 1, 1])
+        );
+    }
+
+    #[test]
+    fn test_verification_needs_min_relayers() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract.set_min_relayers(U256::from(2)).unwrap();
+        contract.add_relayer(Address::repeat_byte(0xa1)).unwrap();
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::InsufficientRelayers(InsufficientRelayers { count }))
+                if count == U256::from(1)
+        ));
+
+        contract.add_relayer(Address::repeat_byte(0xa2)).unwrap();
+        contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap();
+
+        contract.remove_relayer(Address::repeat_byte(0xa2)).unwrap();
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::InsufficientRelayers(_))
+        ));
+        contract.set_min_relayers(U256::from(1)).unwrap();
+        contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_deregister_with_l1_removal_proof() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let [removed, kept] = [1, 2].map(FixedBytes::<32>::repeat_byte);
+        contract.register_device(removed, owner).unwrap();
+        contract.register_device(kept, owner).unwrap();
+
+        let algo = merkle::HASH_KECCAK256;
+        let leaf = merkle::removal_leaf(&vm, algo, removed).unwrap();
+        let leaves = [
+            leaf,
+            B256::repeat_byte(0xa2),
+            B256::repeat_byte(0xa3),
+            B256::repeat_byte(0xa4),
+        ];
+        let (root, proof) = merkle::tests::four_leaf_tree(&vm, algo, leaves);
+        contract.set_state_root(root).unwrap();
+        let packed: Vec<u8> = proof.iter().flat_map(|h| h.0).collect();
+
+        // Anyone may relay the proof, but only for the device it marks
+        vm.set_sender(Address::repeat_byte(0x99));
+        assert!(matches!(
+            contract.deregister_with_proof(kept, packed.clone()),
+            Err(VerifierError::InvalidStateProof(_))
+        ));
+        assert!(matches!(
+            contract.deregister_with_proof(removed, packed[..40].to_vec()),
+            Err(VerifierError::InvalidStateProof(_))
+        ));
+        assert!(contract.is_registered(removed).unwrap());
+
+        contract
+            .deregister_with_proof(removed, packed.clone())
+            .unwrap();
+        assert!(!contract.is_registered(removed).unwrap());
+        assert!(contract.is_registered(kept).unwrap());
+        assert!(matches!(
+            contract.deregister_with_proof(removed, packed),
+            Err(VerifierError::DeviceNotRegistered(_))
+        ));
+    }
+
+    #[test]
+    fn test_attestation_nonces_accept_in_window_reordering() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        let verify = |contract: &mut CertIDVerifier, nonce: u64| {
+            let payload = cbor::tests::encode_attestation_with_nonce([0xab; 32], 1, 0, nonce);
+            contract.verify_tee_attestation(device_id, payload)
+        };
+        let rejected = |result: Result<bool, VerifierError>, nonce: u64| matches!(result, Err(VerifierError::InvalidNonce(InvalidNonce { nonce: n })) if n == nonce);
+
+        // Strict by default: only the next nonce
+        assert!(rejected(verify(&mut contract, 1), 1));
+        assert!(verify(&mut contract, 0).unwrap());
+        assert!(rejected(verify(&mut contract, 0), 0));
+
+        // With a window of 2, nonces 1–3 may arrive in any order
+        contract.set_nonce_window(U256::from(2)).unwrap();
+        assert!(verify(&mut contract, 3).unwrap());
+        assert!(rejected(verify(&mut contract, 3), 3));
+        assert!(rejected(verify(&mut contract, 4), 4));
+        assert!(verify(&mut contract, 1).unwrap());
+        assert_eq!(
+            contract.get_attestation_nonce(device_id).unwrap(),
+            U256::from(2)
+        );
+        assert!(verify(&mut contract, 2).unwrap());
+        assert_eq!(
+            contract.get_attestation_nonce(device_id).unwrap(),
+            U256::from(4)
+        );
+        assert!(rejected(verify(&mut contract, 1), 1));
+
+        assert!(matches!(
+            contract.set_nonce_window(U256::from(MAX_NONCE_WINDOW + 1)),
+            Err(VerifierError::InvalidPolicy(_))
+        ));
+    }
+
+    #[test]
+    fn test_score_drop_alarm_pauses_until_next_epoch() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+        contract.add_relayer(admin).unwrap();
+        contract.set_score_decrease_alarm(U256::from(2)).unwrap();
+
+        let fleet = [1, 2, 3, 4].map(FixedBytes::<32>::repeat_byte);
+        for device_id in fleet {
+            contract
+                .register_device(device_id, Address::repeat_byte(0x11))
+                .unwrap();
+            contract
+                .update_trust_score(device_id, U256::from(90))
+                .unwrap();
+        }
+
+        // Raises do not count; the third drop trips the alarm
+        contract
+            .update_trust_score(fleet[0], U256::from(95))
+            .unwrap();
+        for device_id in &fleet[..3] {
+            contract
+                .update_trust_score(*device_id, U256::from(10))
+                .unwrap();
+        }
+        assert_eq!(
+            contract.score_decreases_this_epoch().unwrap(),
+            U256::from(3)
+        );
+        assert!(contract.paused().unwrap());
+        let logs = vm.get_emitted_logs();
+        assert_eq!(logs.last().unwrap().0[0], AnomalyDetected::SIGNATURE_HASH);
+        assert!(matches!(
+            contract.update_trust_score(fleet[3], U256::from(10)),
+            Err(VerifierError::ContractPaused(_))
+        ));
+
+        // A new epoch starts the count afresh
+        contract.set_current_epoch(1).unwrap();
+        assert_eq!(contract.score_decreases_this_epoch().unwrap(), U256::ZERO);
+        contract.unpause().unwrap();
+        contract
+            .update_trust_score(fleet[3], U256::from(10))
+            .unwrap();
+        assert_eq!(
+            contract.score_decreases_this_epoch().unwrap(),
+            U256::from(1)
+        );
+        assert!(!contract.paused().unwrap());
+
+        // Drops from failed verifications never count
+        contract.set_ewma_alpha(U256::from(EWMA_SCALE / 2)).unwrap();
+        contract.revoke_devices_batch(fleet.to_vec()).unwrap();
+        for device_id in fleet {
+            assert!(!contract
+                .verify_tee_attestation(device_id, Vec::new())
+                .unwrap());
+        }
+        assert_eq!(
+            contract.score_decreases_this_epoch().unwrap(),
+            U256::from(1)
+        );
+        assert!(!contract.paused().unwrap());
+    }
+
+    #[test]
+    fn test_score_certificates_apply_once_in_sequence() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let admin = vm.msg_sender();
+        vm.set_block_timestamp(1_000);
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        let relayer = signing_key(0x71);
+        contract.add_relayer(signer_address(&relayer)).unwrap();
+        let certify = |contract: &CertIDVerifier, key: &SigningKey, score: u64, seq: u64| {
+            let digest =
+                contract.score_certificate_digest(device_id, U256::from(score), U256::from(seq));
+            sign(&vm, key, digest)
+        };
+
+        vm.set_sender(owner);
+        let cert = certify(&contract, &relayer, 80, 1);
+        contract
+            .submit_score_certificate(device_id, U256::from(80), U256::from(1), cert.clone())
+            .unwrap();
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(80)
+        );
+        assert_eq!(contract.get_bridge_seq(device_id).unwrap(), U256::from(1));
+
+        // Replaying the certificate, or any older seq, is stale
+        assert!(matches!(
+            contract.submit_score_certificate(device_id, U256::from(80), U256::from(1), cert),
+            Err(VerifierError::StaleSequence(_))
+        ));
+
+        // Only relayers can certify, and only the exact tuple they signed
+        let forged = certify(&contract, &signing_key(0x72), 95, 2);
+        assert!(matches!(
+            contract.submit_score_certificate(device_id, U256::from(95), U256::from(2), forged),
+            Err(VerifierError::BadSignature(_))
+        ));
+        let cert = certify(&contract, &relayer, 60, 2);
+        assert!(matches!(
+            contract.submit_score_certificate(device_id, U256::from(95), U256::from(2), cert),
+            Err(VerifierError::BadSignature(_))
+        ));
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(80)
+        );
+
+        // Certificates get the same delta clamp as `update_trust_score`
+        vm.set_sender(admin);
+        contract.set_max_score_delta(U256::from(5)).unwrap();
+        vm.set_sender(owner);
+        let cert = certify(&contract, &relayer, 20, 2);
+        contract
+            .submit_score_certificate(device_id, U256::from(20), U256::from(2), cert)
+            .unwrap();
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(75)
+        );
+
+        // and cannot bypass a configured validator quorum
+        vm.set_sender(admin);
+        contract
+            .set_validator_set(vec![Address::repeat_byte(0x31)], U256::from(1))
+            .unwrap();
+        vm.set_sender(owner);
+        let cert = certify(&contract, &relayer, 90, 3);
+        assert!(matches!(
+            contract.submit_score_certificate(device_id, U256::from(90), U256::from(3), cert),
+            Err(VerifierError::ConsensusRequired(_))
+        ));
+    }
+
+    #[test]
+    fn test_hw_counter_must_strictly_increase() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        let verify = |contract: &mut CertIDVerifier, counter: u64| {
+            let payload = cbor::tests::encode_attestation_with_counter([0xab; 32], 1, 0, counter);
+            contract.verify_tee_attestation(device_id, payload)
+        };
+        let rolled_back = |result: Result<bool, VerifierError>| {
+            matches!(result, Err(VerifierError::CounterRollback(_)))
+        };
+
+        assert!(verify(&mut contract, 0).unwrap());
+        assert!(verify(&mut contract, 5).unwrap());
+        assert_eq!(
+            contract.get_hw_counter(device_id).unwrap(),
+            (true, U256::from(5))
+        );
+
+        // Replayed, lower and missing counters are all rollbacks
+        assert!(rolled_back(verify(&mut contract, 5)));
+        assert!(rolled_back(verify(&mut contract, 4)));
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+        assert!(rolled_back(
+            contract.verify_tee_attestation(device_id, payload)
+        ));
+        assert!(verify(&mut contract, 6).unwrap());
+    }
+
+    #[test]
+    fn test_device_fee_is_forwarded_to_owner_on_success() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+
+        let owner = Address::repeat_byte(0x11);
+        let verifier = Address::repeat_byte(0x33);
+        let [paid, free] = [1, 2].map(FixedBytes::<32>::repeat_byte);
+        contract.register_device(paid, owner).unwrap();
+        contract.register_device(free, owner).unwrap();
+        contract.update_trust_score(paid, U256::from(90)).unwrap();
+        let fee = U256::from(700);
+        vm.set_sender(owner);
+        contract.set_device_verification_fee(paid, fee).unwrap();
+
+        // Free devices need no payment
+        vm.set_sender(verifier);
+        contract.verify_tee_attestation(free, Vec::new()).unwrap();
+        vm.set_value(U256::from(699));
+        assert!(matches!(
+            contract.verify_tee_attestation(paid, Vec::new()),
+            Err(VerifierError::InsufficientFee(InsufficientFee { required })) if required == fee
+        ));
+
+        vm.set_value(fee);
+        vm.set_balance(vm.contract_address(), fee);
+        assert!(contract.verify_tee_attestation(paid, Vec::new()).unwrap());
+        assert_eq!(vm.balance(owner), fee);
+        assert_eq!(contract.get_fee_pool().unwrap(), U256::ZERO);
+
+        // A failed verification returns the fee to the verifier
+        vm.set_sender(admin);
+        contract.update_trust_score(paid, U256::ZERO).unwrap();
+        vm.set_sender(verifier);
+        vm.set_balance(vm.contract_address(), fee);
+        assert!(!contract.verify_tee_attestation(paid, Vec::new()).unwrap());
+        assert_eq!(vm.balance(verifier), fee);
+        assert_eq!(vm.balance(owner), fee);
+    }
+
+    #[test]
+    fn test_domain_separator_matches_eip712_reference() {
+        let vm = TestVM::default();
+        let contract_address = Address::repeat_byte(0xcc);
+        vm.set_contract_address(contract_address);
+        let contract = CertIDVerifier::from(&vm);
+
+        let reference = alloy_sol_types::eip712_domain! {
+            name: EIP712_NAME,
+            version: EIP712_VERSION,
+            chain_id: 42161,
+            verifying_contract: contract_address,
+        };
+        assert_eq!(contract.domain_separator().unwrap(), reference.separator());
+    }
+
+    #[test]
+    fn test_new_devices_start_at_default_score() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract
+            .set_policy(
+                U256::from(40),
+                U256::ZERO,
+                U256::ZERO,
+                U256::from(MAX_ATTESTATION_LEN),
+                U256::ZERO,
+            )
+            .unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let [before, baseline, prescored] = [1, 2, 3].map(FixedBytes::<32>::repeat_byte);
+        contract.register_device(before, owner).unwrap();
+        contract.set_default_trust_score(U256::from(250)).unwrap();
+        assert_eq!(
+            contract.get_default_trust_score().unwrap(),
+            U256::from(MAX_TRUST_SCORE)
+        );
+        contract.set_default_trust_score(U256::from(60)).unwrap();
+
+        contract.register_device(baseline, owner).unwrap();
+        assert_eq!(contract.get_device_trust(baseline).unwrap(), U256::from(60));
+        assert!(contract
+            .verify_tee_attestation(baseline, Vec::new())
+            .unwrap());
+        assert!(!contract.verify_tee_attestation(before, Vec::new()).unwrap());
+
+        // A score relayed before registration is kept
+        contract
+            .update_trust_score(prescored, U256::from(20))
+            .unwrap();
+        contract.register_device(prescored, owner).unwrap();
+        assert_eq!(
+            contract.get_device_trust(prescored).unwrap(),
+            U256::from(20)
+        );
+        assert_eq!(contract.get_aggregate_stats().unwrap().1, U256::from(80));
+    }
+
+    #[test]
+    fn test_device_nonces_follow_input_order() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let [busy, fresh, absent] = [1, 2, 3].map(FixedBytes::<32>::repeat_byte);
+        for device_id in [busy, fresh] {
+            contract
+                .register_device(device_id, Address::repeat_byte(0x11))
+                .unwrap();
+        }
+        contract.update_trust_score(busy, U256::from(90)).unwrap();
+        for nonce in 0..3 {
+            let payload = cbor::tests::encode_attestation_with_nonce([0xab; 32], 1, 0, nonce);
+            assert!(contract.verify_tee_attestation(busy, payload).unwrap());
+        }
+
+        assert_eq!(
+            contract
+                .get_device_nonces(vec![absent, busy, fresh, busy])
+                .unwrap(),
+            vec![U256::ZERO, U256::from(3), U256::ZERO, U256::from(3)]
+        );
+        assert!(matches!(
+            contract.get_device_nonces(vec![busy; page::MAX_PAGE_LIMIT + 1]),
+            Err(VerifierError::BatchTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_staged_score_takes_effect_then_commits() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(40))
+            .unwrap();
+        vm.set_block_timestamp(1_000);
+        contract
+            .stage_trust_score(device_id, U256::from(85), U256::from(2_000))
+            .unwrap();
+
+        // Before it is effective the current score stands
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(40)
+        );
+        assert_eq!(contract.get_trust_tier(device_id).unwrap(), TIER_BRONZE);
+        assert!(matches!(
+            contract.commit_staged_score(device_id),
+            Err(VerifierError::StagedScoreNotReady(StagedScoreNotReady { effective_at }))
+                if effective_at == U256::from(2_000)
+        ));
+
+        vm.set_block_timestamp(2_000);
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(85)
+        );
+        assert_eq!(contract.get_aggregate_stats().unwrap().1, U256::from(40));
+
+        // Per-device views already read the effective score
+        assert_eq!(contract.get_trust_tier(device_id).unwrap(), TIER_GOLD);
+        assert_eq!(
+            contract.get_rank_key(device_id).unwrap() >> 160,
+            U256::from(85)
+        );
+        assert_eq!(
+            contract.get_devices_batch(vec![device_id]).unwrap().1,
+            vec![U256::from(85)]
+        );
+        assert!(contract
+            .scan_problem_devices(U256::ZERO, U256::from(10), U256::from(50), U256::ZERO)
+            .unwrap()
+            .0
+            .is_empty());
+
+        contract.commit_staged_score(device_id).unwrap();
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(85)
+        );
+        assert_eq!(contract.get_aggregate_stats().unwrap().1, U256::from(85));
+        assert_eq!(
+            contract.get_staged_score(device_id).unwrap(),
+            (U256::ZERO, U256::ZERO)
+        );
+        assert!(matches!(
+            contract.commit_staged_score(device_id),
+            Err(VerifierError::StagedScoreNotReady(_))
+        ));
+
+        vm.set_sender(Address::repeat_byte(0x22));
+        assert!(matches!(
+            contract.stage_trust_score(device_id, U256::from(10), U256::from(3_000)),
+            Err(VerifierError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_staged_scores_are_controlled_and_settle_on_verification() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        vm.set_block_timestamp(1_000);
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(60))
+            .unwrap();
+
+        // Staging is clamped like an update, and refused while paused
+        contract.set_max_score_delta(U256::from(5)).unwrap();
+        contract
+            .stage_trust_score(device_id, U256::from(99), U256::from(2_000))
+            .unwrap();
+        assert_eq!(
+            contract.get_staged_score(device_id).unwrap(),
+            (U256::from(65), U256::from(2_000))
+        );
+        contract.pause().unwrap();
+        assert!(matches!(
+            contract.stage_trust_score(device_id, U256::from(60), U256::from(2_000)),
+            Err(VerifierError::ContractPaused(_))
+        ));
+        contract.unpause().unwrap();
+
+        // The device's next verification writes the staged score through
+        vm.set_block_timestamp(2_000);
+        assert_eq!(contract.get_aggregate_stats().unwrap().1, U256::from(60));
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        assert_eq!(contract.get_aggregate_stats().unwrap().1, U256::from(65));
+        assert_eq!(
+            contract.get_staged_score(device_id).unwrap(),
+            (U256::ZERO, U256::ZERO)
+        );
+
+        // A device's first score can arrive staged and pass on settling
+        let staged_only = FixedBytes::<32>::repeat_byte(0x02);
+        contract
+            .register_device(staged_only, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .stage_trust_score(staged_only, U256::from(70), U256::from(2_000))
+            .unwrap();
+        assert!(contract
+            .verify_tee_attestation(staged_only, Vec::new())
+            .unwrap());
+
+        // A configured validator quorum rules out staging
+        contract
+            .set_validator_set(vec![Address::repeat_byte(0x31)], U256::from(1))
+            .unwrap();
+        assert!(matches!(
+            contract.stage_trust_score(device_id, U256::from(60), U256::from(3_000)),
+            Err(VerifierError::ConsensusRequired(_))
+        ));
+    }
+
+    #[test]
+    fn test_reset_statistics_keeps_devices() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        vm.set_block_timestamp(10_000);
+        for _ in 0..3 {
+            assert!(contract
+                .verify_tee_attestation(device_id, Vec::new())
+                .unwrap());
+        }
+
+        contract.reset_statistics().unwrap();
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::ZERO);
+        assert_eq!(contract.score_decreases_this_epoch().unwrap(), U256::ZERO);
+        assert_eq!(
+            contract
+                .verifications_in_window(U256::from(THROUGHPUT_BUCKET_SECONDS))
+                .unwrap(),
+            U256::ZERO
+        );
+        let logs = vm.get_emitted_logs();
+        let (topics, data) = logs.last().unwrap();
+        assert_eq!(topics[0], StatisticsReset::SIGNATURE_HASH);
+        assert_eq!(U256::from_be_slice(data), U256::from(3));
+
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(80)
+        );
+        assert_eq!(contract.get_device_owner(device_id).unwrap(), owner);
+        assert!(contract.is_registered(device_id).unwrap());
+    }
+
+    #[test]
+    fn test_verification_memo_keeps_device_checks() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract
+            .set_policy(
+                U256::ZERO,
+                U256::ZERO,
+                U256::from(60),
+                U256::from(MAX_ATTESTATION_LEN),
+                U256::ZERO,
+            )
+            .unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        contract.set_verification_memo(true).unwrap();
+        assert!(contract.get_verification_memo().unwrap());
+        vm.set_block_number(7);
+        vm.set_block_timestamp(1_000);
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+
+        // Same block, same payload: only the signature verdict is reused,
+        // so the rate limit still applies and each call is counted
+        assert!(contract
+            .verify_tee_attestation(device_id, payload.clone())
+            .unwrap());
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, payload.clone()),
+            Err(VerifierError::VerificationTooFrequent(_))
+        ));
+        assert!(matches!(
+            contract.verify_tee_attestation_strict(device_id, payload),
+            Err(VerifierError::VerificationTooFrequent(_))
+        ));
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(1));
+    }
+
+    #[test]
+    fn test_verification_memo_does_not_outlive_revocation() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        contract.set_verification_memo(true).unwrap();
+        vm.set_block_number(7);
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+
+        assert!(contract
+            .verify_tee_attestation(device_id, payload.clone())
+            .unwrap());
+        assert!(contract
+            .verify_tee_attestation(device_id, payload.clone())
+            .unwrap());
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(2));
+
+        // Revoked in the same block: the repeat is rejected, not replayed
+        contract.revoke_devices_batch(vec![device_id]).unwrap();
+        assert!(!contract
+            .verify_tee_attestation(device_id, payload.clone())
+            .unwrap());
+        assert_eq!(
+            contract
+                .verify_tee_attestation_strict(device_id, payload)
+                .unwrap(),
+            STATUS_REJECTED
+        );
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(2));
+    }
+
+    #[test]
+    fn test_owner_setters_emit_config_changed() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let last_config_change = || {
+            let (topics, data) = vm.get_emitted_logs().last().unwrap().clone();
+            assert_eq!(topics[0], ConfigChanged::SIGNATURE_HASH);
+            (
+                topics[1],
+                U256::from_be_slice(&data[..32]),
+                U256::from_be_slice(&data[32..]),
+            )
+        };
+
+        let set_threshold = |contract: &mut CertIDVerifier, threshold: u64| {
+            contract
+                .set_policy(
+                    U256::from(threshold),
+                    U256::ZERO,
+                    U256::ZERO,
+                    U256::ZERO,
+                    U256::ZERO,
+                )
+                .unwrap();
+        };
+        set_threshold(&mut contract, 60);
+        set_threshold(&mut contract, 75);
+        // `set_policy` logs each field in order; required_claims comes last
+        let logs = vm.get_emitted_logs();
+        let (topics, data) = &logs[logs.len() - 5];
+        assert_eq!(topics[1], config::MIN_THRESHOLD);
+        assert_eq!(U256::from_be_slice(&data[..32]), U256::from(60));
+        assert_eq!(U256::from_be_slice(&data[32..]), U256::from(75));
+        assert_eq!(last_config_change().0, config::REQUIRED_CLAIMS);
+
+        // Inverted flags log the parameter as exposed, not as stored
+        contract.set_verification_enabled(false).unwrap();
+        assert_eq!(
+            last_config_change(),
+            (config::VERIFICATION_ENABLED, U256::from(1), U256::ZERO)
+        );
+
+        let oracle = Address::repeat_byte(0x77);
+        contract.set_policy_oracle(oracle).unwrap();
+        assert_eq!(
+            last_config_change(),
+            (
+                config::POLICY_ORACLE,
+                U256::ZERO,
+                config::address_value(oracle)
+            )
+        );
+    }
+
+    #[test]
+    fn test_rebuild_aggregates_matches_incremental_totals() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        for (i, score) in [90u64, 45, 100, 0, 73].into_iter().enumerate() {
+            let device_id = FixedBytes::<32>::repeat_byte(i as u8 + 1);
+            contract.register_device(device_id, owner).unwrap();
+            contract
+                .update_trust_score(device_id, U256::from(score))
+                .unwrap();
+        }
+        let admin = vm.msg_sender();
+        vm.set_sender(owner);
+        contract
+            .deregister_device(FixedBytes::<32>::repeat_byte(0x02), false)
+            .unwrap();
+        vm.set_sender(admin);
+        let (acme, globex) = (FixedBytes::repeat_byte(0xac), FixedBytes::repeat_byte(0x61));
+        contract
+            .set_manufacturer_weight(acme, U256::from(3))
+            .unwrap();
+        contract
+            .set_manufacturer_weight(globex, U256::from(1))
+            .unwrap();
+        for (i, manufacturer) in [(1u8, acme), (3, acme), (5, globex)] {
+            contract
+                .set_device_manufacturer(FixedBytes::repeat_byte(i), manufacturer)
+                .unwrap();
+        }
+        let stats = contract.get_aggregate_stats().unwrap();
+        let histogram = contract.get_score_histogram().unwrap();
+        let fleet_trust = contract.get_fleet_trust().unwrap();
+
+        // Simulate drift, then rebuild two devices at a time
+        contract.trust_score_sum.set(U256::from(7));
+        contract
+            .score_histogram
+            .setter(U256::from(9))
+            .set(U256::ZERO);
+        contract.weighted_score_sum.set(U256::from(7));
+        contract.total_trust_weight.set(U256::from(1));
+        contract
+            .manufacturer_score_sums
+            .setter(globex)
+            .set(U256::from(7));
+        let mut next = contract
+            .rebuild_aggregates(U256::ZERO, U256::from(2))
+            .unwrap();
+        assert_eq!(next, U256::from(2));
+        assert_eq!(contract.get_aggregate_stats().unwrap().1, U256::from(7));
+        assert!(matches!(
+            contract.rebuild_aggregates(U256::from(3), U256::from(2)),
+            Err(VerifierError::IndexOutOfBounds(_))
+        ));
+        while !next.is_zero() {
+            next = contract.rebuild_aggregates(next, U256::from(2)).unwrap();
+        }
+        assert_eq!(contract.get_aggregate_stats().unwrap(), stats);
+        assert_eq!(contract.get_score_histogram().unwrap(), histogram);
+        assert_eq!(contract.get_fleet_trust().unwrap(), fleet_trust);
+
+        // Per-manufacturer totals are rebuilt too, so a weight change
+        // afterwards lands on the right sums: acme alone averages 90 and 100
+        contract
+            .set_manufacturer_weight(globex, U256::ZERO)
+            .unwrap();
+        assert_eq!(contract.get_fleet_trust().unwrap(), U256::from(95));
+
+        // A rebuild can be restarted from scratch
+        contract.rebuild_aggregates(U256::ZERO, U256::ZERO).unwrap();
+        assert_eq!(contract.get_aggregate_stats().unwrap(), stats);
+
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.rebuild_aggregates(U256::ZERO, U256::ZERO),
+            Err(VerifierError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_model_allowlist_enforced_when_enabled() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        let (pixel, unknown) = ([0xa1; 32], [0xa2; 32]);
+        contract.add_allowed_model(FixedBytes::from(pixel)).unwrap();
+        let attest = |model| cbor::tests::encode_attestation_of_model([0xab; 32], 1, 0, model);
+
+        // Not enforced yet: any model verifies
+        assert!(contract
+            .verify_tee_attestation(device_id, attest(unknown))
+            .unwrap());
+
+        contract.set_model_enforcement(true).unwrap();
+        assert!(contract
+            .verify_tee_attestation(device_id, attest(pixel))
+            .unwrap());
+        for payload in [attest(unknown), Vec::new()] {
+            assert!(matches!(
+                contract.verify_tee_attestation(device_id, payload),
+                Err(VerifierError::ModelNotAllowed(_))
+            ));
+        }
+
+        contract
+            .remove_allowed_model(FixedBytes::from(pixel))
+            .unwrap();
+        assert!(!contract.is_model_allowed(FixedBytes::from(pixel)).unwrap());
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, attest(pixel)),
+            Err(VerifierError::ModelNotAllowed(_))
+        ));
+        assert!(matches!(
+            contract.add_allowed_model(FixedBytes::ZERO),
+            Err(VerifierError::InvalidPolicy(_))
+        ));
+    }
+
+    #[test]
+    fn test_swap_device_owners_keeps_owner_lists_consistent() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+
+        let (alice, bob) = (Address::repeat_byte(0xa1), Address::repeat_byte(0xb0));
+        let ids: Vec<_> = (1..=5).map(FixedBytes::<32>::repeat_byte).collect();
+        for (i, device_id) in ids.iter().enumerate() {
+            let owner = if i < 3 { alice } else { bob };
+            contract.register_device(*device_id, owner).unwrap();
+        }
+        let owned = |contract: &CertIDVerifier, owner| {
+            contract
+                .get_owner_devices(owner, U256::ZERO, U256::ZERO)
+                .unwrap()
+                .0
+        };
+
+        contract.swap_device_owners(ids[1], ids[4]).unwrap();
+        assert_eq!(contract.get_device_owner(ids[1]).unwrap(), bob);
+        assert_eq!(contract.get_device_owner(ids[4]).unwrap(), alice);
+        assert_eq!(owned(&contract, alice), vec![ids[0], ids[4], ids[2]]);
+        assert_eq!(owned(&contract, bob), vec![ids[3], ids[1]]);
+
+        // Positions followed the swap, so removals still find the right slot
+        vm.set_sender(bob);
+        contract.deregister_device(ids[1], false).unwrap();
+        vm.set_sender(alice);
+        contract.deregister_device(ids[0], false).unwrap();
+        assert_eq!(owned(&contract, alice), vec![ids[2], ids[4]]);
+        assert_eq!(owned(&contract, bob), vec![ids[3]]);
+        assert!(matches!(
+            contract.swap_device_owners(ids[0], ids[3]),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        vm.set_sender(admin);
+        assert!(matches!(
+            contract.swap_device_owners(ids[0], ids[3]),
+            Err(VerifierError::DeviceNotRegistered(_))
+        ));
+    }
+
+    #[test]
+    fn test_unregistered_device_fails_simulation_and_verification() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        // Scored but never registered
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+
+        assert_eq!(
+            contract
+                .simulate_verification(device_id, payload.clone())
+                .unwrap(),
+            (false, true, true, true, true, false)
+        );
+        assert!(!contract
+            .verify_tee_attestation(device_id, payload.clone())
+            .unwrap());
+        assert_eq!(
+            contract
+                .verify_tee_attestation_strict(device_id, payload)
+                .unwrap(),
+            STATUS_REJECTED
+        );
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_simulate_verification_isolates_failing_gate() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract
+            .set_policy(
+                U256::from(50),
+                U256::from(600),
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+            )
+            .unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<_> = (1..=4).map(FixedBytes::<32>::repeat_byte).collect();
+        vm.set_block_timestamp(1_000);
+        for device_id in &ids {
+            contract.register_device(*device_id, owner).unwrap();
+            contract
+                .update_trust_score(*device_id, U256::from(80))
+                .unwrap();
+        }
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+        let simulate = |contract: &CertIDVerifier, device_id| {
+            contract
+                .simulate_verification(device_id, payload.clone())
+                .unwrap()
+        };
+
+        // ids[0] passes; ids[1] scores low, ids[2] is revoked, ids[3] is
+        // stale after its peers are refreshed
+        contract.update_trust_score(ids[1], U256::from(40)).unwrap();
+        contract.revoke_devices_batch(vec![ids[2]]).unwrap();
+        vm.set_block_timestamp(1_500);
+        for device_id in &ids[..3] {
+            contract
+                .update_trust_score(*device_id, contract.get_device_trust(*device_id).unwrap())
+                .unwrap();
+        }
+        vm.set_block_timestamp(1_700);
+
+        assert_eq!(
+            simulate(&contract, ids[0]),
+            (true, true, true, true, true, true)
+        );
+        assert_eq!(
+            simulate(&contract, ids[1]),
+            (true, false, true, true, true, false)
+        );
+        assert_eq!(
+            simulate(&contract, ids[2]),
+            (true, true, true, false, true, false)
+        );
+        assert_eq!(
+            simulate(&contract, ids[3]),
+            (true, true, true, true, false, false)
+        );
+
+        // The simulation agrees with the real call, which it does not affect
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::ZERO);
+        assert!(contract
+            .verify_tee_attestation(ids[0], payload.clone())
+            .unwrap());
+        assert!(!contract
+            .verify_tee_attestation(ids[3], payload.clone())
+            .unwrap());
+
+        // Checks outside the reported gates only show in `final`
+        contract.pause().unwrap();
+        assert_eq!(
+            simulate(&contract, ids[0]),
+            (true, true, true, true, true, false)
+        );
+    }
+
+    #[test]
+    fn test_beacon_must_be_a_recent_block_hash() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        contract.set_beacon_window(U256::from(16)).unwrap();
+
+        // ArbSys reports block 1_000 as current and knows two hashes
+        let word = |value: U256| value.to_be_bytes::<32>().to_vec();
+        vm.mock_static_call(
+            interfaces::ARB_SYS,
+            interfaces::IArbSys::arbBlockNumberCall {}.abi_encode(),
+            Ok(word(U256::from(1_000))),
+        );
+        for (block, hash) in [(990u64, [0xb1; 32]), (980, [0xb2; 32])] {
+            vm.mock_static_call(
+                interfaces::ARB_SYS,
+                interfaces::IArbSys::arbBlockHashCall {
+                    arbBlockNum: U256::from(block),
+                }
+                .abi_encode(),
+                Ok(hash.to_vec()),
+            );
+        }
+        let attest = |block, hash| {
+            cbor::tests::encode_attestation_with_beacon([0xab; 32], 1, 0, block, hash)
+        };
+
+        assert!(contract
+            .verify_tee_attestation(device_id, attest(990, [0xb1; 32]))
+            .unwrap());
+        for (payload, block) in [
+            (attest(990, [0xee; 32]), 990),
+            (attest(980, [0xb2; 32]), 980),
+            (attest(1_000, [0xb1; 32]), 1_000),
+            (cbor::tests::encode_attestation([0xab; 32], 1, 0), 0),
+        ] {
+            assert!(matches!(
+                contract.verify_tee_attestation(device_id, payload),
+                Err(VerifierError::StaleBeacon(StaleBeacon { block: b })) if b == U256::from(block)
+            ));
+        }
+
+        assert!(matches!(
+            contract.set_beacon_window(U256::from(257)),
+            Err(VerifierError::InvalidPolicy(_))
+        ));
+    }
+
+    #[test]
+    fn test_set_all_scores_updates_one_page_at_a_time() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<_> = (1..=5).map(FixedBytes::<32>::repeat_byte).collect();
+        for device_id in &ids {
+            contract.register_device(*device_id, owner).unwrap();
+            contract
+                .update_trust_score(*device_id, U256::from(20))
+                .unwrap();
+        }
+        let scores = |contract: &CertIDVerifier| -> Vec<U256> {
+            ids.iter()
+                .map(|id| contract.get_device_trust(*id).unwrap())
+                .collect()
+        };
+
+        let next = contract
+            .set_all_scores(U256::ZERO, U256::from(3), U256::from(500))
+            .unwrap();
+        assert_eq!(next, U256::from(3));
+        let (hundred, twenty) = (U256::from(100), U256::from(20));
+        assert_eq!(
+            scores(&contract),
+            vec![hundred, hundred, hundred, twenty, twenty]
+        );
+
+        let next = contract
+            .set_all_scores(next, U256::from(3), U256::from(500))
+            .unwrap();
+        assert_eq!(next, U256::ZERO);
+        assert_eq!(scores(&contract), vec![hundred; 5]);
+        assert_eq!(contract.get_aggregate_stats().unwrap().1, U256::from(500));
+
+        vm.set_sender(owner);
+        assert!(matches!(
+            contract.set_all_scores(U256::ZERO, U256::ZERO, U256::ZERO),
+            Err(VerifierError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_verification_quota_decrements_and_refills() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+        let relayer = Address::repeat_byte(0x22);
+        contract.add_relayer(relayer).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+
+        // Off by default: verification needs no quota
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        contract.set_quota_enabled(true).unwrap();
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::QuotaExhausted(_))
+        ));
+
+        assert!(matches!(
+            contract.grant_quota(device_id, U256::from(2)),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        vm.set_sender(relayer);
+        contract.grant_quota(device_id, U256::from(2)).unwrap();
+        assert!(matches!(
+            contract.grant_quota(FixedBytes::<32>::repeat_byte(0x02), U256::from(2)),
+            Err(VerifierError::DeviceNotRegistered(_))
+        ));
+
+        // Both verdicts spend quota
+        vm.set_sender(admin);
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        contract.update_trust_score(device_id, U256::ZERO).unwrap();
+        assert!(!contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        assert_eq!(contract.get_device_quota(device_id).unwrap(), U256::ZERO);
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::QuotaExhausted(_))
+        ));
+
+        vm.set_sender(relayer);
+        contract.grant_quota(device_id, U256::from(1)).unwrap();
+        vm.set_sender(admin);
+        contract
+            .update_trust_score(device_id, U256::from(90))
+            .unwrap();
+        assert!(contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+    }
+
+    /// Mock `ecrecover` for the key-derivation input of `key`, answering
+    /// with what real ECDSA recovery of that input yields.
+    fn mock_key_derivation(vm: &TestVM, key: &SigningKey) -> Vec<u8> {
+        let secret = key.to_bytes().to_vec();
+        let input = signature::secret_key_ecrecover_input(&secret).unwrap();
+        let signature = k256::ecdsa::Signature::from_slice(&input[64..]).unwrap();
+        let recovered = k256::ecdsa::VerifyingKey::recover_from_prehash(
+            &[0u8; 32],
+            &signature,
+            k256::ecdsa::RecoveryId::from_byte(input[63] - 27).unwrap(),
+        )
+        .unwrap();
+        let point = recovered.to_encoded_point(false);
+        let mut output = vec![0u8; 12];
+        output.extend_from_slice(&keccak256(&point.as_bytes()[1..])[12..]);
+        vm.mock_static_call(signature::ECRECOVER, input.to_vec(), Ok(output));
+        secret
+    }
+
+    #[test]
+    fn test_leaked_key_proof_revokes_and_pays_bounty() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract.set_compromise_bounty(U256::from(50)).unwrap();
+        contract.fee_pool.set(U256::from(30));
+        vm.set_balance(vm.contract_address(), U256::from(30));
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let (device_key, other_key) = (signing_key(0x41), signing_key(0x42));
+        contract.register_device(device_id, owner).unwrap();
+        vm.set_sender(owner);
+        contract
+            .set_device_attestation_key(device_id, signer_address(&device_key))
+            .unwrap();
+
+        // The derivation really recovers the key's address
+        let reporter = Address::repeat_byte(0x99);
+        vm.set_sender(reporter);
+        let wrong = mock_key_derivation(&vm, &other_key);
+        assert!(matches!(
+            contract.revoke_with_key_proof(device_id, wrong),
+            Err(VerifierError::KeyProofMismatch(KeyProofMismatch { derived }))
+                if derived == signer_address(&other_key)
+        ));
+        for invalid in [vec![0u8; 32], vec![0xff; 32], vec![0x41; 31]] {
+            assert!(matches!(
+                contract.revoke_with_key_proof(device_id, invalid),
+                Err(VerifierError::KeyProofMismatch(_))
+            ));
+        }
+        assert!(!contract.is_revoked(device_id).unwrap());
+
+        let leaked = mock_key_derivation(&vm, &device_key);
+        contract
+            .revoke_with_key_proof(device_id, leaked.clone())
+            .unwrap();
+        assert!(contract.is_revoked(device_id).unwrap());
+        assert_eq!(
+            vm.get_emitted_logs().last().unwrap().0[0],
+            DeviceRevoked::SIGNATURE_HASH
+        );
+        // The bounty is capped at the pool, and paid only once
+        assert_eq!(vm.balance(reporter), U256::from(30));
+        assert_eq!(contract.get_fee_pool().unwrap(), U256::ZERO);
+        contract.revoke_with_key_proof(device_id, leaked).unwrap();
+        assert_eq!(contract.get_revoked_count().unwrap(), U256::from(1));
+    }
+
+    #[test]
+    fn test_ewma_converges_toward_verification_outcomes() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        assert!(matches!(
+            contract.set_ewma_alpha(U256::from(EWMA_SCALE + 1)),
+            Err(VerifierError::InvalidPolicy(_))
+        ));
+        contract.set_ewma_alpha(U256::from(EWMA_SCALE / 2)).unwrap();
+        assert_eq!(
+            contract.get_ewma_alpha().unwrap(),
+            U256::from(EWMA_SCALE / 2)
+        );
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(60))
+            .unwrap();
+
+        let verify = |contract: &mut CertIDVerifier| {
+            contract
+                .verify_tee_attestation(device_id, Vec::new())
+                .unwrap();
+            contract.get_device_trust(device_id).unwrap()
+        };
+        for expected in [80, 90, 95, 98, 99, 100, 100] {
+            assert_eq!(verify(&mut contract), U256::from(expected));
+        }
+        assert_eq!(contract.get_aggregate_stats().unwrap().1, U256::from(100));
+
+        // Revoked devices fail verification, but a third party's failures
+        // leave the score alone
+        contract.revoke_devices_batch(vec![device_id]).unwrap();
+        let admin = vm.msg_sender();
+        vm.set_sender(Address::repeat_byte(0x99));
+        assert_eq!(verify(&mut contract), U256::from(100));
+        assert_eq!(verify(&mut contract), U256::from(100));
+
+        // The owner's failures drag it down
+        vm.set_sender(admin);
+        for expected in [50, 25, 12, 6, 3, 1, 0, 0] {
+            assert_eq!(verify(&mut contract), U256::from(expected));
+        }
+
+        contract.set_ewma_alpha(U256::ZERO).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(60))
+            .unwrap();
+        assert_eq!(verify(&mut contract), U256::from(60));
+    }
+
+    #[test]
+    fn test_revoked_count_ignores_repeat_revocations() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<_> = (1..=3).map(FixedBytes::<32>::repeat_byte).collect();
+        for id in &ids {
+            contract.register_device(*id, owner).unwrap();
+        }
+        assert_eq!(contract.get_revoked_count().unwrap(), U256::ZERO);
+
+        contract
+            .revoke_devices_batch(vec![ids[0], ids[1], ids[0]])
+            .unwrap();
+        assert_eq!(contract.get_revoked_count().unwrap(), U256::from(2));
+        contract.revoke_devices_batch(vec![ids[1]]).unwrap();
+        assert_eq!(contract.get_revoked_count().unwrap(), U256::from(2));
+
+        // Revocation outlives the registration, and so does the count
+        vm.set_sender(owner);
+        contract.deregister_device(ids[0], false).unwrap();
+        assert!(contract.is_revoked(ids[0]).unwrap());
+        assert_eq!(contract.get_revoked_count().unwrap(), U256::from(2));
+    }
+
+    #[test]
+    fn test_digest_verification_binds_device_and_nonce() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_key = signing_key(0x41);
+        let (device_id, keyless) = (
+            FixedBytes::<32>::repeat_byte(0x01),
+            FixedBytes::<32>::repeat_byte(0x02),
+        );
+        for id in [device_id, keyless] {
+            contract.register_device(id, owner).unwrap();
+            contract.update_trust_score(id, U256::from(80)).unwrap();
+        }
+        vm.set_sender(owner);
+        contract
+            .set_device_attestation_key(device_id, signer_address(&device_key))
+            .unwrap();
+
+        // A digest not bound to this device's next nonce is refused
+        let expected = contract.attestation_digest_for(device_id).unwrap();
+        let other = contract.attestation_digest_for(keyless).unwrap();
+        let signature = sign(&vm, &device_key, other);
+        assert!(matches!(
+            contract.verify_tee_attestation_digest(device_id, other, signature),
+            Err(VerifierError::DigestMismatch(DigestMismatch { expected: e })) if e == expected
+        ));
+
+        // Only the device key's signature over the expected digest passes
+        let forged = sign(&vm, &signing_key(0x42), expected);
+        assert!(!contract
+            .verify_tee_attestation_digest(device_id, expected, forged)
+            .unwrap());
+        let signature = sign(&vm, &device_key, expected);
+        assert!(contract
+            .verify_tee_attestation_digest(device_id, expected, signature.clone())
+            .unwrap());
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(1));
+
+        // The pass consumed the nonce, so the same digest cannot be replayed
+        let next = contract.attestation_digest_for(device_id).unwrap();
+        assert_ne!(next, expected);
+        assert!(matches!(
+            contract.verify_tee_attestation_digest(device_id, expected, signature),
+            Err(VerifierError::DigestMismatch(_))
+        ));
+
+        // Keyless devices have nothing to check a digest signature against
+        let signature = sign(&vm, &device_key, other);
+        assert!(!contract
+            .verify_tee_attestation_digest(keyless, other, signature)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_payments_stop_at_balance_cap() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract
+            .set_verification_fee(U256::ZERO, U256::from(10))
+            .unwrap();
+        contract.set_max_contract_balance(U256::from(25)).unwrap();
+        assert_eq!(contract.get_max_contract_balance().unwrap(), U256::from(25));
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        // The test VM does not credit msg_value, so credit it by hand
+        let this = vm.contract_address();
+        let pay = |contract: &mut CertIDVerifier, value: u64| {
+            vm.set_value(U256::from(value));
+            vm.set_balance(this, vm.balance(this) + U256::from(value));
+            contract.verify_tee_attestation(device_id, Vec::new())
+        };
+        assert!(pay(&mut contract, 10).unwrap());
+        assert!(pay(&mut contract, 10).unwrap());
+        assert!(matches!(
+            pay(&mut contract, 10),
+            Err(VerifierError::BalanceCapExceeded(_))
+        ));
+        assert!(matches!(
+            contract.register_device(
+                FixedBytes::<32>::repeat_byte(0x02),
+                Address::repeat_byte(0x11)
+            ),
+            Err(VerifierError::BalanceCapExceeded(_))
+        ));
+
+        // Lifting the cap lets payments through again
+        contract.set_max_contract_balance(U256::ZERO).unwrap();
+        assert!(pay(&mut contract, 10).unwrap());
+    }
+
+    #[test]
+    fn test_epoch_verifications_are_per_epoch_deltas() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract.add_relayer(vm.msg_sender()).unwrap();
+        // Pilot payloads carry epoch 0
+        contract.set_epoch_grace(U256::from(10)).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        let verify = |contract: &mut CertIDVerifier, times: usize| {
+            for _ in 0..times {
+                assert!(contract
+                    .verify_tee_attestation(device_id, Vec::new())
+                    .unwrap());
+            }
+        };
+        let per_epoch = |contract: &CertIDVerifier| {
+            (0..5u64)
+                .map(|epoch| contract.get_epoch_verifications(U256::from(epoch)).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        verify(&mut contract, 2);
+        contract.set_current_epoch(1).unwrap();
+        verify(&mut contract, 3);
+        // Epoch 2 passes without verifications
+        contract.set_current_epoch(3).unwrap();
+        verify(&mut contract, 1);
+        assert_eq!(
+            per_epoch(&contract),
+            [2, 3, 0, 1, 0].map(U256::from).to_vec()
+        );
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(6));
+
+        // An epoch change is only noticed at the next counted verification
+        contract.set_current_epoch(4).unwrap();
+        assert_eq!(
+            contract.get_epoch_verifications(U256::from(3)).unwrap(),
+            U256::from(1)
+        );
+        verify(&mut contract, 2);
+        assert_eq!(
+            per_epoch(&contract),
+            [2, 3, 0, 1, 2].map(U256::from).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_verify_all_is_all_or_nothing() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<_> = (1..=3).map(FixedBytes::<32>::repeat_byte).collect();
+        for id in &ids {
+            contract.register_device(*id, owner).unwrap();
+            contract.update_trust_score(*id, U256::from(80)).unwrap();
+        }
+        contract.revoke_devices_batch(vec![ids[2]]).unwrap();
+        let payloads = |count: usize| vec![Vec::new(); count];
+
+        assert!(matches!(
+            contract.verify_all(ids.clone(), payloads(2)),
+            Err(VerifierError::LengthMismatch(_))
+        ));
+
+        // The revoked third device fails the whole call, untouched
+        assert!(!contract.verify_all(ids.clone(), payloads(3)).unwrap());
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::ZERO);
+        for id in &ids {
+            assert_eq!(contract.get_failure_count(*id).unwrap(), U256::ZERO);
+        }
+
+        assert!(contract.verify_all(ids[..2].to_vec(), payloads(2)).unwrap());
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(2));
+    }
+
+    #[test]
+    fn test_signatures_accept_both_recovery_id_conventions() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_key = signing_key(0x41);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        vm.set_sender(owner);
+        contract
+            .set_device_attestation_key(device_id, signer_address(&device_key))
+            .unwrap();
+
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+        let signed = sign_attestation(&vm, &contract, &device_key, device_id, payload);
+        let v = signed.len() - 1;
+        assert!(signed[v] == 27 || signed[v] == 28);
+        let mut raw_id = signed.clone();
+        raw_id[v] -= 27;
+        assert!(contract.verify_tee_attestation(device_id, signed).unwrap());
+        assert!(contract
+            .verify_tee_attestation(device_id, raw_id.clone())
+            .unwrap());
+
+        // Any other recovery id is no signature at all
+        for bad in [2, 26, 29] {
+            raw_id[v] = bad;
+            assert!(!contract
+                .verify_tee_attestation(device_id, raw_id.clone())
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn test_device_audit_reports_every_field() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        assert_eq!(
+            contract.get_device_audit(device_id).unwrap(),
+            (
+                owner,
+                U256::from(80),
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                0,
+                FixedBytes::ZERO
+            )
+        );
+
+        vm.set_block_timestamp(1_000);
+        let payload = cbor::tests::encode_attestation_with_nonce([0xab; 32], 1, 0, 0);
+        assert!(contract
+            .verify_tee_attestation(device_id, payload.clone())
+            .unwrap());
+        contract.revoke_devices_batch(vec![device_id]).unwrap();
+        assert_eq!(
+            contract.get_device_audit(device_id).unwrap(),
+            (
+                owner,
+                U256::from(80),
+                U256::from(1),
+                U256::from(1_000),
+                U256::from(1),
+                AUDIT_REVOKED,
+                keccak256(&payload)
+            )
+        );
+
+        contract.retire_device(device_id).unwrap();
+        let (.., flags, _) = contract.get_device_audit(device_id).unwrap();
+        assert_eq!(flags, AUDIT_REVOKED | AUDIT_RETIRED);
+    }
+
+    #[test]
+    fn test_key_rotation_requires_continuity_proof_and_refuses_ed25519() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_key = signing_key(0x41);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        vm.set_sender(owner);
+        contract
+            .set_device_attestation_key(device_id, signer_address(&device_key))
+            .unwrap();
+        assert_eq!(
+            contract.get_device_key_scheme(device_id).unwrap(),
+            (
+                KEY_SCHEME_SECP256K1,
+                signer_address(&device_key).into_word()
+            )
+        );
+
+        let new_key = signing_key(0x43);
+        let new_word = signer_address(&new_key).into_word();
+        let proof = |contract: &CertIDVerifier, key: &SigningKey, scheme: u8, word| {
+            let nonce = contract.get_key_rotation_nonce(device_id).unwrap();
+            let digest = contract.key_rotation_digest(device_id, scheme, word, nonce);
+            sign(&vm, key, digest)
+        };
+        assert!(matches!(
+            contract.rotate_key_and_scheme(device_id, 2, new_word, Vec::new()),
+            Err(VerifierError::UnknownKeyScheme(UnknownKeyScheme {
+                scheme: 2
+            }))
+        ));
+
+        // ed25519 has no on-chain verifier, so even a valid proof is refused
+        let ed25519_key = FixedBytes::<32>::repeat_byte(0xed);
+        let signature = proof(&contract, &device_key, KEY_SCHEME_ED25519, ed25519_key);
+        assert!(matches!(
+            contract.rotate_key_and_scheme(device_id, KEY_SCHEME_ED25519, ed25519_key, signature),
+            Err(VerifierError::UnknownKeyScheme(UnknownKeyScheme {
+                scheme: KEY_SCHEME_ED25519
+            }))
+        ));
+
+        // A proof from any key but the current one is refused
+        let forged = proof(
+            &contract,
+            &signing_key(0x42),
+            KEY_SCHEME_SECP256K1,
+            new_word,
+        );
+        assert!(matches!(
+            contract.rotate_key_and_scheme(device_id, KEY_SCHEME_SECP256K1, new_word, forged),
+            Err(VerifierError::BadSignature(_))
+        ));
+
+        let signature = proof(&contract, &device_key, KEY_SCHEME_SECP256K1, new_word);
+        contract
+            .rotate_key_and_scheme(device_id, KEY_SCHEME_SECP256K1, new_word, signature.clone())
+            .unwrap();
+        assert_eq!(
+            contract.get_device_key_scheme(device_id).unwrap(),
+            (KEY_SCHEME_SECP256K1, new_word)
+        );
+
+        // The proof is single-use, and the new key verifies
+        assert!(matches!(
+            contract.rotate_key_and_scheme(device_id, KEY_SCHEME_SECP256K1, new_word, signature),
+            Err(VerifierError::BadSignature(_))
+        ));
+        let payload = cbor::tests::encode_attestation([0xab; 32], 1, 0);
+        let signed = sign_attestation(&vm, &contract, &new_key, device_id, payload);
+        assert!(contract.verify_tee_attestation(device_id, signed).unwrap());
+    }
+
+    #[test]
+    fn test_milestone_fires_at_each_multiple_only() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract.set_milestone_interval(U256::from(3)).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+
+        let mut milestones = Vec::new();
+        for count in 1..=7u64 {
+            let logs = vm.get_emitted_logs().len();
+            assert!(contract
+                .verify_tee_attestation(device_id, Vec::new())
+                .unwrap());
+            if vm.get_emitted_logs()[logs..]
+                .iter()
+                .any(|(topics, _)| topics[0] == MilestoneReached::SIGNATURE_HASH)
+            {
+                milestones.push(count);
+            }
+        }
+        assert_eq!(milestones, [3, 6]);
+        let (topics, data) = vm
+            .get_emitted_logs()
+            .into_iter()
+            .rfind(|(topics, _)| topics[0] == MilestoneReached::SIGNATURE_HASH)
+            .unwrap();
+        assert_eq!(topics[1], device_id);
+        assert_eq!(U256::from_be_slice(&data), U256::from(6));
+    }
+
+    #[test]
+    fn test_registration_restricted_to_configured_shard() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let id = |last: u8| {
+            let mut bytes = [0u8; 32];
+            bytes[0] = 0xff;
+            bytes[31] = last;
+            FixedBytes::<32>::from(bytes)
+        };
+        let shard =
+            |device_id, count: u64| contract.shard_of(device_id, U256::from(count)).unwrap();
+        assert_eq!(shard(id(7), 4), U256::from(3));
+        assert_eq!(shard(id(8), 4), U256::ZERO);
+        assert_eq!(shard(id(7), 1), U256::ZERO);
+        assert_eq!(shard(id(7), 0), U256::ZERO);
+        assert_eq!(
+            shard(FixedBytes::<32>::repeat_byte(0xff), 7),
+            U256::MAX % U256::from(7)
+        );
+
+        assert!(matches!(
+            contract.set_shard(U256::from(4), U256::from(4)),
+            Err(VerifierError::InvalidPolicy(_))
+        ));
+        contract.set_shard(U256::from(3), U256::from(4)).unwrap();
+        let owner = Address::repeat_byte(0x11);
+        contract.register_device(id(7), owner).unwrap();
+        assert!(matches!(
+            contract.register_device(id(8), owner),
+            Err(VerifierError::WrongShard(WrongShard { shard })) if shard == U256::ZERO
+        ));
+
+        // One shard means no restriction
+        contract.set_shard(U256::ZERO, U256::from(1)).unwrap();
+        contract.register_device(id(8), owner).unwrap();
+    }
+
+    #[test]
+    fn test_stake_boosts_effective_trust_up_to_cap() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract.set_stake_per_point(U256::from(100)).unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        assert!(matches!(
+            contract.stake(device_id),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        vm.set_sender(owner);
+        vm.set_value(U256::from(1_050));
+        contract.stake(device_id).unwrap();
+        vm.set_value(U256::ZERO);
+        assert_eq!(
+            contract.get_effective_trust(device_id).unwrap(),
+            U256::from(90)
+        );
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(80)
+        );
+
+        // The boost never takes the score past 100
+        vm.set_value(U256::from(5_000));
+        contract.stake(device_id).unwrap();
+        vm.set_value(U256::ZERO);
+        assert_eq!(
+            contract.get_effective_trust(device_id).unwrap(),
+            U256::from(MAX_TRUST_SCORE)
+        );
+
+        // Unstaking returns the ETH and the boost with it
+        let this = vm.contract_address();
+        vm.set_balance(this, U256::from(6_050));
+        assert!(matches!(
+            contract.unstake(device_id, U256::from(6_051)),
+            Err(VerifierError::InsufficientStake(_))
+        ));
+        contract.unstake(device_id, U256::from(5_960)).unwrap();
+        assert_eq!(vm.balance(owner), U256::from(5_960));
+        assert_eq!(
+            contract.get_device_stake(device_id).unwrap(),
+            (U256::from(90), U256::from(100))
+        );
+        assert_eq!(
+            contract.get_effective_trust(device_id).unwrap(),
+            U256::from(80)
+        );
+
+        // Deregistration returns whatever is left
+        contract.deregister_device(device_id, false).unwrap();
+        assert_eq!(vm.balance(owner), U256::from(6_050));
+        assert_eq!(contract.get_device_stake(device_id).unwrap().0, U256::ZERO);
+    }
+
+    #[test]
+    fn test_attestations_must_carry_current_epoch_seed() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract.add_relayer(vm.msg_sender()).unwrap();
+        contract.set_epoch_grace(U256::from(10)).unwrap();
+        contract.set_require_epoch_seed(true).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        let with_seed =
+            |seed: u8| cbor::tests::encode_attestation_with_seed([0xab; 32], 1, 0, [seed; 32]);
+
+        // No seed published yet for epoch 0
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, with_seed(0x5e)),
+            Err(VerifierError::StaleSeed(StaleSeed { epoch })) if epoch.is_zero()
+        ));
+        contract
+            .set_epoch_seed(0, FixedBytes::repeat_byte(0x5e))
+            .unwrap();
+        contract
+            .set_epoch_seed(1, FixedBytes::repeat_byte(0x5f))
+            .unwrap();
+        assert!(contract
+            .verify_tee_attestation(device_id, with_seed(0x5e))
+            .unwrap());
+
+        // Once the epoch moves on, the previous seed no longer verifies
+        contract.set_current_epoch(1).unwrap();
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, with_seed(0x5e)),
+            Err(VerifierError::StaleSeed(_))
+        ));
+        assert!(matches!(
+            contract.set_epoch_seed(0, FixedBytes::repeat_byte(0x5e)),
+            Err(VerifierError::StaleEpoch(_))
+        ));
+        assert!(contract
+            .verify_tee_attestation(device_id, with_seed(0x5f))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_score_to_u8_rejects_out_of_range_scores() {
+        assert_eq!(CertIDVerifier::score_to_u8(U256::ZERO).unwrap(), 0);
+        assert_eq!(
+            CertIDVerifier::score_to_u8(U256::from(MAX_TRUST_SCORE)).unwrap(),
+            100
+        );
+        for score in [U256::from(MAX_TRUST_SCORE + 1), U256::from(256), U256::MAX] {
+            assert!(matches!(
+                CertIDVerifier::score_to_u8(score),
+                Err(VerifierError::InvalidScore(InvalidScore { score: s })) if s == score
+            ));
+        }
+
+        // Without an input scale, raw scores must already be in range
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        assert!(matches!(
+            contract.update_trust_score(device_id, U256::from(356)),
+            Err(VerifierError::InvalidScore(_))
+        ));
+        assert_eq!(contract.get_score_histogram().unwrap()[0], U256::from(1));
+    }
+
+    #[test]
+    fn test_relayer_verify_and_update_is_atomic() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        assert!(matches!(
+            contract.relayer_verify_and_update(
+                device_id,
+                Vec::new(),
+                U256::from(90),
+                U256::from(1)
+            ),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        contract.add_relayer(vm.msg_sender()).unwrap();
+
+        contract
+            .relayer_verify_and_update(device_id, Vec::new(), U256::from(90), U256::from(1))
+            .unwrap();
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(90)
+        );
+        assert_eq!(contract.get_bridge_seq(device_id).unwrap(), U256::from(1));
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(1));
+
+        // A replayed sequence is rejected before anything is verified
+        assert!(matches!(
+            contract.relayer_verify_and_update(
+                device_id,
+                Vec::new(),
+                U256::from(95),
+                U256::from(1)
+            ),
+            Err(VerifierError::StaleSequence(_))
+        ));
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(1));
+
+        // A failing attestation reverts the whole call, score and sequence
+        // included
+        contract.revoke_devices_batch(vec![device_id]).unwrap();
+        assert!(matches!(
+            contract.relayer_verify_and_update(device_id, Vec::new(), U256::from(95), U256::from(2)),
+            Err(VerifierError::AttestationNotVerified(AttestationNotVerified { status, .. }))
+                if status == STATUS_REJECTED
+        ));
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(90)
+        );
+        assert_eq!(contract.get_bridge_seq(device_id).unwrap(), U256::from(1));
+        assert_eq!(contract.get_total_verifications().unwrap(), U256::from(1));
+    }
+
+    #[test]
+    fn test_relayer_verify_and_update_honours_score_controls() {
+        let vm = TestVM::default();
+        vm.set_block_timestamp(1_000);
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        contract.add_relayer(vm.msg_sender()).unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        let update = |contract: &mut CertIDVerifier, id, score: u64, seq: u64| {
+            contract.relayer_verify_and_update(id, Vec::new(), U256::from(score), U256::from(seq))
+        };
+        assert!(matches!(
+            update(&mut contract, device_id, 90, 1),
+            Err(VerifierError::DeviceNotRegistered(_))
+        ));
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(60))
+            .unwrap();
+
+        // The delta cap clamps the relayer's score like any other update
+        contract.set_max_score_delta(U256::from(5)).unwrap();
+        update(&mut contract, device_id, 100, 1).unwrap();
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(65)
+        );
+        contract.set_max_score_delta(U256::ZERO).unwrap();
+
+        // Scores arrive on the input scale
+        contract.set_input_scale(U256::from(1000)).unwrap();
+        update(&mut contract, device_id, 700, 2).unwrap();
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(70)
+        );
+        assert!(matches!(
+            update(&mut contract, device_id, 1001, 3),
+            Err(VerifierError::InvalidScore(_))
+        ));
+        contract.set_input_scale(U256::ZERO).unwrap();
+
+        // With a consensus threshold, only consensus may move scores
+        contract
+            .set_validator_set(vec![Address::repeat_byte(0x77)], U256::from(1))
+            .unwrap();
+        assert!(matches!(
+            update(&mut contract, device_id, 100, 3),
+            Err(VerifierError::ConsensusRequired(_))
+        ));
+        assert_eq!(
+            contract.get_device_trust(device_id).unwrap(),
+            U256::from(70)
+        );
+        assert_eq!(contract.get_bridge_seq(device_id).unwrap(), U256::from(2));
+    }
+
+    #[test]
+    fn test_score_range_follows_moving_extremes() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        assert_eq!(
+            contract.get_score_range().unwrap(),
+            (U256::ZERO, U256::ZERO)
+        );
+
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<_> = (1..=3).map(FixedBytes::<32>::repeat_byte).collect();
+        for (id, score) in ids.iter().zip([40, 60, 80]) {
+            contract.register_device(*id, owner).unwrap();
+            contract.update_trust_score(*id, U256::from(score)).unwrap();
+        }
+        let range = |contract: &CertIDVerifier| {
+            let (min, max) = contract.get_score_range().unwrap();
+            (min.to::<u64>(), max.to::<u64>())
+        };
+        assert_eq!(range(&contract), (40, 80));
+
+        // Moving an extreme inward rescans to the next held score
+        contract.update_trust_score(ids[0], U256::from(70)).unwrap();
+        assert_eq!(range(&contract), (60, 80));
+        contract.update_trust_score(ids[2], U256::from(50)).unwrap();
+        assert_eq!(range(&contract), (50, 70));
+        // Moving one outward just extends the range
+        contract
+            .update_trust_score(ids[1], U256::from(100))
+            .unwrap();
+        assert_eq!(range(&contract), (50, 100));
+        contract.update_trust_score(ids[1], U256::ZERO).unwrap();
+        assert_eq!(range(&contract), (0, 70));
+
+        // A shared extreme survives losing one of its holders
+        contract.update_trust_score(ids[2], U256::from(70)).unwrap();
+        contract.update_trust_score(ids[0], U256::from(30)).unwrap();
+        assert_eq!(range(&contract), (0, 70));
+
+        vm.set_sender(owner);
+        contract.deregister_device(ids[1], true).unwrap();
+        assert_eq!(range(&contract), (30, 70));
+        contract.deregister_device(ids[0], true).unwrap();
+        contract.deregister_device(ids[2], true).unwrap();
+        assert_eq!(range(&contract), (0, 0));
+    }
+
+    #[test]
+    fn test_devices_manufactured_before_cutoff_are_refused() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        let cutoff = 1_600_000_000;
+        let made_at = |date: u64| {
+            cbor::tests::encode_attestation_with_manufacture_date([0xab; 32], 1, 0, date)
+        };
+
+        // No cutoff: any date, or none, is fine
+        assert!(contract
+            .verify_tee_attestation(device_id, made_at(1))
+            .unwrap());
+
+        contract
+            .set_min_manufacture_date(U256::from(cutoff))
+            .unwrap();
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, made_at(cutoff - 1)),
+            Err(VerifierError::DeviceTooOld(DeviceTooOld { manufactured_at }))
+                if manufactured_at == cutoff - 1
+        ));
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, Vec::new()),
+            Err(VerifierError::DeviceTooOld(_))
+        ));
+        assert!(contract
+            .verify_tee_attestation(device_id, made_at(cutoff))
+            .unwrap());
+        assert!(contract
+            .verify_tee_attestation(device_id, made_at(cutoff + 1))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_reassign_owner_moves_fleet_in_pages() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let (alice, bob) = (Address::repeat_byte(0xa1), Address::repeat_byte(0xb0));
+        let ids: Vec<_> = (1..=5).map(FixedBytes::<32>::repeat_byte).collect();
+        for id in &ids {
+            contract.register_device(*id, alice).unwrap();
+        }
+        let kept = FixedBytes::<32>::repeat_byte(0x09);
+        contract.register_device(kept, bob).unwrap();
+        assert!(matches!(
+            contract.reassign_owner(alice, Address::ZERO, U256::ZERO),
+            Err(VerifierError::InvalidOwner(_))
+        ));
+
+        let mut pages = 0;
+        let mut remaining = U256::from(ids.len());
+        while !remaining.is_zero() {
+            remaining = contract.reassign_owner(alice, bob, U256::from(2)).unwrap();
+            pages += 1;
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(vm.get_emitted_logs().len(), ids.len());
+
+        assert_eq!(contract.get_owner_device_count(alice).unwrap(), U256::ZERO);
+        assert_eq!(contract.get_owner_device_count(bob).unwrap(), U256::from(6));
+        let (owned, _) = contract
+            .get_owner_devices(bob, U256::ZERO, U256::ZERO)
+            .unwrap();
+        assert_eq!(owned[0], kept);
+        for id in &ids {
+            assert!(owned.contains(id));
+            assert_eq!(contract.get_device_owner(*id).unwrap(), bob);
+        }
+
+        // Positions stay consistent: bob can still drop devices cleanly
+        vm.set_sender(bob);
+        contract.deregister_device(ids[0], true).unwrap();
+        contract.deregister_device(kept, true).unwrap();
+        let (owned, _) = contract
+            .get_owner_devices(bob, U256::ZERO, U256::ZERO)
+            .unwrap();
+        assert_eq!(owned.len(), 4);
+        assert!(!owned.contains(&ids[0]) && !owned.contains(&kept));
+    }
+
+    #[test]
+    fn test_revocation_outranks_other_failures() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract
+            .register_device(device_id, Address::repeat_byte(0x11))
+            .unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(10))
+            .unwrap();
+        contract
+            .set_policy(
+                U256::from(50),
+                U256::ZERO,
+                U256::ZERO,
+                U256::from(128),
+                U256::ZERO,
+            )
+            .unwrap();
+        assert_eq!(
+            contract
+                .verify_tee_attestation_strict(device_id, Vec::new())
+                .unwrap(),
+            STATUS_BAD_SIGNATURE
+        );
+
+        // Revoked, unsigned and below threshold: revocation decides
+        contract.revoke_devices_batch(vec![device_id]).unwrap();
+        assert_eq!(
+            contract
+                .verify_tee_attestation_strict(device_id, Vec::new())
+                .unwrap(),
+            STATUS_REJECTED
+        );
+        assert!(!contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+
+        // An exhausted quota and an unpaid fee would revert an active
+        // device; a revoked one is rejected before either is checked
+        contract.set_quota_enabled(true).unwrap();
+        contract
+            .set_verification_fee(U256::ZERO, U256::from(1_000))
+            .unwrap();
+        assert_eq!(
+            contract
+                .verify_tee_attestation_strict(device_id, Vec::new())
+                .unwrap(),
+            STATUS_REJECTED
+        );
+        assert!(!contract
+            .verify_tee_attestation(device_id, Vec::new())
+            .unwrap());
+        assert_eq!(contract.get_fee_pool().unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_get_device_attestation_key() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let (keyed, unkeyed) = (
+            FixedBytes::<32>::repeat_byte(0x01),
+            FixedBytes::<32>::repeat_byte(0x02),
+        );
+        contract.register_device(keyed, owner).unwrap();
+        contract.register_device(unkeyed, owner).unwrap();
+        let key = Address::repeat_byte(0x42);
+        vm.set_sender(owner);
+        contract.set_device_attestation_key(keyed, key).unwrap();
+
+        assert_eq!(contract.get_device_attestation_key(keyed).unwrap(), key);
+        assert_eq!(
+            contract.get_device_attestation_key(unkeyed).unwrap(),
+            Address::ZERO
+        );
+    }
+
+    #[test]
+    fn test_fleet_trust_weights_scores_by_manufacturer() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        assert_eq!(contract.get_fleet_trust().unwrap(), U256::ZERO);
+
+        let (acme, globex) = (FixedBytes::repeat_byte(0xac), FixedBytes::repeat_byte(0x61));
+        let owner = Address::repeat_byte(0x11);
+        let ids: Vec<_> = (1..=3).map(FixedBytes::<32>::repeat_byte).collect();
+        for (id, score) in ids.iter().zip([90, 70, 40]) {
+            contract.register_device(*id, owner).unwrap();
+            contract.update_trust_score(*id, U256::from(score)).unwrap();
+        }
+        contract.set_device_manufacturer(ids[0], acme).unwrap();
+        contract.set_device_manufacturer(ids[1], acme).unwrap();
+        contract.set_device_manufacturer(ids[2], globex).unwrap();
+        // Assigned but unweighted: still nothing to average
+        assert_eq!(contract.get_fleet_trust().unwrap(), U256::ZERO);
+
+        // (3·90 + 3·70 + 1·40) / (3 + 3 + 1) = 520 / 7
+        contract
+            .set_manufacturer_weight(acme, U256::from(3))
+            .unwrap();
+        contract
+            .set_manufacturer_weight(globex, U256::from(1))
+            .unwrap();
+        assert_eq!(contract.get_fleet_trust().unwrap(), U256::from(74));
+
+        // Score updates move the KPI without any rescan
+        contract
+            .update_trust_score(ids[2], U256::from(100))
+            .unwrap();
+        assert_eq!(contract.get_fleet_trust().unwrap(), U256::from(82));
+        contract.set_manufacturer_weight(acme, U256::ZERO).unwrap();
+        assert_eq!(contract.get_fleet_trust().unwrap(), U256::from(100));
+
+        vm.set_sender(owner);
+        contract.deregister_device(ids[2], true).unwrap();
+        assert_eq!(contract.get_fleet_trust().unwrap(), U256::ZERO);
+        assert_eq!(
+            contract.get_device_manufacturer(ids[2]).unwrap(),
+            FixedBytes::ZERO
+        );
+    }
+
+    #[test]
+    fn test_attestations_chain_through_intermediate_ca() {
+        let vm = TestVM::default();
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let device_key = signing_key(0x41);
+        let intermediate_key = signing_key(0x61);
+        let root_key = signing_key(0x51);
+        let rogue_key = signing_key(0x52);
+        let admin = vm.msg_sender();
+        contract
+            .add_trusted_root(keccak256(signer_address(&root_key)))
+            .unwrap();
+        let device_id = FixedBytes::<32>::repeat_byte(0x01);
+        contract.register_device(device_id, owner).unwrap();
+        contract
+            .update_trust_score(device_id, U256::from(80))
+            .unwrap();
+        vm.set_sender(owner);
+        contract
+            .set_device_attestation_key(device_id, signer_address(&device_key))
+            .unwrap();
+
+        // device ← intermediate ← `issuer`
+        let chained = |contract: &CertIDVerifier, issuer: &SigningKey| {
+            let leaf = contract.endorsement_digest(device_id, signer_address(&device_key));
+            let mut chain = sign(&vm, &intermediate_key, leaf);
+            let ca = contract.ca_digest(signer_address(&intermediate_key));
+            chain.extend(sign(&vm, issuer, ca));
+            let payload = cbor::tests::encode_attestation_with_chain([0xab; 32], 1, 0, &chain);
+            sign_attestation(&vm, contract, &device_key, device_id, payload)
+        };
+
+        let valid = chained(&contract, &root_key);
+        assert!(
+            contract
+                .simulate_verification(device_id, valid.clone())
+                .unwrap()
+                .2
+        );
+        assert!(contract.verify_tee_attestation(device_id, valid).unwrap());
+
+        // The intermediate is signed by a key that is not a trusted root
+        let broken = chained(&contract, &rogue_key);
+        assert!(
+            !contract
+                .simulate_verification(device_id, broken.clone())
+                .unwrap()
+                .2
+        );
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, broken),
+            Err(VerifierError::InvalidCertChain(_))
+        ));
+
+        // Malformed and overlong chains are rejected before any recovery
+        for chain in [
+            vec![0u8; 64],
+            vec![0u8; signature::SIGNATURE_LEN * (MAX_CERT_CHAIN_LEN + 1)],
+        ] {
+            let payload = cbor::tests::encode_attestation_with_chain([0xab; 32], 1, 0, &chain);
+            let data = sign_attestation(&vm, &contract, &device_key, device_id, payload);
+            assert!(matches!(
+                contract.verify_tee_attestation(device_id, data),
+                Err(VerifierError::InvalidCertChain(_))
+            ));
+        }
+
+        // With self-signed attestations disallowed, a chain to a trusted
+        // root stands in for the trailing root endorsement
+        vm.set_sender(admin);
+        contract.set_allow_self_signed(false).unwrap();
+        let valid = chained(&contract, &root_key);
+        assert!(
+            contract
+                .simulate_verification(device_id, valid.clone())
+                .unwrap()
+                .2
+        );
+        assert!(contract.verify_tee_attestation(device_id, valid).unwrap());
+        assert!(matches!(
+            contract.verify_tee_attestation(device_id, chained(&contract, &rogue_key)),
+            Err(VerifierError::InvalidCertChain(_))
+        ));
+        let unchained = sign_attestation(
+            &vm,
+            &contract,
+            &device_key,
+            device_id,
+            cbor::tests::encode_attestation([0xab; 32], 1, 0),
+        );
+        assert!(!contract
+            .verify_tee_attestation(device_id, unchained)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_renounce_waits_for_timelock_then_disables_owner() {
+        let vm = TestVM::default();
+        vm.set_block_timestamp(1_000);
+        let mut contract = CertIDVerifier::from(&vm);
+        contract.initialize().unwrap();
+        let admin = vm.msg_sender();
+
+        assert!(matches!(
+            contract.finalize_renounce(),
+            Err(VerifierError::RenounceNotReady(RenounceNotReady { eta })) if eta.is_zero()
+        ));
+        contract.initiate_renounce().unwrap();
+        let eta = U256::from(1_000 + DEFAULT_RENOUNCE_TIMELOCK);
+        assert_eq!(
+            contract.get_pending_renounce().unwrap(),
+            (eta, U256::from(DEFAULT_RENOUNCE_TIMELOCK))
+        );
+        vm.set_block_timestamp(1_000 + DEFAULT_RENOUNCE_TIMELOCK - 1);
+        assert!(matches!(
+            contract.finalize_renounce(),
+            Err(VerifierError::RenounceNotReady(_))
+        ));
+        assert_eq!(contract.owner().unwrap(), admin);
+        vm.set_sender(Address::repeat_byte(0x77));
+        assert!(matches!(
+            contract.initiate_renounce(),
+            Err(VerifierError::Unauthorized(_))
+        ));
+
+        vm.set_sender(admin);
+        vm.set_block_timestamp(1_000 + DEFAULT_RENOUNCE_TIMELOCK);
+        contract.finalize_renounce().unwrap();
+        assert_eq!(contract.owner().unwrap(), Address::ZERO);
+        assert_eq!(contract.get_pending_renounce().unwrap().0, U256::ZERO);
+        assert!(contract.uptime_seconds().unwrap() > U256::ZERO);
+        assert!(matches!(
+            contract.set_default_trust_score(U256::from(50)),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            contract.pause(),
+            Err(VerifierError::Unauthorized(_))
+        ));
+        // Nobody can claim the contract back through initialize
+        assert!(matches!(
+            contract.initialize(),
+            Err(VerifierError::AlreadyInitialized(_))
+        ));
     }
 }
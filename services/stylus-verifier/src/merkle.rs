@@ -0,0 +1,182 @@
+// Merkle inclusion proofs against a state root committed from the Cosmos Hub.
+//
+// Trees use sorted-pair hashing: a parent is `H(min(a, b) || max(a, b))`,
+// so a proof is just the list of sibling hashes from leaf to root, with no
+// left/right flags. The hash `H` is selected by `merkle_hash_algo` to match
+// whatever the L1 tree uses:
+//
+//   0  keccak256 (native host hash)
+//   1  sha256    (EVM precompile at 0x02)
+//
+// Leaves are supplied already hashed; how a leaf is derived from device
+// state is up to the caller. The one leaf this contract derives itself is a
+// device's removal marker, which L1 commits once the device is removed:
+//
+//   removal leaf = H("CERTID_REMOVED" || device_id)
+//
+// and a removal proof is its sibling hashes concatenated, 32 bytes each,
+// leaf to root.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{address, Address, B256},
+    prelude::*,
+    stylus_core::calls::context::Call,
+};
+
+/// `merkle_hash_algo` values.
+pub(crate) const HASH_KECCAK256: u64 = 0;
+pub(crate) const HASH_SHA256: u64 = 1;
+
+/// Address of the `sha256` precompile.
+pub(crate) const SHA256: Address = address!("0000000000000000000000000000000000000002");
+
+/// Domain tag of a device's removal leaf.
+const REMOVAL_PREFIX: &[u8] = b"CERTID_REMOVED";
+
+/// `H(input)`, or `None` if `algo` is unknown or the precompile call fails.
+fn hash(vm: &dyn Host, algo: u64, input: &[u8]) -> Option<B256> {
+    match algo {
+        HASH_KECCAK256 => Some(vm.native_keccak256(input)),
+        HASH_SHA256 => {
+            let output = vm.static_call(&Call::new(), SHA256, input).ok()?;
+            (output.len() == 32).then(|| B256::from_slice(&output))
+        }
+        _ => None,
+    }
+}
+
+/// `H(min(a, b) || max(a, b))`, or `None` if `algo` is unknown or the
+/// precompile call fails.
+pub(crate) fn hash_pair(vm: &dyn Host, algo: u64, a: B256, b: B256) -> Option<B256> {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut input = [0u8; 64];
+    input[..32].copy_from_slice(lo.as_slice());
+    input[32..].copy_from_slice(hi.as_slice());
+    hash(vm, algo, &input)
+}
+
+/// The leaf L1 commits once `device_id` is removed.
+pub(crate) fn removal_leaf(vm: &dyn Host, algo: u64, device_id: B256) -> Option<B256> {
+    let mut input = Vec::with_capacity(REMOVAL_PREFIX.len() + 32);
+    input.extend_from_slice(REMOVAL_PREFIX);
+    input.extend_from_slice(device_id.as_slice());
+    hash(vm, algo, &input)
+}
+
+/// Split a packed proof into its 32-byte sibling hashes, or `None` if the
+/// length is not a multiple of 32.
+pub(crate) fn decode_proof(proof: &[u8]) -> Option<Vec<B256>> {
+    if !proof.len().is_multiple_of(32) {
+        return None;
+    }
+    Some(proof.chunks_exact(32).map(B256::from_slice).collect())
+}
+
+/// Whether `proof` links `leaf` to `root` under `algo`.
+pub(crate) fn verify_proof(
+    vm: &dyn Host,
+    algo: u64,
+    leaf: B256,
+    proof: &[B256],
+    root: B256,
+) -> bool {
+    let mut node = leaf;
+    for sibling in proof {
+        match hash_pair(vm, algo, node, *sibling) {
+            Some(parent) => node = parent,
+            None => return false,
+        }
+    }
+    node == root
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+    use stylus_sdk::testing::*;
+
+    /// Compute `H(a, b)` off-chain, mocking the sha256 precompile so the
+    /// test VM can answer the on-chain call.
+    pub(crate) fn parent(vm: &TestVM, algo: u64, a: B256, b: B256) -> B256 {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        let input = [lo.as_slice(), hi.as_slice()].concat();
+        match algo {
+            HASH_KECCAK256 => stylus_sdk::alloy_primitives::keccak256(&input),
+            _ => {
+                let output = B256::from_slice(&Sha256::digest(&input));
+                vm.mock_static_call(SHA256, input, Ok(output.to_vec()));
+                output
+            }
+        }
+    }
+
+    /// Build a four-leaf tree, returning the root and the proof for leaf 0.
+    pub(crate) fn four_leaf_tree(vm: &TestVM, algo: u64, leaves: [B256; 4]) -> (B256, Vec<B256>) {
+        let left = parent(vm, algo, leaves[0], leaves[1]);
+        let right = parent(vm, algo, leaves[2], leaves[3]);
+        (parent(vm, algo, left, right), Vec::from([leaves[1], right]))
+    }
+
+    fn leaves() -> [B256; 4] {
+        [1, 2, 3, 4].map(B256::repeat_byte)
+    }
+
+    #[test]
+    fn test_proofs_verify_only_under_matching_algorithm() {
+        let vm = TestVM::default();
+        let (keccak_root, keccak_proof) = four_leaf_tree(&vm, HASH_KECCAK256, leaves());
+        let (sha_root, sha_proof) = four_leaf_tree(&vm, HASH_SHA256, leaves());
+        assert_ne!(keccak_root, sha_root);
+
+        let leaf = leaves()[0];
+        assert!(verify_proof(
+            &vm,
+            HASH_KECCAK256,
+            leaf,
+            &keccak_proof,
+            keccak_root
+        ));
+        assert!(verify_proof(&vm, HASH_SHA256, leaf, &sha_proof, sha_root));
+        assert!(!verify_proof(
+            &vm,
+            HASH_KECCAK256,
+            leaf,
+            &sha_proof,
+            sha_root
+        ));
+        assert!(!verify_proof(
+            &vm,
+            HASH_SHA256,
+            leaf,
+            &keccak_proof,
+            keccak_root
+        ));
+    }
+
+    #[test]
+    fn test_rejects_wrong_leaf_and_unknown_algorithm() {
+        let vm = TestVM::default();
+        let (root, proof) = four_leaf_tree(&vm, HASH_KECCAK256, leaves());
+        assert!(!verify_proof(
+            &vm,
+            HASH_KECCAK256,
+            leaves()[2],
+            &proof,
+            root
+        ));
+        assert!(!verify_proof(&vm, 2, leaves()[0], &proof, root));
+    }
+
+    #[test]
+    fn test_decode_proof_splits_whole_hashes() {
+        let packed = [[0x01; 32], [0x02; 32]].concat();
+        assert_eq!(
+            decode_proof(&packed),
+            Some(Vec::from([B256::repeat_byte(1), B256::repeat_byte(2)]))
+        );
+        assert_eq!(decode_proof(&[]), Some(Vec::new()));
+        assert_eq!(decode_proof(&packed[..40]), None);
+    }
+}
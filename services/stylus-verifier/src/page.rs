@@ -0,0 +1,80 @@
+// Gas-bounded pagination for the enumeration views.
+//
+// Paged views take `(start_index, limit)` and return
+// `(results, next_index)`. A caller keeps passing `next_index` back as
+// `start_index` until it comes back as 0, which marks the end of the
+// enumeration. `limit` is capped at `MAX_PAGE_LIMIT` (0 selects the cap),
+// so no single call's gas grows with the fleet.
+//
+// Enumerations use swap-remove, so a removal between calls can move an
+// entry from the tail into an already visited slot.
+
+use core::ops::Range;
+use stylus_sdk::alloy_primitives::U256;
+
+/// Largest number of entries one paged call examines.
+pub(crate) const MAX_PAGE_LIMIT: usize = 100;
+
+/// The slice of an enumeration a paged call covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PageCursor {
+    start: usize,
+    end: usize,
+    len: usize,
+}
+
+impl PageCursor {
+    /// Cursor over `[start, start + limit)` of an enumeration of `len`
+    /// entries, clamped to its end.
+    pub(crate) fn new(start: U256, limit: U256, len: usize) -> Self {
+        let limit = match usize::try_from(limit) {
+            Ok(0) | Err(_) => MAX_PAGE_LIMIT,
+            Ok(limit) => limit.min(MAX_PAGE_LIMIT),
+        };
+        let start = usize::try_from(start).unwrap_or(len).min(len);
+        let end = start.saturating_add(limit).min(len);
+        Self { start, end, len }
+    }
+
+    /// Indices this page covers.
+    pub(crate) fn indices(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Where the next page starts, or 0 once the enumeration is exhausted.
+    pub(crate) fn next_index(&self) -> U256 {
+        if self.end >= self.len {
+            U256::ZERO
+        } else {
+            U256::from(self.end)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(start: u64, limit: u64, len: usize) -> (Range<usize>, U256) {
+        let cursor = PageCursor::new(U256::from(start), U256::from(limit), len);
+        (cursor.indices(), cursor.next_index())
+    }
+
+    #[test]
+    fn test_pages_cover_enumeration_and_end_with_zero() {
+        assert_eq!(page(0, 4, 10), (0..4, U256::from(4)));
+        assert_eq!(page(8, 4, 10), (8..10, U256::ZERO));
+        assert_eq!(page(10, 4, 10), (10..10, U256::ZERO));
+        assert_eq!(page(50, 4, 10), (10..10, U256::ZERO));
+        assert_eq!(page(0, 4, 0), (0..0, U256::ZERO));
+    }
+
+    #[test]
+    fn test_limit_is_capped() {
+        let len = MAX_PAGE_LIMIT * 3;
+        assert_eq!(page(0, 0, len).0.len(), MAX_PAGE_LIMIT);
+        assert_eq!(page(0, u64::MAX, len).0.len(), MAX_PAGE_LIMIT);
+        let cursor = PageCursor::new(U256::ZERO, U256::MAX, len);
+        assert_eq!(cursor.next_index(), U256::from(MAX_PAGE_LIMIT));
+    }
+}
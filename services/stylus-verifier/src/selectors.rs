@@ -0,0 +1,293 @@
+// 4-byte selectors of every public method, for ABI introspection.
+//
+// The router generated by `#[public]` keeps its selectors private, so this
+// list mirrors the public impl in `lib.rs` by hand: each entry names the
+// method in camelCase with its Rust argument types, exactly as the router
+// derives it. Add an entry whenever a public method is added.
+
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, U256},
+    function_selector,
+};
+
+/// Selectors of the contract's public methods, in declaration order.
+pub(crate) const FUNCTION_SELECTORS: &[[u8; 4]] = &[
+    function_selector!("initialize"),
+    function_selector!("setSuccessor", Address),
+    function_selector!("getSuccessor"),
+    function_selector!("owner"),
+    function_selector!("setRenounceTimelock", U256),
+    function_selector!("initiateRenounce"),
+    function_selector!("cancelRenounce"),
+    function_selector!("finalizeRenounce"),
+    function_selector!("getPendingRenounce"),
+    function_selector!("pause"),
+    function_selector!("unpause"),
+    function_selector!("paused"),
+    function_selector!("setScoreDecreaseAlarm", U256),
+    function_selector!("getScoreDecreaseAlarm"),
+    function_selector!("scoreDecreasesThisEpoch"),
+    function_selector!("setVerificationEnabled", bool),
+    function_selector!("isVerificationEnabled"),
+    function_selector!("setRequireOnchainChallenge", bool),
+    function_selector!("isOnchainChallengeRequired"),
+    function_selector!("setAllowSelfSigned", bool),
+    function_selector!("isSelfSignedAllowed"),
+    function_selector!("setAllowEmptyPayload", bool),
+    function_selector!("isEmptyPayloadAllowed"),
+    function_selector!("setPolicyOracle", Address),
+    function_selector!("getPolicyOracle"),
+    function_selector!("setPreserveOnReregister", bool),
+    function_selector!("isPreserveOnReregister"),
+    function_selector!("setAcceptZeroScore", bool),
+    function_selector!("isZeroScoreAccepted"),
+    function_selector!("setDefaultTrustScore", U256),
+    function_selector!("getDefaultTrustScore"),
+    function_selector!("setAggregator", Address),
+    function_selector!("getAggregator"),
+    function_selector!("setPolicy", U256, U256, U256, U256, U256),
+    function_selector!("setInputScale", U256),
+    function_selector!("setRoundingMode", U256),
+    function_selector!("setMaxScoreDelta", U256),
+    function_selector!("getMaxScoreDelta"),
+    function_selector!("setEwmaAlpha", U256),
+    function_selector!("getEwmaAlpha"),
+    function_selector!("getScoreScaling"),
+    function_selector!("setTrustTiers", U256, U256),
+    function_selector!("getTrustTiers"),
+    function_selector!("addRelayer", Address),
+    function_selector!("removeRelayer", Address),
+    function_selector!("relayerHeartbeat"),
+    function_selector!("grantQuota", FixedBytes<32>, U256),
+    function_selector!("getDeviceQuota", FixedBytes<32>),
+    function_selector!("setQuotaEnabled", bool),
+    function_selector!("isQuotaEnabled"),
+    function_selector!("setMaxRelayerSilence", U256),
+    function_selector!("setMinRelayers", U256),
+    function_selector!("getMinRelayers"),
+    function_selector!("setCurrentEpoch", u64),
+    function_selector!("setEpochGrace", U256),
+    function_selector!("getEpoch"),
+    function_selector!("getEpochVerifications", U256),
+    function_selector!("getRelayerLiveness"),
+    function_selector!("setStateRoot", FixedBytes<32>),
+    function_selector!("setMerkleHashAlgo", U256),
+    function_selector!("setVerificationLogCapacity", U256),
+    function_selector!("getStateRoot"),
+    function_selector!("verifyStateProof", FixedBytes<32>, Vec<FixedBytes<32>>),
+    function_selector!("isRelayer", Address),
+    function_selector!("getRelayerCount"),
+    function_selector!("rotateRelayerKey", Address),
+    function_selector!("setRelayerTimelock", U256),
+    function_selector!("proposeRelayer", Address),
+    function_selector!("executeRelayerChange"),
+    function_selector!("getPendingRelayer"),
+    function_selector!("setKeyRotationGrace", U256),
+    function_selector!("getRelayerKeys"),
+    function_selector!("setValidatorSet", Vec<Address>, U256),
+    function_selector!("getValidatorSet"),
+    function_selector!("addBridgeKey", Address),
+    function_selector!("removeBridgeKey", Address),
+    function_selector!("isBridgeKey", Address),
+    function_selector!("setRegistrationDeposit", Address, U256),
+    function_selector!("getRegistrationDeposit"),
+    function_selector!("stake", FixedBytes<32>),
+    function_selector!("unstake", FixedBytes<32>, U256),
+    function_selector!("setStakePerPoint", U256),
+    function_selector!("getDeviceStake", FixedBytes<32>),
+    function_selector!("getEffectiveTrust", FixedBytes<32>),
+    function_selector!("setVerificationFee", U256, U256),
+    function_selector!("getVerificationFee", U256),
+    function_selector!("getFeePool"),
+    function_selector!("withdrawFees", Address, U256),
+    function_selector!("setMaxContractBalance", U256),
+    function_selector!("getMaxContractBalance"),
+    function_selector!("setDeviceVerificationFee", FixedBytes<32>, U256),
+    function_selector!("getDeviceVerificationFee", FixedBytes<32>),
+    function_selector!("setRewardConfig", Address, U256),
+    function_selector!("getRewardConfig"),
+    function_selector!("addTrustedRoot", FixedBytes<32>),
+    function_selector!("removeTrustedRoot", FixedBytes<32>),
+    function_selector!("isTrustedRoot", FixedBytes<32>),
+    function_selector!("getTrustedRootCount"),
+    function_selector!("getTrustedRootAt", U256),
+    function_selector!("addAllowedModel", FixedBytes<32>),
+    function_selector!("removeAllowedModel", FixedBytes<32>),
+    function_selector!("isModelAllowed", FixedBytes<32>),
+    function_selector!("setModelEnforcement", bool),
+    function_selector!("isModelEnforced"),
+    function_selector!("setBeaconWindow", U256),
+    function_selector!("getBeaconWindow"),
+    function_selector!("setEpochSeed", u64, FixedBytes<32>),
+    function_selector!("getEpochSeed", U256),
+    function_selector!("setRequireEpochSeed", bool),
+    function_selector!("isEpochSeedRequired"),
+    function_selector!("setMinManufactureDate", U256),
+    function_selector!("getMinManufactureDate"),
+    function_selector!("getPolicy"),
+    function_selector!("setClassPolicy", U256, U256, U256),
+    function_selector!("getClassPolicy", U256),
+    function_selector!("shardOf", FixedBytes<32>, U256),
+    function_selector!("setShard", U256, U256),
+    function_selector!("getShard"),
+    function_selector!("registerDevice", FixedBytes<32>, Address),
+    function_selector!("registerDeviceAuthorized", FixedBytes<32>, Address, Vec<u8>),
+    function_selector!(
+        "registerDeviceWithCommitment",
+        FixedBytes<32>,
+        Address,
+        FixedBytes<32>
+    ),
+    function_selector!("registerDeviceWithClass", FixedBytes<32>, Address, U256),
+    function_selector!("deregisterDevice", FixedBytes<32>, bool),
+    function_selector!("deregisterWithProof", FixedBytes<32>, Vec<u8>),
+    function_selector!("retireDevice", FixedBytes<32>),
+    function_selector!("swapDeviceOwners", FixedBytes<32>, FixedBytes<32>),
+    function_selector!("reassignOwner", Address, Address, U256),
+    function_selector!("revokeDevicesBatch", Vec<FixedBytes<32>>),
+    function_selector!("revokeWithKeyProof", FixedBytes<32>, Vec<u8>),
+    function_selector!("setCompromiseBounty", U256),
+    function_selector!("getCompromiseBounty"),
+    function_selector!("setVerificationOverride", FixedBytes<32>, u8),
+    function_selector!("setDeviceAttestationKey", FixedBytes<32>, Address),
+    function_selector!("setDeviceKeyGrace", U256),
+    function_selector!("getDevicePreviousKey", FixedBytes<32>),
+    function_selector!(
+        "rotateKeyAndScheme",
+        FixedBytes<32>,
+        u8,
+        FixedBytes<32>,
+        Vec<u8>
+    ),
+    function_selector!("getDeviceKeyScheme", FixedBytes<32>),
+    function_selector!("getKeyRotationNonce", FixedBytes<32>),
+    function_selector!("setDeviceSigners", FixedBytes<32>, Vec<Address>, U256),
+    function_selector!("addDelegateSigner", FixedBytes<32>, Address),
+    function_selector!("getDeviceSigners", FixedBytes<32>),
+    function_selector!("issueChallenge", FixedBytes<32>),
+    function_selector!("getOutstandingChallenge", FixedBytes<32>),
+    function_selector!("setDeviceMetadata", FixedBytes<32>, Vec<u8>),
+    function_selector!("addTag", FixedBytes<32>, FixedBytes<32>),
+    function_selector!("removeTag", FixedBytes<32>, FixedBytes<32>),
+    function_selector!("updateTrustScore", FixedBytes<32>, U256),
+    function_selector!(
+        "updateTrustScoreWithConsensus",
+        FixedBytes<32>,
+        U256,
+        U256,
+        Vec<u8>
+    ),
+    function_selector!("applyBridgeMessage", Vec<u8>, Vec<u8>),
+    function_selector!(
+        "submitScoreCertificate",
+        FixedBytes<32>,
+        U256,
+        U256,
+        Vec<u8>
+    ),
+    function_selector!("stageTrustScore", FixedBytes<32>, U256, U256),
+    function_selector!("commitStagedScore", FixedBytes<32>),
+    function_selector!("getStagedScore", FixedBytes<32>),
+    function_selector!("restoreScore", FixedBytes<32>, U256, Vec<u8>),
+    function_selector!("verifyTeeAttestation", FixedBytes<32>, Vec<u8>),
+    function_selector!("verifyTeeAttestationStrict", FixedBytes<32>, Vec<u8>),
+    function_selector!(
+        "verifyTeeAttestationDigest",
+        FixedBytes<32>,
+        FixedBytes<32>,
+        Vec<u8>
+    ),
+    function_selector!("attestationDigestFor", FixedBytes<32>),
+    function_selector!("simulateVerification", FixedBytes<32>, Vec<u8>),
+    function_selector!("verifyAll", Vec<FixedBytes<32>>, Vec<Vec<u8>>),
+    function_selector!(
+        "relayerVerifyAndUpdate",
+        FixedBytes<32>,
+        Vec<u8>,
+        U256,
+        U256
+    ),
+    function_selector!("setVerificationMemo", bool),
+    function_selector!("getVerificationMemo"),
+    function_selector!("setStrictLivenessWindow", U256),
+    function_selector!("getStrictLivenessWindow"),
+    function_selector!("setNonceWindow", U256),
+    function_selector!("getNonceWindow"),
+    function_selector!("getAttestationNonce", FixedBytes<32>),
+    function_selector!("getHwCounter", FixedBytes<32>),
+    function_selector!("claimReward", FixedBytes<32>),
+    function_selector!("claimRewardWithSig", FixedBytes<32>, Address, Vec<u8>),
+    function_selector!("parseAttestation", Vec<u8>),
+    function_selector!("getDeviceTrust", FixedBytes<32>),
+    function_selector!("hasScore", FixedBytes<32>),
+    function_selector!("getTrustTier", FixedBytes<32>),
+    function_selector!("getDeviceOwner", FixedBytes<32>),
+    function_selector!("getDeviceAttestationKey", FixedBytes<32>),
+    function_selector!("getBridgeSeq", FixedBytes<32>),
+    function_selector!("getPendingReward", FixedBytes<32>),
+    function_selector!("isRetired", FixedBytes<32>),
+    function_selector!("isRevoked", FixedBytes<32>),
+    function_selector!("getRevokedCount"),
+    function_selector!("getVerificationOverride", FixedBytes<32>),
+    function_selector!("getDeviceClass", FixedBytes<32>),
+    function_selector!("getDeviceMetadata", FixedBytes<32>),
+    function_selector!("hasTag", FixedBytes<32>, FixedBytes<32>),
+    function_selector!("getDeviceVerificationCount", FixedBytes<32>),
+    function_selector!("getDeviceAudit", FixedBytes<32>),
+    function_selector!("setMilestoneInterval", U256),
+    function_selector!("getMilestoneInterval"),
+    function_selector!("getFailureCount", FixedBytes<32>),
+    function_selector!("getRegistrationNonce", FixedBytes<32>),
+    function_selector!("getClaimNonce", FixedBytes<32>),
+    function_selector!("getRestoreNonce", FixedBytes<32>),
+    function_selector!("isRegistered", FixedBytes<32>),
+    function_selector!("getRankKey", FixedBytes<32>),
+    function_selector!("getVerificationLogCount"),
+    function_selector!("verificationsInWindow", U256),
+    function_selector!("getVerificationLogAt", U256),
+    function_selector!("getDeviceCount"),
+    function_selector!("getDeviceAt", U256),
+    function_selector!("getDevices", U256, U256),
+    function_selector!("getDevicesBatch", Vec<FixedBytes<32>>),
+    function_selector!("getDeviceNonces", Vec<FixedBytes<32>>),
+    function_selector!("getOwnerDeviceCount", Address),
+    function_selector!("getOwnerDevices", Address, U256, U256),
+    function_selector!("scanProblemDevices", U256, U256, U256, U256),
+    function_selector!("getAggregateStats"),
+    function_selector!("getScoreHistogram"),
+    function_selector!("getScoreRange"),
+    function_selector!("setDeviceManufacturer", FixedBytes<32>, FixedBytes<32>),
+    function_selector!("getDeviceManufacturer", FixedBytes<32>),
+    function_selector!("setManufacturerWeight", FixedBytes<32>, U256),
+    function_selector!("getManufacturerWeight", FixedBytes<32>),
+    function_selector!("getFleetTrust"),
+    function_selector!("setAllScores", U256, U256, U256),
+    function_selector!("rebuildAggregates", U256, U256),
+    function_selector!("snapshotStats"),
+    function_selector!("verifyStatsUnchanged"),
+    function_selector!("resetStatistics"),
+    function_selector!("getTotalVerifications"),
+    function_selector!("getDeployedAt"),
+    function_selector!("uptimeSeconds"),
+    function_selector!("functionSelectors"),
+    function_selector!("domainSeparator"),
+    function_selector!("errorCodeOf", FixedBytes<4>),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_one_selector_per_public_method() {
+        let source = include_str!("lib.rs");
+        let public = source
+            .split("#[public]\nimpl CertIDVerifier {")
+            .nth(1)
+            .unwrap();
+        let public = public.split("\n}\n").next().unwrap();
+        let methods: Vec<_> = public.matches("\n    pub fn ").collect();
+        assert_eq!(methods.len(), FUNCTION_SELECTORS.len());
+    }
+}
@@ -0,0 +1,105 @@
+// ECDSA (secp256k1) helpers built on the EVM `ecrecover` precompile.
+//
+// Recovery is delegated to the precompile rather than compiled into the
+// WASM binary, keeping the contract well under the Stylus size limit.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{address, uint, Address, B256, U256},
+    prelude::*,
+    stylus_core::calls::context::Call,
+};
+
+/// Address of the `ecrecover` precompile.
+pub(crate) const ECRECOVER: Address = address!("0000000000000000000000000000000000000001");
+
+/// Length of an `r || s || v` signature.
+pub(crate) const SIGNATURE_LEN: usize = 65;
+
+/// secp256k1 group order.
+const SECP256K1_N: U256 =
+    uint!(0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141_U256);
+
+/// x-coordinate of the secp256k1 generator G, whose y-coordinate is even.
+const SECP256K1_GX: U256 =
+    uint!(0x79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798_U256);
+
+/// EIP-191 `personal_sign` digest of a 32-byte message hash.
+pub(crate) fn eth_signed_message_hash(vm: &dyn Host, hash: B256) -> B256 {
+    let mut message = Vec::with_capacity(60);
+    message.extend_from_slice(b"\x19Ethereum Signed Message:\n32");
+    message.extend_from_slice(hash.as_slice());
+    vm.native_keccak256(&message)
+}
+
+/// Build the 128-byte `ecrecover` input `hash || v || r || s`, or `None`
+/// when the signature is not 65 bytes with `v` in {0, 1, 27, 28}. Raw
+/// recovery ids 0 and 1 are normalized to the precompile's 27 and 28.
+pub(crate) fn ecrecover_input(digest: B256, signature: &[u8]) -> Option<[u8; 128]> {
+    if signature.len() != SIGNATURE_LEN {
+        return None;
+    }
+    let v = match signature[64] {
+        v @ (0 | 1) => v + 27,
+        v @ (27 | 28) => v,
+        _ => return None,
+    };
+
+    let mut input = [0u8; 128];
+    input[..32].copy_from_slice(digest.as_slice());
+    input[63] = v;
+    input[64..128].copy_from_slice(&signature[..64]);
+    Some(input)
+}
+
+/// Build the `ecrecover` input whose result is the address of the 32-byte
+/// private key `secret`, or `None` if `secret` is not a valid key.
+///
+/// `ecrecover(e, v, r, s)` returns the address of `r⁻¹ · (s·R − e·G)`.
+/// With `R = G` (`r = Gx`, `v = 27` for its even y), `e = 0` and
+/// `s = r·k mod n`, that is `k·G`, so the precompile derives the public
+/// key with no curve arithmetic in the contract. A high `s` is swapped
+/// for `n − s` with `R = −G` (`v = 28`), which recovers the same point.
+pub(crate) fn secret_key_ecrecover_input(secret: &[u8]) -> Option<[u8; 128]> {
+    let k = U256::try_from_be_slice(secret).filter(|_| secret.len() == 32)?;
+    if k.is_zero() || k >= SECP256K1_N {
+        return None;
+    }
+    let mut s = SECP256K1_GX.mul_mod(k, SECP256K1_N);
+    let mut v = 27;
+    if s > SECP256K1_N >> 1 {
+        s = SECP256K1_N - s;
+        v = 28;
+    }
+    let mut input = [0u8; 128];
+    input[63] = v;
+    input[64..96].copy_from_slice(&SECP256K1_GX.to_be_bytes::<32>());
+    input[96..128].copy_from_slice(&s.to_be_bytes::<32>());
+    Some(input)
+}
+
+/// Address of the private key `secret`, or `None` if it is not a valid key.
+pub(crate) fn address_of_secret_key(vm: &dyn Host, secret: &[u8]) -> Option<Address> {
+    let input = secret_key_ecrecover_input(secret)?;
+    let output = vm.static_call(&Call::new(), ECRECOVER, &input).ok()?;
+    if output.len() != 32 {
+        return None;
+    }
+    let address = Address::from_slice(&output[12..]);
+    (!address.is_zero()).then_some(address)
+}
+
+/// Recover the signer of `digest`, or `None` if the signature is malformed
+/// or the precompile cannot recover a key.
+///
+/// A recovered zero address is treated as a failed recovery, so it can
+/// never match a zero-valued key, commitment or unset relayer slot.
+pub(crate) fn recover_signer(vm: &dyn Host, digest: B256, signature: &[u8]) -> Option<Address> {
+    let input = ecrecover_input(digest, signature)?;
+    let output = vm.static_call(&Call::new(), ECRECOVER, &input).ok()?;
+    if output.len() != 32 {
+        return None;
+    }
+    let signer = Address::from_slice(&output[12..]);
+    (!signer.is_zero()).then_some(signer)
+}